@@ -0,0 +1,53 @@
+use brunch::{Bench, Benches};
+use genrepass::PasswordSettings;
+use std::time::Duration;
+
+fn main() {
+    let sentence = "The quick brown fox jumps over the lazy dog. ";
+    let ascii = sentence.repeat(10 * 1024 * 1024 / sentence.len());
+    // Mostly ASCII with a single non-ASCII word at the end, the worst case for the check.
+    let mixed = format!("{ascii}Äpfel");
+
+    println!("Checking 10 MB for non-ASCII:");
+
+    let mut benches = Benches::default();
+
+    benches.push(Bench::new("str::is_ascii: ASCII").run(|| ascii.is_ascii()));
+    benches.push(Bench::new("str::is_ascii: mixed").run(|| mixed.is_ascii()));
+    benches.push(
+        Bench::new("simdutf8 validation: ASCII")
+            .run(|| simdutf8::basic::from_utf8(ascii.as_bytes()).is_ok()),
+    );
+    benches.push(
+        Bench::new("simdutf8 validation: mixed")
+            .run(|| simdutf8::basic::from_utf8(mixed.as_bytes()).is_ok()),
+    );
+
+    benches.finish();
+
+    println!("Extracting words from 10 MB:");
+
+    let mut benches = Benches::default();
+    let mut settings = PasswordSettings::new();
+
+    benches.push(
+        Bench::new("get_words_from_str: ASCII")
+            .with_samples(20)
+            .with_timeout(Duration::from_secs(120))
+            .run(|| {
+                settings.clear_words();
+                settings.get_words_from_str(&ascii);
+            }),
+    );
+    benches.push(
+        Bench::new("get_words_from_str: mixed")
+            .with_samples(20)
+            .with_timeout(Duration::from_secs(120))
+            .run(|| {
+                settings.clear_words();
+                settings.get_words_from_str(&mixed);
+            }),
+    );
+
+    benches.finish();
+}
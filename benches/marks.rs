@@ -1,5 +1,5 @@
 use brunch::{Bench, Benches};
-use genrepass::{CharFilter, Lexicon, PasswordSettings, Split};
+use genrepass::{CharFilter, Lexicon, PasswordSettings, Split, WalkOptions};
 use std::time::Duration;
 
 fn main() {
@@ -70,7 +70,11 @@ Words extracted from:
             .with_samples(200)
             .run(|| {
                 lexicon_license.clear_words();
-                lexicon_license.extract_words_from_path(&["LICENSE"], 0, None, |_| true);
+                lexicon_license.extract_words_from_path_with(
+                    &["LICENSE"],
+                    &WalkOptions::new().depth(0),
+                    |_| true,
+                );
             }),
     );
     benches.push(
@@ -78,10 +82,9 @@ Words extracted from:
             .with_samples(200)
             .run(|| {
                 lexicon_src.clear_words();
-                lexicon_src.extract_words_from_path(
+                lexicon_src.extract_words_from_path_with(
                     &["src"],
-                    1,
-                    None,
+                    &WalkOptions::new().depth(1),
                     CharFilter::AsciiWithoutDigitsOrPunctuation.closure(),
                 );
             }),
@@ -92,10 +95,9 @@ Words extracted from:
             .with_timeout(Duration::from_secs(300))
             .run(|| {
                 lexicon_examples.clear_words();
-                lexicon_examples.extract_words_from_path(
+                lexicon_examples.extract_words_from_path_with(
                     &["examples"],
-                    3,
-                    Some(&["rs", "toml"]),
+                    &WalkOptions::new().depth(3).extensions(["rs", "toml"]),
                     CharFilter::AsciiWithoutDigitsOrPunctuation.closure(),
                 );
             }),
@@ -124,87 +126,72 @@ Words extracted from:
 
     let mut benches = Benches::default();
 
-    ps_license.pass_amount = 1;
-    ps_src.pass_amount = 1;
-    ps_examples.pass_amount = 1;
     benches.push(
         Bench::new(format!("1 from LICENSE ({license_word_len} words)"))
-            .run(|| ps_license.generate().unwrap()),
+            .run(|| ps_license.generate_n(1).unwrap()),
     );
     benches.push(
         Bench::new(format!("1 from src/ ({src_word_len} words)"))
-            .run(|| ps_src.generate().unwrap()),
+            .run(|| ps_src.generate_n(1).unwrap()),
     );
     benches.push(
         Bench::new(format!("1 from examples/ ({examples_word_len} words)"))
-            .run(|| ps_examples.generate().unwrap()),
+            .run(|| ps_examples.generate_n(1).unwrap()),
     );
 
-    ps_license.pass_amount = 10;
-    ps_src.pass_amount = 10;
-    ps_examples.pass_amount = 10;
     benches.push(
         Bench::new(format!("10 from LICENSE ({license_word_len} words)"))
-            .run(|| ps_license.generate().unwrap()),
+            .run(|| ps_license.generate_n(10).unwrap()),
     );
     benches.push(
         Bench::new(format!("10 from src/ ({src_word_len} words)"))
-            .run(|| ps_src.generate().unwrap()),
+            .run(|| ps_src.generate_n(10).unwrap()),
     );
     benches.push(
         Bench::new(format!("10 from examples/ ({examples_word_len} words)"))
-            .run(|| ps_examples.generate().unwrap()),
+            .run(|| ps_examples.generate_n(10).unwrap()),
     );
 
-    ps_license.pass_amount = 100;
-    ps_src.pass_amount = 100;
-    ps_examples.pass_amount = 100;
     benches.push(
         Bench::new(format!("100 from LICENSE ({license_word_len} words)"))
-            .run(|| ps_license.generate().unwrap()),
+            .run(|| ps_license.generate_n(100).unwrap()),
     );
     benches.push(
         Bench::new(format!("100 from src/ ({src_word_len} words)"))
-            .run(|| ps_src.generate().unwrap()),
+            .run(|| ps_src.generate_n(100).unwrap()),
     );
     benches.push(
         Bench::new(format!("100 from examples/ ({examples_word_len} words)"))
-            .run(|| ps_examples.generate().unwrap()),
+            .run(|| ps_examples.generate_n(100).unwrap()),
     );
 
-    ps_license.pass_amount = 1000;
-    ps_src.pass_amount = 1000;
-    ps_examples.pass_amount = 1000;
     benches.push(
         Bench::new(format!("1000 from LICENSE ({license_word_len} words)"))
-            .run(|| ps_license.generate().unwrap()),
+            .run(|| ps_license.generate_n(1000).unwrap()),
     );
     benches.push(
         Bench::new(format!("1000 from src/ ({src_word_len} words)"))
-            .run(|| ps_src.generate().unwrap()),
+            .run(|| ps_src.generate_n(1000).unwrap()),
     );
     benches.push(
         Bench::new(format!("1000 from examples/ ({examples_word_len} words)"))
-            .run(|| ps_examples.generate().unwrap()),
+            .run(|| ps_examples.generate_n(1000).unwrap()),
     );
 
-    ps_license.pass_amount = 10000;
-    ps_src.pass_amount = 10000;
-    ps_examples.pass_amount = 10000;
     benches.push(
         Bench::new(format!("10000 from LICENSE ({license_word_len} words)"))
             .with_timeout(Duration::from_secs(60))
-            .run(|| ps_license.generate().unwrap()),
+            .run(|| ps_license.generate_n(10000).unwrap()),
     );
     benches.push(
         Bench::new(format!("10000 from src/ ({src_word_len} words)"))
             .with_timeout(Duration::from_secs(60))
-            .run(|| ps_src.generate().unwrap()),
+            .run(|| ps_src.generate_n(10000).unwrap()),
     );
     benches.push(
         Bench::new(format!("10000 from examples/ ({examples_word_len} words)"))
             .with_timeout(Duration::from_secs(60))
-            .run(|| ps_examples.generate().unwrap()),
+            .run(|| ps_examples.generate_n(10000).unwrap()),
     );
 
     benches.finish();
@@ -215,87 +202,72 @@ Words extracted from:
 
         let mut benches = Benches::default();
 
-        ps_license.pass_amount = 1;
-        ps_src.pass_amount = 1;
-        ps_examples.pass_amount = 1;
         benches.push(
             Bench::new(format!("1 from LICENSE ({license_word_len} words)"))
-                .run(|| ps_license.generate_parallel().unwrap()),
+                .run(|| ps_license.generate_n_parallel(1).unwrap()),
         );
         benches.push(
             Bench::new(format!("1 from src/ ({src_word_len} words)"))
-                .run(|| ps_src.generate_parallel().unwrap()),
+                .run(|| ps_src.generate_n_parallel(1).unwrap()),
         );
         benches.push(
             Bench::new(format!("1 from examples/ ({examples_word_len} words)"))
-                .run(|| ps_examples.generate_parallel().unwrap()),
+                .run(|| ps_examples.generate_n_parallel(1).unwrap()),
         );
 
-        ps_license.pass_amount = 10;
-        ps_src.pass_amount = 10;
-        ps_examples.pass_amount = 10;
         benches.push(
             Bench::new(format!("10 from LICENSE ({license_word_len} words)"))
-                .run(|| ps_license.generate_parallel().unwrap()),
+                .run(|| ps_license.generate_n_parallel(10).unwrap()),
         );
         benches.push(
             Bench::new(format!("10 from src/ ({src_word_len} words)"))
-                .run(|| ps_src.generate_parallel().unwrap()),
+                .run(|| ps_src.generate_n_parallel(10).unwrap()),
         );
         benches.push(
             Bench::new(format!("10 from examples/ ({examples_word_len} words)"))
-                .run(|| ps_examples.generate_parallel().unwrap()),
+                .run(|| ps_examples.generate_n_parallel(10).unwrap()),
         );
 
-        ps_license.pass_amount = 100;
-        ps_src.pass_amount = 100;
-        ps_examples.pass_amount = 100;
         benches.push(
             Bench::new(format!("100 from LICENSE ({license_word_len} words)"))
-                .run(|| ps_license.generate_parallel().unwrap()),
+                .run(|| ps_license.generate_n_parallel(100).unwrap()),
         );
         benches.push(
             Bench::new(format!("100 from src/ ({src_word_len} words)"))
-                .run(|| ps_src.generate_parallel().unwrap()),
+                .run(|| ps_src.generate_n_parallel(100).unwrap()),
         );
         benches.push(
             Bench::new(format!("100 from examples/ ({examples_word_len} words)"))
-                .run(|| ps_examples.generate_parallel().unwrap()),
+                .run(|| ps_examples.generate_n_parallel(100).unwrap()),
         );
 
-        ps_license.pass_amount = 1000;
-        ps_src.pass_amount = 1000;
-        ps_examples.pass_amount = 1000;
         benches.push(
             Bench::new(format!("1000 from LICENSE ({license_word_len} words)"))
-                .run(|| ps_license.generate_parallel().unwrap()),
+                .run(|| ps_license.generate_n_parallel(1000).unwrap()),
         );
         benches.push(
             Bench::new(format!("1000 from src/ ({src_word_len} words)"))
-                .run(|| ps_src.generate_parallel().unwrap()),
+                .run(|| ps_src.generate_n_parallel(1000).unwrap()),
         );
         benches.push(
             Bench::new(format!("1000 from examples/ ({examples_word_len} words)"))
-                .run(|| ps_examples.generate_parallel().unwrap()),
+                .run(|| ps_examples.generate_n_parallel(1000).unwrap()),
         );
 
-        ps_license.pass_amount = 10000;
-        ps_src.pass_amount = 10000;
-        ps_examples.pass_amount = 10000;
         benches.push(
             Bench::new(format!("10000 from LICENSE ({license_word_len} words)"))
                 .with_timeout(Duration::from_secs(60))
-                .run(|| ps_license.generate_parallel().unwrap()),
+                .run(|| ps_license.generate_n_parallel(10000).unwrap()),
         );
         benches.push(
             Bench::new(format!("10000 from src/ ({src_word_len} words)"))
                 .with_timeout(Duration::from_secs(60))
-                .run(|| ps_src.generate_parallel().unwrap()),
+                .run(|| ps_src.generate_n_parallel(10000).unwrap()),
         );
         benches.push(
             Bench::new(format!("10000 from examples/ ({examples_word_len} words)"))
                 .with_timeout(Duration::from_secs(60))
-                .run(|| ps_examples.generate_parallel().unwrap()),
+                .run(|| ps_examples.generate_n_parallel(10000).unwrap()),
         );
 
         benches.finish();
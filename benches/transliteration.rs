@@ -0,0 +1,74 @@
+use deunicode::deunicode;
+use genrepass::PasswordSettings;
+use regex::Regex;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The system allocator, counting the bytes allocated at the same time.
+struct Counting;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(current, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// The most bytes allocated at once while running `f`, besides what was already allocated.
+fn peak_of(f: impl FnOnce()) -> usize {
+    let before = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    f();
+    PEAK.load(Ordering::Relaxed) - before
+}
+
+fn mb(bytes: usize) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+fn main() {
+    let sentence = "Der Fuchs springt über den faulen Hund, 東京 и быстрая лиса. ";
+    let text = sentence.repeat(10 * 1024 * 1024 / sentence.len());
+
+    println!(
+        "Peak memory extracting words from {:.1} MB of mixed text:",
+        mb(text.len())
+    );
+
+    let mut settings = PasswordSettings::new();
+    let chunked = peak_of(|| {
+        settings.get_words_from_str(&text);
+    });
+    let words = settings.words().len();
+    drop(settings);
+
+    // The words as they were extracted before, transliterating the whole text at once.
+    let re = Regex::new(r"[^\d\W]+").unwrap();
+    let monolithic = peak_of(|| {
+        let converted = deunicode(&text);
+        let words: Vec<String> = re
+            .find_iter(&converted)
+            .map(|word| word.as_str().to_owned())
+            .collect();
+        black_box(words);
+    });
+
+    println!("  {words} words");
+    println!("  in chunks:   {:>5.1} MB", mb(chunked));
+    println!("  all at once: {:>5.1} MB", mb(monolithic));
+}
@@ -1,4 +1,3 @@
-use copypasta_ext::{prelude::ClipboardProvider, x11_bin::ClipboardContext};
 use eframe::{
     egui::{
         Button, CentralPanel, Checkbox, Color32, DragValue, Key, Label, Layout, RichText,
@@ -7,10 +6,13 @@ use eframe::{
     emath::Align,
     get_value, run_native, set_value, App, CreationContext, NativeOptions, Storage, APP_KEY,
 };
-use genrepass::PasswordSettings;
+use genrepass::{is_probably_text_path, PasswordSettings};
 use rfd::FileDialog;
 use serde::{Deserialize, Serialize};
 
+/// Above this many words they're summarised instead of shown as buttons.
+const MAX_WORD_BUTTONS: usize = 1000;
+
 fn main() {
     let native_options = NativeOptions::default();
 
@@ -25,11 +27,14 @@ fn main() {
 #[serde(default)]
 struct Gui {
     settings: PasswordSettings,
+    pass_amount: usize,
     passwords: Vec<String>,
     words_manual_input: String,
     special_chars_manual_input: String,
     special_chars_good: bool,
     word_index_to_remove: Option<usize>,
+    /// The picked files that don't look like text, so they weren't loaded.
+    skipped_files: Vec<String>,
 }
 
 impl Gui {
@@ -38,6 +43,7 @@ impl Gui {
             Some(storage) => get_value(storage, APP_KEY).unwrap_or_default(),
             None => Gui {
                 special_chars_good: true,
+                pass_amount: 1,
                 ..Default::default()
             },
         }
@@ -52,31 +58,27 @@ impl App for Gui {
                 ui.with_layout(Layout::top_down_justified(Align::Center), |ui| {
                     ui.columns(2, |columns| {
                         columns[0].vertical_centered_justified(|ui| {
-                            if self.settings.words().len() <= 1 {
+                            if let Err(e) = self.settings.can_generate() {
                                 ui.add_enabled(false, Button::new("Generate"))
-                                    .on_disabled_hover_text(
-                                        "Must have more than one word for generation",
-                                    );
+                                    .on_disabled_hover_text(e.to_string());
                             } else if ui
                                 .button("Generate")
                                 .on_hover_text("Uses only a single thread")
                                 .clicked()
                             {
-                                self.passwords = self.settings.generate().unwrap();
+                                self.passwords = self.settings.generate_n(self.pass_amount).unwrap();
                             }
                         });
                         columns[1].with_layout(Layout::top_down_justified(Align::Center), |ui| {
-                            if self.settings.words().len() <= 1 {
+                            if let Err(e) = self.settings.can_generate() {
                                 ui.add_enabled(false, Button::new("Generate parallel"))
-                                    .on_disabled_hover_text(
-                                        "Must have more than one word for generation",
-                                    );
+                                    .on_disabled_hover_text(e.to_string());
                             } else if ui
                                 .button("Generate parallel")
                                 .on_hover_text("Uses all available threads")
                                 .clicked()
                             {
-                                self.passwords = self.settings.generate_parallel().unwrap();
+                                self.passwords = self.settings.generate_n_parallel(self.pass_amount).unwrap();
                             }
                         });
                     });
@@ -86,8 +88,7 @@ impl App for Gui {
                                 for password in &self.passwords {
                                     if ui.button(password).on_hover_text("Click to copy").clicked()
                                     {
-                                        let mut ctx = ClipboardContext::new().unwrap();
-                                        ctx.set_contents(password.to_owned()).unwrap();
+                                        genrepass::clipboard::copy(password).unwrap();
                                     }
                                 }
                             });
@@ -105,9 +106,9 @@ impl App for Gui {
                         .on_hover_text("Reset the settings to their defaults")
                         .clicked()
                     {
-                        let words = self.settings.words().join(" ");
+                        let words = self.settings.clone_words();
                         self.settings = Default::default();
-                        self.settings.get_words_from_str(&words);
+                        self.settings.set_words(words);
                     }
                     ui.add_sized(
                         ui.available_size(),
@@ -119,7 +120,7 @@ impl App for Gui {
 
             ui.horizontal(|ui| {
                 ui.add(
-                    DragValue::new(&mut self.settings.pass_amount)
+                    DragValue::new(&mut self.pass_amount)
                         .speed(1)
                         .clamp_range(1..=1000000),
                 );
@@ -230,8 +231,13 @@ impl App for Gui {
                 columns[0].vertical_centered_justified(|ui| {
                     if ui.button("Load words from files").clicked() {
                         if let Some(paths) = FileDialog::new().pick_files() {
+                            self.skipped_files.clear();
                             for path in paths {
-                                self.settings.get_words_from_path(path).unwrap();
+                                if is_probably_text_path(&path, None) {
+                                    self.settings.get_words_from_path(path).unwrap();
+                                } else {
+                                    self.skipped_files.push(path.display().to_string());
+                                }
                             }
                         }
                     }
@@ -254,20 +260,33 @@ impl App for Gui {
                 });
             });
 
+            for file in &self.skipped_files {
+                ui.add_enabled(false, Label::new(format!("Skipped {file}")))
+                    .on_disabled_hover_text("Doesn't look like a text file");
+            }
+
             if let Some(index) = self.word_index_to_remove {
                 self.settings.remove_word_at(index);
                 self.word_index_to_remove = None;
             }
 
-            ScrollArea::vertical().show(ui, |ui| {
-                ui.horizontal_wrapped(|ui| {
-                    for (index, word) in self.settings.words().iter().enumerate() {
-                        if ui.button(word).on_hover_text("Click to remove").clicked() {
-                            self.word_index_to_remove = Some(index);
+            let stats = self.settings.word_stats(false);
+            if stats.count > MAX_WORD_BUTTONS {
+                ui.label(format!(
+                    "{} words ({} bytes), from {} to {} characters long",
+                    stats.count, stats.total_bytes, stats.shortest, stats.longest
+                ));
+            } else {
+                ScrollArea::vertical().show(ui, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        for (index, word) in self.settings.words().iter().enumerate() {
+                            if ui.button(word).on_hover_text("Click to remove").clicked() {
+                                self.word_index_to_remove = Some(index);
+                            }
                         }
-                    }
+                    });
                 });
-            });
+            }
         });
     }
 
@@ -0,0 +1,187 @@
+//! Extract words from a path with a [`Lexicon`], keep the ones of the right length
+//! and generate passwords from them with [`PasswordSettings`].
+//!
+//! ```text
+//! cargo run --example lexicon_cli --features from_path -- ~/Documents/notes --count 5
+//! cargo run --example lexicon_cli --features from_path,schema -- --print-schema
+//! ```
+
+use genrepass::{CharFilter, Deunicode, Lexicon, PasswordSettings, Split, WalkOptions};
+use std::{
+    error::Error,
+    io::{self, Write},
+    process::exit,
+};
+
+const USAGE: &str = "\
+Usage: lexicon_cli <PATH> [OPTIONS]
+       lexicon_cli --print-schema
+
+Options:
+    --split <MODE>        unicode-words, word-bounds, unicode-whitespace or ascii-whitespace
+                          [default: unicode-words]
+    --filter <FILTER>     ascii, ascii-letters, unicode or unicode-letters [default: unicode]
+    --deunicode <STAGE>   off, before-splitting, before-filtering or after-filtering
+                          [default: after-filtering]
+    --min-len <N>         Leave out the words shorter than this [default: 1]
+    --max-len <N>         Leave out the words longer than this [default: unlimited]
+    --count <N>           How many passwords to generate [default: 1]
+    --print-schema        Print the JSON Schema of the settings instead, with the schema feature";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(&args, &mut io::stdout().lock()) {
+        eprintln!("Error: {}.\n\n{}", e, USAGE);
+        exit(1);
+    }
+}
+
+/// What was asked for on the command line.
+struct Args {
+    path: String,
+    split: Split,
+    filter: CharFilter,
+    deunicode: Deunicode,
+    min_len: usize,
+    max_len: usize,
+    count: usize,
+    print_schema: bool,
+}
+
+impl Args {
+    fn parse(args: &[String]) -> Result<Self, Box<dyn Error>> {
+        let mut parsed = Args {
+            path: String::new(),
+            split: Split::UnicodeWords,
+            filter: CharFilter::Unicode,
+            deunicode: Deunicode::AfterFiltering,
+            min_len: 1,
+            max_len: usize::MAX,
+            count: 1,
+            print_schema: false,
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                if !parsed.path.is_empty() {
+                    return Err(format!("unexpected argument '{}'", arg).into());
+                }
+                parsed.path = arg.clone();
+                continue;
+            }
+            if arg == "--print-schema" {
+                parsed.print_schema = true;
+                continue;
+            }
+
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing the value of '{}'", arg))?;
+            let invalid = || format!("invalid value '{}' for '{}'", value, arg);
+            match arg.as_str() {
+                "--split" => {
+                    parsed.split = match value.as_str() {
+                        "unicode-words" => Split::UnicodeWords,
+                        "word-bounds" => Split::WordBounds,
+                        "unicode-whitespace" => Split::UnicodeWhitespace,
+                        "ascii-whitespace" => Split::AsciiWhitespace,
+                        _ => return Err(invalid().into()),
+                    }
+                }
+                "--filter" => {
+                    parsed.filter = match value.as_str() {
+                        "ascii" => CharFilter::Ascii,
+                        "ascii-letters" => CharFilter::AsciiWithoutDigitsOrPunctuation,
+                        "unicode" => CharFilter::Unicode,
+                        "unicode-letters" => CharFilter::UnicodeWithoutNumbersOrAsciiPunctuation,
+                        _ => return Err(invalid().into()),
+                    }
+                }
+                "--deunicode" => {
+                    parsed.deunicode = match value.as_str() {
+                        "off" => Deunicode::Deactivated,
+                        "before-splitting" => Deunicode::BeforeSplitting,
+                        "before-filtering" => Deunicode::BeforeFiltering,
+                        "after-filtering" => Deunicode::AfterFiltering,
+                        _ => return Err(invalid().into()),
+                    }
+                }
+                "--min-len" => parsed.min_len = value.parse().map_err(|_| invalid())?,
+                "--max-len" => parsed.max_len = value.parse().map_err(|_| invalid())?,
+                "--count" => parsed.count = value.parse().map_err(|_| invalid())?,
+                _ => return Err(format!("unknown option '{}'", arg).into()),
+            }
+        }
+
+        if parsed.path.is_empty() && !parsed.print_schema {
+            return Err("missing the path to extract words from".into());
+        }
+        Ok(parsed)
+    }
+}
+
+/// Run the example with the arguments, writing what it prints to `out`.
+pub fn run(args: &[String], out: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let args = Args::parse(args)?;
+    if args.print_schema {
+        return print_schema(out);
+    }
+
+    let mut lexicon = Lexicon::new(args.path.as_str(), args.split);
+    lexicon.deunicode = args.deunicode;
+    let report = lexicon.extract_words_from_path_with(
+        &[&args.path],
+        &WalkOptions::new(),
+        args.filter.closure(),
+    );
+
+    let stats = lexicon.word_stats(true);
+    writeln!(
+        out,
+        "Read {} files, skipped {}",
+        report.read,
+        report.skipped.len()
+    )?;
+    writeln!(
+        out,
+        "Extracted {} words ({} distinct), {} to {} characters long",
+        stats.count,
+        stats.distinct.unwrap_or_default(),
+        stats.shortest,
+        stats.longest
+    )?;
+
+    let words: Vec<String> = lexicon
+        .words()
+        .iter()
+        .filter(|word| (args.min_len..=args.max_len).contains(&word.chars().count()))
+        .cloned()
+        .collect();
+    let mut settings = PasswordSettings::new();
+    settings.set_words(words);
+    writeln!(
+        out,
+        "Kept {} words with the fingerprint {:016x}",
+        settings.words().len(),
+        settings.words_fingerprint()
+    )?;
+    settings.pass_amount = args.count;
+    for password in settings.generate()? {
+        writeln!(out, "{}", password)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "schema")]
+fn print_schema(out: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    serde_json::to_writer_pretty(&mut *out, &PasswordSettings::json_schema())?;
+    writeln!(out)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "schema"))]
+fn print_schema(_: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    Err("printing the schema needs the schema feature".into())
+}
@@ -0,0 +1,10 @@
+#![no_main]
+
+use genrepass::range_inc_from_str;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|range: &str| {
+    if let Ok(range) = range_inc_from_str(range) {
+        assert!(range.start() <= range.end());
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use genrepass::PasswordSettings;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|json: &str| {
+    // Whatever is accepted has to be safe to generate from.
+    if let Ok(settings) = PasswordSettings::from_untrusted_json(json) {
+        let _ = settings.generate();
+    }
+});
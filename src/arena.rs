@@ -0,0 +1,136 @@
+use crate::sources::SourceId;
+use rand::{rngs::StdRng, seq::SliceRandom, thread_rng, SeedableRng};
+use std::{borrow::Cow, fmt, ops::Range, sync::OnceLock};
+
+/// The words laid out one after the other in a single allocation,
+/// so that going through consecutive words doesn't jump around the heap.
+#[derive(Clone)]
+pub(crate) struct WordArena {
+    text: String,
+    ranges: Vec<Range<u32>>,
+}
+
+impl WordArena {
+    fn new<'a>(words: impl Iterator<Item = &'a str> + Clone) -> Self {
+        let total: usize = words.clone().map(str::len).sum();
+        assert!(
+            u32::try_from(total).is_ok(),
+            "words shouldn't add up to more than 4 GiB"
+        );
+        let mut text = String::with_capacity(total);
+        let mut ranges = Vec::with_capacity(words.size_hint().0);
+
+        for word in words {
+            let start = text.len() as u32;
+            text.push_str(word);
+            ranges.push(start..text.len() as u32);
+        }
+
+        Self { text, ranges }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub(crate) fn get(&self, index: usize) -> &str {
+        let range = &self.ranges[index];
+        &self.text[range.start as usize..range.end as usize]
+    }
+}
+
+impl fmt::Debug for WordArena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WordArena")
+            .field("words", &self.ranges.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`WordArena`] built on first use, which has to be invalidated whenever the words change.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct LazyArena(OnceLock<WordArena>);
+
+// Only a cache of the words, which are compared on their own.
+impl PartialEq for LazyArena {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl LazyArena {
+    pub(crate) fn get(&self, words: &[String]) -> &WordArena {
+        self.0
+            .get_or_init(|| WordArena::new(words.iter().map(String::as_str)))
+    }
+
+    pub(crate) fn invalidate(&mut self) {
+        self.0.take();
+    }
+}
+
+/// The words in the order they were shuffled into for
+/// [`randomise`](crate::PasswordSettings#structfield.randomise), along with their sources.
+#[derive(Debug, Clone)]
+pub(crate) struct ShuffledWords {
+    /// The seed they were shuffled with, if any.
+    seed: Option<u64>,
+    /// The index of each of the stored words, in the shuffled order.
+    pub(crate) order: Vec<usize>,
+    pub(crate) arena: WordArena,
+    pub(crate) sources: Vec<SourceId>,
+}
+
+impl ShuffledWords {
+    fn new(words: &[String], sources: &[SourceId], seed: Option<u64>) -> Self {
+        let mut order: Vec<usize> = (0..words.len()).collect();
+        match seed {
+            Some(seed) => order.shuffle(&mut StdRng::seed_from_u64(seed)),
+            None => order.shuffle(&mut thread_rng()),
+        }
+
+        Self {
+            seed,
+            arena: WordArena::new(order.iter().map(|&i| words[i].as_str())),
+            sources: order.iter().map(|&i| sources[i]).collect(),
+            order,
+        }
+    }
+}
+
+/// [`ShuffledWords`] shuffled on first use, which has to be invalidated whenever the words change.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct LazyShuffle(OnceLock<ShuffledWords>);
+
+// Only a cache of the words, which are compared on their own.
+impl PartialEq for LazyShuffle {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl LazyShuffle {
+    /// The words shuffled with `seed`, or with [`thread_rng()`] if there's none.
+    ///
+    /// Only the first shuffle is kept, so asking for another seed than
+    /// the one it was made with shuffles them again just for that call.
+    pub(crate) fn get(
+        &self,
+        words: &[String],
+        sources: &[SourceId],
+        seed: Option<u64>,
+    ) -> Cow<'_, ShuffledWords> {
+        let shuffled = self
+            .0
+            .get_or_init(|| ShuffledWords::new(words, sources, seed));
+        if shuffled.seed == seed {
+            Cow::Borrowed(shuffled)
+        } else {
+            Cow::Owned(ShuffledWords::new(words, sources, seed))
+        }
+    }
+
+    pub(crate) fn invalidate(&mut self) {
+        self.0.take();
+    }
+}
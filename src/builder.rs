@@ -0,0 +1,383 @@
+use crate::settings::{
+    BatchRetryMode, ExhaustedBehaviour, GenerateError, InsertPlacement, InvalidProbabilityError,
+    LengthMode, LengthUnit, NonAsciiSpecialCharsError, PasswordSettings, RetryBudget, WordCase,
+    WordSelection,
+};
+use snafu::{ResultExt, Snafu};
+use std::{
+    io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+impl PasswordSettings {
+    /// Start building settings from the defaults, validating them all at once
+    /// with [`build()`](PasswordSettingsBuilder::build).
+    pub fn builder() -> PasswordSettingsBuilder {
+        PasswordSettingsBuilder::new()
+    }
+}
+
+/// Chainable construction of [`PasswordSettings`], as an alternative to changing its fields.
+///
+/// Nothing is checked until [`build()`](PasswordSettingsBuilder::build), and the words
+/// are only extracted then, after every other setting, so the order of the calls
+/// doesn't matter. Anything without a method here can still be changed on the built settings.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{ConfigError, PasswordSettings, WordCase};
+/// let settings = PasswordSettings::builder()
+///     .words_from_str("the quick brown fox jumps over the lazy dog")
+///     .word_case(WordCase::Capitalised)
+///     .length(16..=20)
+///     .pass_amount(5)
+///     .transliterate(false)
+///     .words_from_str("ärger über straße")
+///     .build()
+///     .unwrap();
+/// assert!(settings.words().contains(&String::from("straße")));
+/// assert_eq!(settings.generate().unwrap().len(), 5);
+///
+/// // The same as changing the fields.
+/// let mut fields = PasswordSettings::new();
+/// fields.word_case = WordCase::Capitalised;
+/// fields.length = 16..=20;
+/// let built = PasswordSettings::builder().word_case(WordCase::Capitalised).length(16..=20).build().unwrap();
+/// assert_eq!(built, fields);
+///
+/// let built = PasswordSettings::builder().special_chars("€").length(20..=10).build();
+/// assert!(matches!(built, Err(ConfigError::NonAsciiSpecialChars { .. })));
+///
+/// let built = PasswordSettings::builder().length(8..=10).number_amount(6..=8).special_chars_amount(5..=5).build();
+/// assert!(matches!(built, Err(ConfigError::InsertsExceedLength { needed: 11, length: 10 })));
+///
+/// let built = PasswordSettings::builder().upper_amount(3..=2).build();
+/// assert!(matches!(built, Err(ConfigError::Invalid { .. })));
+///
+/// let built = PasswordSettings::builder().words_from_path("does/not/exist").build();
+/// assert!(matches!(built, Err(ConfigError::ReadWords { .. })));
+/// ```
+#[derive(Debug, Default)]
+pub struct PasswordSettingsBuilder {
+    settings: PasswordSettings,
+    words: Vec<WordsFrom>,
+    error: Option<ConfigError>,
+}
+
+/// Where to extract words from once the settings are built.
+#[derive(Debug)]
+enum WordsFrom {
+    Str(String),
+    Path(PathBuf),
+}
+
+impl PasswordSettingsBuilder {
+    /// Start from the [default settings](PasswordSettings::default).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the [`word_case`](PasswordSettings#structfield.word_case).
+    pub fn word_case(mut self, case: WordCase) -> Self {
+        self.settings.word_case = case;
+        self
+    }
+
+    /// Set the [`word_case`](PasswordSettings#structfield.word_case)
+    /// to [`WordCase::Capitalised`], or back to [`WordCase::AsIs`].
+    #[deprecated(note = "use `word_case` instead")]
+    pub fn capitalise(self, capitalise: bool) -> Self {
+        self.word_case(if capitalise {
+            WordCase::Capitalised
+        } else {
+            WordCase::AsIs
+        })
+    }
+
+    /// Set the [`separator`](PasswordSettings#structfield.separator).
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.settings.separator = separator.into();
+        self
+    }
+
+    /// Set [`replace`](PasswordSettings#structfield.replace).
+    pub fn replace(mut self, replace: bool) -> Self {
+        self.settings.replace = replace;
+        self
+    }
+
+    /// Set [`leet`](PasswordSettings#structfield.leet).
+    pub fn leet(mut self, leet: bool) -> Self {
+        self.settings.leet = leet;
+        self
+    }
+
+    /// Set [`insert_at_boundaries`](PasswordSettings#structfield.insert_at_boundaries).
+    pub fn insert_at_boundaries(mut self, insert_at_boundaries: bool) -> Self {
+        self.settings.insert_at_boundaries = insert_at_boundaries;
+        self
+    }
+
+    /// Set [`insert_placement`](PasswordSettings#structfield.insert_placement).
+    pub fn insert_placement(mut self, insert_placement: InsertPlacement) -> Self {
+        self.settings.insert_placement = insert_placement;
+        self
+    }
+
+    /// Set [`no_adjacent_inserts`](PasswordSettings#structfield.no_adjacent_inserts).
+    pub fn no_adjacent_inserts(mut self, no_adjacent_inserts: bool) -> Self {
+        self.settings.no_adjacent_inserts = no_adjacent_inserts;
+        self
+    }
+
+    /// Set [`insert_zone`](PasswordSettings#structfield.insert_zone).
+    pub fn insert_zone(mut self, insert_zone: impl Into<Option<RangeInclusive<usize>>>) -> Self {
+        self.settings.insert_zone = insert_zone.into();
+        self
+    }
+
+    /// Set the [most replaced fraction](PasswordSettings::set_max_replaced_fraction).
+    pub fn max_replaced_fraction(mut self, fraction: f64) -> Self {
+        if let Err(e) = self.settings.set_max_replaced_fraction(fraction) {
+            self.error.get_or_insert(e.into());
+        }
+        self
+    }
+
+    /// Set [`randomise`](PasswordSettings#structfield.randomise).
+    pub fn randomise(mut self, randomise: bool) -> Self {
+        self.settings.randomise = randomise;
+        self
+    }
+
+    /// Set [`pass_amount`](PasswordSettings#structfield.pass_amount).
+    pub fn pass_amount(mut self, amount: usize) -> Self {
+        self.settings.pass_amount = amount;
+        self
+    }
+
+    /// Set [`unique`](PasswordSettings#structfield.unique).
+    pub fn unique(mut self, unique: bool) -> Self {
+        self.settings.unique = unique;
+        self
+    }
+
+    /// Set the [`seed`](PasswordSettings#structfield.seed).
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.settings.seed = Some(seed);
+        self
+    }
+
+    /// Set the [`retry_budget`](PasswordSettings#structfield.retry_budget).
+    pub fn retry_budget(mut self, budget: RetryBudget) -> Self {
+        self.settings.retry_budget = budget;
+        self
+    }
+
+    /// Set [`on_exhausted`](PasswordSettings#structfield.on_exhausted).
+    pub fn on_exhausted(mut self, behaviour: ExhaustedBehaviour) -> Self {
+        self.settings.on_exhausted = behaviour;
+        self
+    }
+
+    /// Set the [`batch_retry`](PasswordSettings#structfield.batch_retry) mode.
+    pub fn batch_retry(mut self, mode: BatchRetryMode) -> Self {
+        self.settings.batch_retry = mode;
+        self
+    }
+
+    /// Set the [`length`](PasswordSettings#structfield.length).
+    pub fn length(mut self, length: impl Into<RangeInclusive<usize>>) -> Self {
+        self.settings.length = length.into();
+        self
+    }
+
+    /// Set the [`length_unit`](PasswordSettings#structfield.length_unit).
+    pub fn length_unit(mut self, unit: LengthUnit) -> Self {
+        self.settings.length_unit = unit;
+        self
+    }
+
+    /// Set the [`length_mode`](PasswordSettings#structfield.length_mode).
+    pub fn length_mode(mut self, mode: LengthMode) -> Self {
+        self.settings.length_mode = mode;
+        self
+    }
+
+    /// Set the [continue probability](PasswordSettings::set_continue_probability).
+    pub fn continue_probability(mut self, probability: f64) -> Self {
+        if let Err(e) = self.settings.set_continue_probability(probability) {
+            self.error.get_or_insert(e.into());
+        }
+        self
+    }
+
+    /// Set the [`number_amount`](PasswordSettings#structfield.number_amount).
+    pub fn number_amount(mut self, amount: impl Into<RangeInclusive<usize>>) -> Self {
+        self.settings.number_amount = amount.into();
+        self
+    }
+
+    /// Set the [`special_chars_amount`](PasswordSettings#structfield.special_chars_amount).
+    pub fn special_chars_amount(mut self, amount: impl Into<RangeInclusive<usize>>) -> Self {
+        self.settings.special_chars_amount = amount.into();
+        self
+    }
+
+    /// Set the [special characters](PasswordSettings::set_special_chars).
+    pub fn special_chars(mut self, chars: &str) -> Self {
+        if let Err(e) = self.settings.set_special_chars(chars) {
+            self.error.get_or_insert(e.into());
+        }
+        self
+    }
+
+    /// Set the [`upper_amount`](PasswordSettings#structfield.upper_amount).
+    pub fn upper_amount(mut self, amount: impl Into<RangeInclusive<usize>>) -> Self {
+        self.settings.upper_amount = amount.into();
+        self
+    }
+
+    /// Set the [`lower_amount`](PasswordSettings#structfield.lower_amount).
+    pub fn lower_amount(mut self, amount: impl Into<RangeInclusive<usize>>) -> Self {
+        self.settings.lower_amount = amount.into();
+        self
+    }
+
+    /// Set the [`word_selection`](PasswordSettings#structfield.word_selection).
+    pub fn word_selection(mut self, selection: WordSelection) -> Self {
+        self.settings.word_selection = selection;
+        self
+    }
+
+    /// Set [`transliterate`](PasswordSettings#structfield.transliterate),
+    /// which applies to all the words, whenever they were added.
+    pub fn transliterate(mut self, transliterate: bool) -> Self {
+        self.settings.transliterate = transliterate;
+        self
+    }
+
+    /// Extract words from a string when building, like
+    /// [`get_words_from_str()`](PasswordSettings::get_words_from_str).
+    pub fn words_from_str(mut self, text: impl Into<String>) -> Self {
+        self.words.push(WordsFrom::Str(text.into()));
+        self
+    }
+
+    /// Extract words from a file or directory when building, like
+    /// [`get_words_from_path()`](PasswordSettings::get_words_from_path).
+    pub fn words_from_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.words
+            .push(WordsFrom::Path(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Extract the words and check that the settings fit together.
+    ///
+    /// Returns the first error of the methods that can fail, in the order they were called,
+    /// then any error reading the words, then whether the numbers and special characters
+    /// that have to be inserted fit the maximum length, and then anything else that
+    /// [`can_generate()`](PasswordSettings::can_generate) checks.
+    /// Not having words isn't an error, since they can still be added to the settings.
+    pub fn build(self) -> Result<PasswordSettings, ConfigError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let mut settings = self.settings;
+        for words in self.words {
+            match words {
+                WordsFrom::Str(text) => {
+                    settings.get_words_from_str(&text);
+                }
+                WordsFrom::Path(path) => {
+                    settings
+                        .get_words_from_path(&path)
+                        .context(ReadWordsSnafu { path })?;
+                }
+            }
+        }
+
+        settings.check_config()?;
+        Ok(settings)
+    }
+}
+
+impl PasswordSettings {
+    /// Check the settings like [`PasswordSettingsBuilder::build()`], except for the words.
+    pub(crate) fn check_config(&self) -> Result<(), ConfigError> {
+        if self.length_mode == LengthMode::Units {
+            let specials =
+                if self.get_special_chars().is_empty() && self.get_special_sequences().is_empty() {
+                    0
+                } else {
+                    *self.special_chars_amount.start()
+                };
+            let needed = self.number_amount.start()
+                + specials
+                + self
+                    .get_special_groups()
+                    .iter()
+                    .filter(|g| !g.chars.is_empty())
+                    .map(|g| g.amount.start())
+                    .sum::<usize>();
+            let length = *self.length.end();
+            snafu::ensure!(
+                needed <= length,
+                InsertsExceedLengthSnafu { needed, length }
+            );
+        }
+
+        match self.can_generate() {
+            Ok(()) | Err(GenerateError::NotEnoughWords { .. }) => Ok(()),
+            Err(source) => Err(ConfigError::Invalid { source }),
+        }
+    }
+}
+
+/// The errors that [`PasswordSettingsBuilder::build()`] can return.
+#[derive(Debug, Snafu)]
+pub enum ConfigError {
+    /// See [`NonAsciiSpecialCharsError`].
+    #[snafu(context(false), display("{source}"))]
+    NonAsciiSpecialChars {
+        /// The underlying error.
+        source: NonAsciiSpecialCharsError,
+    },
+
+    /// See [`InvalidProbabilityError`].
+    #[snafu(context(false), display("{source}"))]
+    InvalidProbability {
+        /// The underlying error.
+        source: InvalidProbabilityError,
+    },
+
+    /// When the words couldn't be read from a path.
+    #[snafu(display("couldn't read words from {}: {source}", path.display()))]
+    ReadWords {
+        /// The path given to [`words_from_path()`](PasswordSettingsBuilder::words_from_path).
+        path: PathBuf,
+        /// The underlying error.
+        source: io::Error,
+    },
+
+    /// When the fewest numbers and special characters to insert are more than the maximum length.
+    #[snafu(display(
+        "at least {needed} numbers and special characters can't fit in a length of {length}"
+    ))]
+    InsertsExceedLength {
+        /// The fewest numbers and special characters to insert.
+        needed: usize,
+        /// The maximum length.
+        length: usize,
+    },
+
+    /// When the settings can't generate, like with an empty range.
+    #[snafu(display("{source}"))]
+    Invalid {
+        /// The underlying error.
+        source: GenerateError,
+    },
+}
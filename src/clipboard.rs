@@ -0,0 +1,322 @@
+//! Copying passwords to the system clipboard.
+//!
+//! Instead of talking to the clipboard directly, the text is handed over to the usual
+//! command line tool of the platform, which keeps serving it after this process exits.
+//! On Linux and the BSDs that means `wl-copy` on Wayland and `xclip` or `xsel` on X11,
+//! so one of them needs to be installed.
+
+use crate::helpers::Fingerprint;
+use snafu::{ensure, ResultExt, Snafu};
+use std::{
+    env,
+    io::{self, Read, Write},
+    process::{Command, ExitStatus, Stdio},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// Copy the text to the clipboard with the first [`Backend`] that's available.
+pub fn copy(text: &str) -> Result<(), ClipboardError> {
+    copy_with(&Environment::current(), text)
+}
+
+/// Copy the text to the clipboard and clear it after the given duration.
+///
+/// The clipboard is only cleared if it still holds the text, so anything copied
+/// in the meantime is kept. When the clipboard can't be read back, like when only
+/// `wl-copy` is installed without `wl-paste`, it's cleared regardless.
+///
+/// The clearing happens on a background thread, which doesn't outlive the process,
+/// so join the returned handle before exiting if the clipboard must be cleared.
+pub fn copy_and_clear_after(
+    text: &str,
+    duration: Duration,
+) -> Result<JoinHandle<Result<(), ClipboardError>>, ClipboardError> {
+    let environment = Environment::current();
+    copy_with(&environment, text)?;
+    // Only a fingerprint, so the thread doesn't keep the password around.
+    let copied = text_fingerprint(text);
+
+    Ok(thread::spawn(move || {
+        thread::sleep(duration);
+        match paste_with(&environment) {
+            Ok(current) if text_fingerprint(&current) != copied => Ok(()),
+            _ => copy_with(&environment, ""),
+        }
+    }))
+}
+
+fn text_fingerprint(text: &str) -> u64 {
+    let mut fingerprint = Fingerprint::new();
+    fingerprint.push(text);
+    fingerprint.finish()
+}
+
+fn copy_with(environment: &Environment, text: &str) -> Result<(), ClipboardError> {
+    for backend in Backend::candidates(environment) {
+        match backend.copy(text) {
+            Err(ClipboardError::Spawn { source, .. })
+                if source.kind() == io::ErrorKind::NotFound =>
+            {
+                continue
+            }
+            result => return result,
+        }
+    }
+
+    NoBackendSnafu.fail()
+}
+
+fn paste_with(environment: &Environment) -> Result<String, ClipboardError> {
+    for backend in Backend::candidates(environment) {
+        match backend.paste() {
+            Err(ClipboardError::Spawn { source, .. })
+                if source.kind() == io::ErrorKind::NotFound =>
+            {
+                continue
+            }
+            result => return result,
+        }
+    }
+
+    NoBackendSnafu.fail()
+}
+
+/// The operating system family, which decides the available [`Backend`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    /// Linux, the BSDs and anything else that isn't macOS or Windows.
+    Unix,
+    MacOs,
+    Windows,
+}
+
+/// What [`Backend::candidates()`] needs to know about the system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Environment {
+    pub platform: Platform,
+
+    /// Whether a Wayland compositor is running, i.e. `WAYLAND_DISPLAY` is set.
+    pub wayland: bool,
+
+    /// Whether an X server is running, i.e. `DISPLAY` is set.
+    pub x11: bool,
+}
+
+impl Environment {
+    /// Read the environment of the current process.
+    pub fn current() -> Self {
+        let platform = if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else if cfg!(windows) {
+            Platform::Windows
+        } else {
+            Platform::Unix
+        };
+
+        Self {
+            platform,
+            wayland: env::var_os("WAYLAND_DISPLAY").is_some(),
+            x11: env::var_os("DISPLAY").is_some(),
+        }
+    }
+}
+
+/// A command line tool that puts its input into the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    WlCopy,
+    Xclip,
+    Xsel,
+    Pbcopy,
+    Clip,
+}
+
+impl Backend {
+    /// The backends to try for the given environment, in order of preference.
+    ///
+    /// Wayland is preferred over X11 when both are available,
+    /// since X11 programs running under XWayland can still read its clipboard.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::clipboard::{Backend, Environment, Platform};
+    /// let both = Environment { platform: Platform::Unix, wayland: true, x11: true };
+    /// assert_eq!(
+    ///     Backend::candidates(&both),
+    ///     [Backend::WlCopy, Backend::Xclip, Backend::Xsel]
+    /// );
+    ///
+    /// let x11 = Environment { wayland: false, ..both.clone() };
+    /// assert_eq!(Backend::candidates(&x11), [Backend::Xclip, Backend::Xsel]);
+    ///
+    /// let headless = Environment { x11: false, ..x11 };
+    /// assert!(Backend::candidates(&headless).is_empty());
+    ///
+    /// let mac = Environment { platform: Platform::MacOs, ..both.clone() };
+    /// assert_eq!(Backend::candidates(&mac), [Backend::Pbcopy]);
+    ///
+    /// let windows = Environment { platform: Platform::Windows, ..both };
+    /// assert_eq!(Backend::candidates(&windows), [Backend::Clip]);
+    /// ```
+    pub fn candidates(environment: &Environment) -> Vec<Backend> {
+        match environment.platform {
+            Platform::MacOs => vec![Backend::Pbcopy],
+            Platform::Windows => vec![Backend::Clip],
+            Platform::Unix => {
+                let mut backends = Vec::new();
+                if environment.wayland {
+                    backends.push(Backend::WlCopy);
+                }
+                if environment.x11 {
+                    backends.extend([Backend::Xclip, Backend::Xsel]);
+                }
+                backends
+            }
+        }
+    }
+
+    /// The name of the executable.
+    pub fn program(self) -> &'static str {
+        match self {
+            Backend::WlCopy => "wl-copy",
+            Backend::Xclip => "xclip",
+            Backend::Xsel => "xsel",
+            Backend::Pbcopy => "pbcopy",
+            Backend::Clip => "clip",
+        }
+    }
+
+    fn args(self) -> &'static [&'static str] {
+        match self {
+            Backend::Xclip => &["-selection", "clipboard"],
+            Backend::Xsel => &["--clipboard", "--input"],
+            Backend::WlCopy | Backend::Pbcopy | Backend::Clip => &[],
+        }
+    }
+
+    /// The executable and its arguments for reading the clipboard back.
+    fn paste_command(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            Backend::WlCopy => ("wl-paste", &["--no-newline"]),
+            Backend::Xclip => ("xclip", &["-selection", "clipboard", "-out"]),
+            Backend::Xsel => ("xsel", &["--clipboard", "--output"]),
+            Backend::Pbcopy => ("pbpaste", &[]),
+            Backend::Clip => (
+                "powershell",
+                &[
+                    "-NoProfile",
+                    "-Command",
+                    "[Console]::OutputEncoding = [Text.Encoding]::UTF8; \
+                     [Console]::Write((Get-Clipboard -Raw))",
+                ],
+            ),
+        }
+    }
+
+    /// The bytes to write to the backend for the text.
+    ///
+    /// That's UTF-8 for all of them except `clip`, which reads the console code page
+    /// unless the text starts with the byte order mark of UTF-16LE.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::clipboard::Backend;
+    /// assert_eq!(Backend::Pbcopy.encode("Ærø"), "Ærø".as_bytes());
+    /// assert_eq!(
+    ///     Backend::Clip.encode("Ærø"),
+    ///     [0xFF, 0xFE, 0xC6, 0x00, 0x72, 0x00, 0xF8, 0x00]
+    /// );
+    /// ```
+    pub fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            Backend::Clip => [0xFF, 0xFE]
+                .into_iter()
+                .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+                .collect(),
+            _ => text.as_bytes().to_vec(),
+        }
+    }
+
+    /// Copy the text to the clipboard with this backend.
+    pub fn copy(self, text: &str) -> Result<(), ClipboardError> {
+        let program = self.program();
+        // The X11 tools fork to keep serving the clipboard,
+        // so the output is discarded instead of waiting for it to close.
+        let mut child = Command::new(program)
+            .args(self.args())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .context(SpawnSnafu { program })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(&self.encode(text))
+                .context(WriteSnafu { program })?;
+        }
+
+        let status = child.wait().context(WriteSnafu { program })?;
+        ensure!(status.success(), FailedSnafu { program, status });
+        Ok(())
+    }
+
+    /// Read the text in the clipboard with the counterpart of this backend.
+    pub fn paste(self) -> Result<String, ClipboardError> {
+        let (program, args) = self.paste_command();
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context(SpawnSnafu { program })?;
+
+        let mut bytes = Vec::new();
+        if let Some(mut stdout) = child.stdout.take() {
+            stdout
+                .read_to_end(&mut bytes)
+                .context(ReadSnafu { program })?;
+        }
+
+        let status = child.wait().context(ReadSnafu { program })?;
+        ensure!(status.success(), FailedSnafu { program, status });
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+/// The errors that copying to the clipboard can return.
+#[derive(Debug, Snafu)]
+pub enum ClipboardError {
+    /// None of the [candidates](Backend::candidates) are installed,
+    /// or there's no graphical session.
+    #[snafu(display("no clipboard tool available"))]
+    NoBackend,
+
+    #[snafu(display("couldn't run {program}: {source}"))]
+    Spawn {
+        program: &'static str,
+        source: io::Error,
+    },
+
+    #[snafu(display("couldn't pass the text to {program}: {source}"))]
+    Write {
+        program: &'static str,
+        source: io::Error,
+    },
+
+    #[snafu(display("couldn't read the text from {program}: {source}"))]
+    Read {
+        program: &'static str,
+        source: io::Error,
+    },
+
+    #[snafu(display("{program} exited with {status}"))]
+    Failed {
+        program: &'static str,
+        status: ExitStatus,
+    },
+}
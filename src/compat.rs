@@ -0,0 +1,179 @@
+#![allow(deprecated)]
+
+use crate::{
+    builder::ConfigError,
+    frozen::FrozenSettings,
+    settings::{GenerateError, PasswordSettings},
+};
+use std::ops::{Deref, DerefMut};
+
+/// The configuration of the generator from before [`PasswordSettings`],
+/// kept so that code written for it still compiles.
+///
+/// It's the same as [`PasswordSettings`], which it dereferences to,
+/// only adding [`validate()`](PassConfig::validate) for the old workflow.
+///
+/// # Example
+///
+/// ```
+/// # #![allow(deprecated)]
+/// # use genrepass::PassConfig;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut config = PassConfig::new();
+/// config.get_words_from_str("the quick brown fox jumps over the lazy dog");
+/// config.pass_amount = 3;
+///
+/// let validated = config.validate()?;
+/// assert_eq!(validated.generate()?.len(), 3);
+///
+/// // The same as:
+/// let settings = genrepass::PasswordSettings::builder()
+///     .words_from_str("the quick brown fox jumps over the lazy dog")
+///     .pass_amount(3)
+///     .build()?
+///     .freeze();
+/// assert_eq!(settings.generate()?.len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+#[deprecated(note = "use `PasswordSettings`, or `PasswordSettings::builder()` for validating")]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PassConfig(PasswordSettings);
+
+impl PassConfig {
+    /// See [`PasswordSettings::new()`].
+    #[deprecated(note = "use `PasswordSettings::new()` instead")]
+    pub fn new() -> Self {
+        Self(PasswordSettings::new())
+    }
+
+    /// Check the settings like [`PasswordSettingsBuilder::build()`](crate::PasswordSettingsBuilder::build),
+    /// freezing them for generating.
+    ///
+    /// Having no words isn't an error, so it's left for
+    /// [`ValidatedConfig::generate()`] to report.
+    #[deprecated(
+        note = "use `PasswordSettings::builder()` and `PasswordSettings::freeze()` instead"
+    )]
+    pub fn validate(self) -> Result<ValidatedConfig, ConfigError> {
+        self.0.check_config()?;
+        Ok(ValidatedConfig(FrozenSettings::new(self.0)))
+    }
+
+    /// Take the settings out of the old configuration.
+    #[deprecated(note = "use `PasswordSettings` instead")]
+    pub fn into_settings(self) -> PasswordSettings {
+        self.0
+    }
+}
+
+impl From<PasswordSettings> for PassConfig {
+    fn from(settings: PasswordSettings) -> Self {
+        Self(settings)
+    }
+}
+
+impl Deref for PassConfig {
+    type Target = PasswordSettings;
+
+    fn deref(&self) -> &PasswordSettings {
+        &self.0
+    }
+}
+
+impl DerefMut for PassConfig {
+    fn deref_mut(&mut self) -> &mut PasswordSettings {
+        &mut self.0
+    }
+}
+
+/// A [`PassConfig`] that was validated, ready for generating.
+///
+/// It's a [`FrozenSettings`], so the settings can't be changed anymore.
+#[deprecated(note = "use `FrozenSettings` instead")]
+#[derive(Debug, Clone)]
+pub struct ValidatedConfig(FrozenSettings);
+
+impl ValidatedConfig {
+    /// See [`PasswordSettings::generate()`].
+    ///
+    /// The settings were already checked, so it fails with
+    /// [`GenerateError::NotEnoughWords`] when there are no words, and otherwise only
+    /// when a password can't be made to follow a policy that was opted into, like
+    /// [`strict_case`](PasswordSettings#structfield.strict_case) or
+    /// [`unique`](PasswordSettings#structfield.unique).
+    #[deprecated(note = "use `FrozenSettings::generate()` instead")]
+    pub fn generate(&self) -> Result<Vec<String>, GenerateError> {
+        self.0.generate()
+    }
+
+    /// Get a reference to the validated settings.
+    #[deprecated(note = "use `FrozenSettings::settings()` instead")]
+    pub fn settings(&self) -> &PasswordSettings {
+        self.0.settings()
+    }
+
+    /// Take the frozen settings, to keep generating with them without this wrapper.
+    #[deprecated(note = "use `FrozenSettings` instead")]
+    pub fn into_frozen(self) -> FrozenSettings {
+        self.0
+    }
+}
+
+/// The settings as they're saved, with the keys that earlier versions saved them under.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SavedSettings {
+    #[serde(flatten, with = "PasswordSettings")]
+    settings: PasswordSettings,
+    /// The [assembly retries](crate::RetryBudget::assembly) up to 1.1.4.
+    reset_amount: Option<usize>,
+    /// The flags that became [`CaseRule`](crate::CaseRule)s after 1.1.4.
+    #[serde(default)]
+    force_upper: bool,
+    #[serde(default)]
+    force_lower: bool,
+    #[serde(default)]
+    dont_upper: bool,
+    #[serde(default)]
+    dont_lower: bool,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PasswordSettings {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let SavedSettings {
+            mut settings,
+            reset_amount,
+            force_upper,
+            force_lower,
+            dont_upper,
+            dont_lower,
+        } = SavedSettings::deserialize(deserializer)?;
+        settings.source_saved_words();
+        if let Some(amount) = reset_amount {
+            settings.retry_budget.assembly = amount;
+        }
+        // Only the flags that were set, so that they can't undo the rules saved with them.
+        if force_upper {
+            settings.set_force_upper(true);
+        }
+        if force_lower {
+            settings.set_force_lower(true);
+        }
+        if dont_upper {
+            settings.set_dont_upper(true);
+        }
+        if dont_lower {
+            settings.set_dont_lower(true);
+        }
+        Ok(settings)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PasswordSettings {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PasswordSettings::serialize(self, serializer)
+    }
+}
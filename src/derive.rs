@@ -0,0 +1,80 @@
+use crate::{
+    options::GenerateOptions,
+    settings::{GenerateError, PasswordSettings},
+};
+use argon2::{Algorithm, Argon2, Params, Version};
+use rand::thread_rng;
+use secrecy::{ExposeSecret, SecretString};
+use std::ops::ControlFlow;
+
+/// Salted along with the site, to be bumped if the derivation ever has to change,
+/// so that passwords derived before aren't silently different.
+const DERIVE_VERSION: u8 = 1;
+
+/// The memory each derivation takes, in KiB, which is 19 MiB.
+const DERIVE_MEMORY: u32 = 19 * 1024;
+
+/// The passes over that memory.
+const DERIVE_PASSES: u32 = 2;
+
+impl PasswordSettings {
+    /// Derive the password for a site from a master secret, so that it can be generated
+    /// again at any time without storing it.
+    ///
+    /// The master and the site are hashed with Argon2id into a
+    /// [`seed`](PasswordSettings#structfield.seed), which replaces the one in the settings,
+    /// and the password is generated with it like any other.
+    /// Each derivation takes 19 MiB of memory and 2 passes over it on a single thread,
+    /// the minimum recommended by OWASP, so that guessing the master from a derived password
+    /// is just as slow, even with the settings and the words at hand.
+    /// The site is used exactly as it's given, so `github` and `GitHub` are different sites.
+    ///
+    /// **The password only stays the same for the exact same settings and words.**
+    /// Changing any setting, adding or removing a single word, or even updating
+    /// this crate or `rand` can change every password derived with it.
+    /// Keep the settings and the words along with the master if the passwords
+    /// have to be derived again later, and check a known password after any change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// # use secrecy::SecretString;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    /// let master = SecretString::from("correct horse battery staple");
+    ///
+    /// let github = settings.derive("github", &master).unwrap();
+    /// assert_eq!(settings.derive("github", &master).unwrap(), github);
+    /// assert_ne!(settings.derive("gitlab", &master).unwrap(), github);
+    /// ```
+    pub fn derive(&self, site: &str, master: &SecretString) -> Result<String, GenerateError> {
+        let options = GenerateOptions {
+            pass_amount: Some(1),
+            ..Default::default()
+        };
+        self.validate(&options)?;
+
+        let seed = derive_seed(site, master.expose_secret());
+        let mut details = self.details(&options, &mut thread_rng(), Some(seed), &mut |_, _| {
+            ControlFlow::Continue(())
+        })?;
+        Ok(details.remove(0).password)
+    }
+}
+
+/// The seed of a site, with the master as the password and the site as the salt,
+/// which is prefixed so that it's never shorter than the 8 bytes Argon2 needs.
+fn derive_seed(site: &str, master: &str) -> u64 {
+    let params = Params::new(DERIVE_MEMORY, DERIVE_PASSES, 1, Some(8))
+        .expect("the derivation cost should be valid");
+    let mut salt = b"genrepass".to_vec();
+    salt.push(DERIVE_VERSION);
+    salt.extend_from_slice(site.as_bytes());
+
+    let mut seed = [0; 8];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(master.as_bytes(), &salt, &mut seed)
+        .expect("the master and the site should fit in Argon2");
+    u64::from_le_bytes(seed)
+}
@@ -0,0 +1,200 @@
+use crate::{
+    helpers::wipe,
+    iter::IterWords,
+    options::GenerateOptions,
+    password::{PasswordBuilder, Stage, WordPicker},
+    report::{PasswordDetails, RolledParameters},
+    settings::{GenerateError, PasswordSettings},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fmt;
+
+/// A password to refine step by step, made with [`PasswordSettings::draft()`].
+///
+/// It keeps the password as it was after each stage, so that only one part of it
+/// can be rolled again: the words, the inserted characters or the case.
+/// The amounts rolled for it, like the length and the amount of numbers,
+/// stay the same for as long as the draft lives.
+///
+/// Rerolling the inserts keeps the case of every letter of the words as it is,
+/// as long as the inserted characters are inserted and not
+/// [replacing](PasswordSettings#structfield.replace) or
+/// [substituting](PasswordSettings#structfield.leet) letters,
+/// since the case is adjusted again for the letters that are left.
+///
+/// The context exclusions and [`strict_case`](PasswordSettings#structfield.strict_case)
+/// aren't checked, and a password that ran out of words is kept
+/// as [`truncated`](PasswordDetails#structfield.truncated) whatever the
+/// [`on_exhausted`](PasswordSettings#structfield.on_exhausted) behaviour.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::PasswordSettings;
+/// let mut settings = PasswordSettings::new();
+/// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+/// let mut rng = rand::thread_rng();
+///
+/// let mut draft = settings.draft(&mut rng).unwrap();
+/// let words: String = draft.password().chars().filter(|c| c.is_alphabetic()).collect();
+///
+/// draft.reroll_inserts(&mut rng);
+/// let letters: String = draft.password().chars().filter(|c| c.is_alphabetic()).collect();
+/// assert_eq!(letters, words);
+/// ```
+pub struct PasswordDraft<'a> {
+    settings: &'a PasswordSettings,
+    words: IterWords<'a>,
+    picker: WordPicker,
+    options: GenerateOptions,
+    rolled: RolledParameters,
+    /// The words, before anything is inserted.
+    assembled: Stage,
+    /// The words with the inserted characters, before the case is adjusted.
+    inserted: Stage,
+    /// The seed the case is adjusted with, so that it's adjusted the same way
+    /// for the same letters when only the inserts change.
+    case_seed: u64,
+    details: PasswordDetails,
+}
+
+impl PasswordSettings {
+    /// Start a password that can be refined step by step with a [`PasswordDraft`].
+    pub fn draft<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<PasswordDraft<'_>, GenerateError> {
+        let options = GenerateOptions {
+            pass_amount: Some(1),
+            ..Default::default()
+        };
+        self.validate(&options)?;
+
+        let (words, picker) = IterWords::with_picker(self, rng, self.seed);
+        let rolled = PasswordBuilder::roll(self, &options, rng);
+        let mut builder = builder(self, &words, &picker, &options, &rolled, rng);
+        builder.assemble(rng);
+        let assembled = builder.stage();
+        builder.insert(rng);
+        let inserted = builder.stage();
+        let case_seed = rng.gen();
+        let details = finish(builder, case_seed);
+
+        Ok(PasswordDraft {
+            settings: self,
+            words,
+            picker,
+            options,
+            rolled,
+            assembled,
+            inserted,
+            case_seed,
+            details,
+        })
+    }
+}
+
+impl PasswordDraft<'_> {
+    /// Select other words, and insert the characters and adjust the case for them.
+    pub fn reroll_words<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let mut builder = self.builder(rng);
+        builder.assemble(rng);
+        let assembled = builder.stage();
+        builder.insert(rng);
+        let inserted = builder.stage();
+        let case_seed = rng.gen();
+        let details = finish(builder, case_seed);
+
+        keep(&mut self.assembled, assembled);
+        keep(&mut self.inserted, inserted);
+        self.case_seed = case_seed;
+        self.keep_details(details);
+    }
+
+    /// Insert other characters in other places, keeping the words and their case.
+    pub fn reroll_inserts<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let mut builder = self.builder(rng).resume(&self.assembled);
+        builder.insert(rng);
+        let inserted = builder.stage();
+        let details = finish(builder, self.case_seed);
+
+        keep(&mut self.inserted, inserted);
+        self.keep_details(details);
+    }
+
+    /// Adjust the case of other letters, keeping the words and the inserted characters.
+    pub fn reroll_case<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let builder = self.builder(rng).resume(&self.inserted);
+        let case_seed = rng.gen();
+        let details = finish(builder, case_seed);
+
+        self.case_seed = case_seed;
+        self.keep_details(details);
+    }
+
+    /// The password as it currently is.
+    pub fn password(&self) -> &str {
+        &self.details.password
+    }
+
+    /// The details of the password as it currently is.
+    pub fn details(&self) -> &PasswordDetails {
+        &self.details
+    }
+
+    /// Take the details of the password, once it's refined.
+    pub fn into_details(self) -> PasswordDetails {
+        self.details.clone()
+    }
+
+    fn builder<R: Rng + ?Sized>(&self, rng: &mut R) -> PasswordBuilder<'_> {
+        builder(
+            self.settings,
+            &self.words,
+            &self.picker,
+            &self.options,
+            &self.rolled,
+            rng,
+        )
+    }
+
+    fn keep_details(&mut self, details: PasswordDetails) {
+        wipe(std::mem::replace(&mut self.details, details).password);
+    }
+}
+
+/// A builder with the rolled amounts, and other characters to insert.
+fn builder<'w, R: Rng + ?Sized>(
+    settings: &PasswordSettings,
+    words: &'w IterWords<'_>,
+    picker: &'w WordPicker,
+    options: &GenerateOptions,
+    rolled: &RolledParameters,
+    rng: &mut R,
+) -> PasswordBuilder<'w> {
+    settings.password_builder(words.get(), picker, options, &Some(rolled.clone()), rng)
+}
+
+/// Adjust the case with `case_seed` and take the details of the password.
+fn finish(mut builder: PasswordBuilder<'_>, case_seed: u64) -> PasswordDetails {
+    builder.ensure_case(&mut StdRng::seed_from_u64(case_seed));
+    builder.finish_details()
+}
+
+/// Replace a kept stage, wiping the password it had.
+fn keep(kept: &mut Stage, stage: Stage) {
+    wipe(std::mem::replace(kept, stage).password);
+}
+
+impl Drop for PasswordDraft<'_> {
+    fn drop(&mut self) {
+        wipe(std::mem::take(&mut self.assembled.password));
+        wipe(std::mem::take(&mut self.inserted.password));
+        wipe(std::mem::take(&mut self.details.password));
+    }
+}
+
+impl fmt::Debug for PasswordDraft<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PasswordDraft")
+            .field("rolled", &self.rolled)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    iter::PasswordIter,
+    options::GenerateOptions,
+    settings::{GenerateError, PasswordSettings},
+};
+use rand::Rng;
+use std::{sync::Arc, time::Duration};
+
+/// An immutable copy of [`PasswordSettings`], made with
+/// [`PasswordSettings::freeze()`], for generating from many places at once.
+///
+/// Cloning it is cheap, since the settings and words are shared,
+/// and nothing done to the original settings afterwards affects it.
+/// Everything generating needs is prepared when freezing,
+/// so generating never waits on anything.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::PasswordSettings;
+/// # use std::thread;
+/// let mut settings = PasswordSettings::new();
+/// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+/// let frozen = settings.freeze();
+///
+/// thread::scope(|s| {
+///     for _ in 0..4 {
+///         let frozen = frozen.clone();
+///         s.spawn(move || assert_eq!(frozen.generate_n(10).unwrap().len(), 10));
+///     }
+///     settings.get_words_from_str("sphinx of black quartz judge my vow");
+/// });
+///
+/// assert_eq!(frozen.settings().words().len(), 9);
+/// assert_eq!(settings.words().len(), 16);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrozenSettings(Arc<PasswordSettings>);
+
+impl FrozenSettings {
+    pub(crate) fn new(settings: PasswordSettings) -> Self {
+        settings.arena.get(&settings.words);
+        Self(Arc::new(settings))
+    }
+
+    /// Get a reference to the frozen settings.
+    pub fn settings(&self) -> &PasswordSettings {
+        &self.0
+    }
+
+    /// See [`PasswordSettings::generate()`].
+    pub fn generate(&self) -> Result<Vec<String>, GenerateError> {
+        self.0.generate()
+    }
+
+    /// See [`PasswordSettings::generate_n()`].
+    pub fn generate_n(&self, n: usize) -> Result<Vec<String>, GenerateError> {
+        self.0.generate_n(n)
+    }
+
+    /// See [`PasswordSettings::generate_secure()`].
+    #[cfg(feature = "zeroize")]
+    pub fn generate_secure(&self) -> Result<Vec<zeroize::Zeroizing<String>>, GenerateError> {
+        self.0.generate_secure()
+    }
+
+    /// See [`PasswordSettings::try_generate()`].
+    pub fn try_generate(&self, deadline: Duration) -> Result<Vec<String>, GenerateError> {
+        self.0.try_generate(deadline)
+    }
+
+    /// See [`PasswordSettings::iter()`].
+    pub fn iter(&self) -> Result<PasswordIter<'_>, GenerateError> {
+        self.0.iter()
+    }
+
+    /// See [`PasswordSettings::generate_with()`].
+    pub fn generate_with(&self, options: &GenerateOptions) -> Result<Vec<String>, GenerateError> {
+        self.0.generate_with(options)
+    }
+
+    /// See [`PasswordSettings::generate_with_rng()`].
+    pub fn generate_with_rng<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<Vec<String>, GenerateError> {
+        self.0.generate_with_rng(rng)
+    }
+
+    /// See [`PasswordSettings::generate_parallel()`].
+    #[cfg(feature = "rayon")]
+    pub fn generate_parallel(&self) -> Result<Vec<String>, GenerateError> {
+        self.0.generate_parallel()
+    }
+
+    /// See [`PasswordSettings::generate_n_parallel()`].
+    #[cfg(feature = "rayon")]
+    pub fn generate_n_parallel(&self, n: usize) -> Result<Vec<String>, GenerateError> {
+        self.0.generate_n_parallel(n)
+    }
+}
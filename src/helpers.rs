@@ -1,12 +1,45 @@
+use crate::{
+    lexicon::CapStrategy,
+    report::{SkipReason, WalkReport},
+    settings::LengthUnit,
+};
+use deunicode::deunicode;
+use rand::Rng;
 use regex::Regex;
-use snafu::{ensure, Snafu};
-use std::{fs, ops::RangeInclusive, path::Path, str::FromStr};
+use snafu::{ensure, ResultExt, Snafu};
+use std::{
+    collections::HashSet,
+    fs::{self, File},
+    io::{self, Read},
+    num::ParseIntError,
+    ops::{Range, RangeInclusive},
+    path::Path,
+    str::FromStr,
+};
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Get a positive inclusive range (..=) from a string in the format of "20-50" or "24".
 ///
 /// This function does some clean-up beforehand to remove trailing and repeating dashes.
 /// So `---20-----30--` becomes `20-30`, and gives no error.
 ///
+/// It never panics, whatever the input.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{range_inc_from_str, ParseRangeError};
+/// assert_eq!(range_inc_from_str("---20-----30--").unwrap(), 20..=30);
+/// assert_eq!(range_inc_from_str("24").unwrap(), 24..=24);
+///
+/// for invalid in ["", "-", "٣", "99999999999999999999999-1"] {
+///     assert!(matches!(
+///         range_inc_from_str(invalid),
+///         Err(ParseRangeError::InvalidNumber { .. })
+///     ));
+/// }
+/// ```
+///
 /// TODO: Adjust it accordingly when making the example GUI.
 pub fn range_inc_from_str(range: &str) -> Result<RangeInclusive<usize>, ParseRangeError> {
     let min;
@@ -25,14 +58,14 @@ pub fn range_inc_from_str(range: &str) -> Result<RangeInclusive<usize>, ParseRan
 
     if range.contains('-') {
         let r: Vec<&str> = range.split('-').collect();
-        min = usize::from_str(r[0]).unwrap();
-        max = usize::from_str(r[1]).unwrap();
+        min = usize::from_str(r[0]).context(InvalidNumberSnafu)?;
+        max = usize::from_str(r[1]).context(InvalidNumberSnafu)?;
 
         ensure!(min <= max, RightSideIsSmallerSnafu);
 
         Ok(RangeInclusive::new(min, max))
     } else {
-        min = usize::from_str(&range).unwrap();
+        min = usize::from_str(&range).context(InvalidNumberSnafu)?;
         max = min;
 
         Ok(RangeInclusive::new(min, max))
@@ -51,34 +84,377 @@ pub enum ParseRangeError {
     /// When the right side of the range is smaller than the left side like "35-25".
     #[snafu(display("right side of range can't be smaller than left side"))]
     RightSideIsSmaller,
+    /// When a side is missing like "" or "-", too big for a `usize`,
+    /// or made of numeric characters other than the ASCII digits.
+    #[snafu(display("not a valid number: {source}"))]
+    InvalidNumber { source: ParseIntError },
 }
 
-pub(crate) fn get_text_from_dir(
+/// Call `f` with the path and contents of every file in the directory, recursively,
+/// skipping the ones that can't be read as UTF-8.
+///
+/// A single buffer is reused for every file, so only one is kept in memory at a time.
+/// With `sorted`, the entries of each directory are read in the order of their names.
+pub(crate) fn for_each_text_in_dir(
     dir: impl AsRef<Path>,
-    text: &mut String,
-) -> Result<(), std::io::Error> {
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            get_text_from_dir(&path, text)?;
-        } else {
-            text.push_str(fs::read_to_string(&path).unwrap_or_default().as_str());
+    sorted: bool,
+    f: &mut impl FnMut(&Path, &str),
+) -> io::Result<WalkReport> {
+    fn visit(
+        dir: &Path,
+        sorted: bool,
+        buf: &mut String,
+        report: &mut WalkReport,
+        f: &mut impl FnMut(&Path, &str),
+    ) -> io::Result<()> {
+        let mut paths = fs::read_dir(dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        if sorted {
+            paths.sort_unstable();
+        }
+
+        for path in paths {
+            if path.is_dir() {
+                visit(&path, sorted, buf, report, f)?;
+            } else {
+                let read = read_found(&path, |path| {
+                    buf.clear();
+                    let len = File::open(path)?.read_to_string(buf)?;
+                    Ok(Some(((), len as u64)))
+                });
+                match read {
+                    Ok(()) => {
+                        report.read += 1;
+                        f(&path, buf);
+                    }
+                    Err(reason) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!(path = %path.display(), ?reason, "skipping file");
+                        report.skipped.push((path, reason));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    let mut report = WalkReport::default();
+    visit(dir.as_ref(), sorted, &mut String::new(), &mut report, f)?;
+    Ok(report)
+}
+
+/// Read a file found while walking a directory with `read`, which gives what was read
+/// along with its size in bytes, or `None` if it isn't text.
+///
+/// A file that vanishes or changes while being read is retried once.
+pub(crate) fn read_found<T>(
+    path: &Path,
+    mut read: impl FnMut(&Path) -> io::Result<Option<(T, u64)>>,
+) -> Result<T, SkipReason> {
+    let reason_for = |error: io::Error| match error.kind() {
+        io::ErrorKind::NotFound => SkipReason::Vanished,
+        io::ErrorKind::InvalidData => SkipReason::NotText,
+        _ => SkipReason::Unreadable,
+    };
+
+    let mut retried = false;
+    loop {
+        let reason = match fs::metadata(path).map_err(reason_for) {
+            Ok(metadata) => match read(path) {
+                Ok(Some((read, len))) if !differs_wildly(metadata.len(), len) => return Ok(read),
+                Ok(Some(_)) => SkipReason::ChangedDuringRead,
+                Ok(None) => SkipReason::NotText,
+                Err(error) => reason_for(error),
+            },
+            Err(reason) => reason,
+        };
+
+        let retry = matches!(reason, SkipReason::Vanished | SkipReason::ChangedDuringRead);
+        if !retry || retried {
+            return Err(reason);
+        }
+        retried = true;
+    }
+}
+
+/// Whether two sizes differ by more than half of the larger one.
+fn differs_wildly(a: u64, b: u64) -> bool {
+    a.abs_diff(b) > a.max(b) / 2
+}
+
+/// How a text file is encoded, as told by [`sniff_encoding()`].
+#[cfg(feature = "from_path")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TextEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+}
+
+/// Guess the encoding of a file from its first 8 KiB,
+/// giving `None` for anything that doesn't look like text.
+#[cfg(feature = "from_path")]
+pub(crate) fn sniff_encoding(file: &mut impl Read) -> Option<TextEncoding> {
+    use simdutf8::compat::from_utf8;
+
+    let mut buf = [0; 8 * 1024];
+    let mut read = 0;
+    while read < buf.len() {
+        match file.read(&mut buf[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => return None,
+        }
+    }
+    let sample = &buf[..read];
+
+    let (encoding, text) = match sample {
+        // UTF-32 isn't supported, and its BOM has to be checked before the one of UTF-16.
+        [0xFF, 0xFE, 0, 0, ..] | [0, 0, 0xFE, 0xFF, ..] => return None,
+        [0xFF, 0xFE, ..] => return Some(TextEncoding::Utf16Le),
+        [0xFE, 0xFF, ..] => return Some(TextEncoding::Utf16Be),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => (TextEncoding::Utf8Bom, rest),
+        _ => (TextEncoding::Utf8, sample),
+    };
+
+    // Valid UTF-8, but a sure sign of a binary file or UTF-16 without a BOM.
+    if text.contains(&0) {
+        return None;
+    }
+
+    match from_utf8(text) {
+        Ok(_) => Some(encoding),
+        // A character cut off by the end of the sample.
+        Err(e) if e.error_len().is_none() && read == buf.len() => Some(encoding),
+        Err(_) => None,
+    }
+}
+
+/// Read the whole file as text in the sniffed encoding, along with its size in bytes,
+/// for [`read_found()`].
+///
+/// UTF-16 is only decoded with the `utf16` feature.
+#[cfg(feature = "from_path")]
+pub(crate) fn read_text(path: &Path, encoding: TextEncoding) -> io::Result<Option<(String, u64)>> {
+    let bytes = fs::read(path)?;
+    let len = bytes.len() as u64;
+    let text = match encoding {
+        TextEncoding::Utf8 => String::from_utf8(bytes).ok(),
+        // The BOM could be gone if the file changed since it was sniffed.
+        TextEncoding::Utf8Bom => String::from_utf8(bytes).ok().map(|mut text| {
+            if text.starts_with('\u{feff}') {
+                text.drain(..'\u{feff}'.len_utf8());
+            }
+            text
+        }),
+        #[cfg(feature = "utf16")]
+        TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+            let units = bytes
+                .get(2..)
+                .unwrap_or_default()
+                .chunks_exact(2)
+                .map(|pair| {
+                    let pair = [pair[0], pair[1]];
+                    match encoding {
+                        TextEncoding::Utf16Le => u16::from_le_bytes(pair),
+                        _ => u16::from_be_bytes(pair),
+                    }
+                });
+            Some(
+                char::decode_utf16(units)
+                    .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                    .collect(),
+            )
+        }
+        #[cfg(not(feature = "utf16"))]
+        TextEncoding::Utf16Le | TextEncoding::Utf16Be => None,
+    };
+    Ok(text.map(|text| (text, len)))
+}
+
+/// Uppercase the character at the char index `i`, which might change the length of the string.
+///
+/// Returns the byte range that was replaced along with the length of its replacement.
+pub(crate) fn capitalise(s: &mut String, i: usize) -> Option<(Range<usize>, usize)> {
+    let (start, c) = s.char_indices().nth(i)?;
+    let upper: String = c.to_uppercase().collect();
+    let range = start..start + c.len_utf8();
+    s.replace_range(range.clone(), &upper);
+    Some((range, upper.len()))
+}
+
+/// Lowercase the character at the char index `i`, which might change the length of the string.
+///
+/// Returns the byte range that was replaced along with the length of its replacement.
+pub(crate) fn decapitalise(s: &mut String, i: usize) -> Option<(Range<usize>, usize)> {
+    let (start, c) = s.char_indices().nth(i)?;
+    let lower: String = c.to_lowercase().collect();
+    let range = start..start + c.len_utf8();
+    s.replace_range(range.clone(), &lower);
+    Some((range, lower.len()))
+}
+
+/// Up to `limit` words starting at `offset`, or none if it's past the end.
+pub(crate) fn words_page(words: &[String], offset: usize, limit: usize) -> Vec<String> {
+    words.iter().skip(offset).take(limit).cloned().collect()
+}
+
+/// Up to `limit` words starting with `prefix`, along with their index.
+pub(crate) fn find_words(words: &[String], prefix: &str, limit: usize) -> Vec<(usize, String)> {
+    words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| word.starts_with(prefix))
+        .take(limit)
+        .map(|(i, word)| (i, word.clone()))
+        .collect()
+}
+
+/// Whether each word is the first one to look like it when lowercased,
+/// and also transliterated into ASCII if `transliterate` is set.
+pub(crate) fn first_occurrences(words: &[String], transliterate: bool) -> Vec<bool> {
+    let mut seen = HashSet::with_capacity(words.len());
+    words
+        .iter()
+        .map(|word| {
+            if transliterate {
+                seen.insert(deunicode(word).to_lowercase())
+            } else {
+                seen.insert(word.to_lowercase())
+            }
+        })
+        .collect()
+}
+
+/// The length of the string in the given unit.
+pub(crate) fn unit_len(s: &str, unit: LengthUnit) -> usize {
+    match unit {
+        LengthUnit::Chars => s.chars().count(),
+        LengthUnit::Graphemes => s.graphemes(true).count(),
+    }
+}
+
+/// An order-sensitive FNV-1a hash of the words, each prefixed by its length
+/// so that moving letters between words changes it, and the same on every platform.
+pub(crate) fn fingerprint<'a>(words: impl IntoIterator<Item = &'a String>) -> u64 {
+    let mut fingerprint = Fingerprint::new();
+    for word in words {
+        fingerprint.push(word);
+    }
+    fingerprint.finish()
+}
+
+/// A [`fingerprint()`] of strings pushed one at a time, like the texts of a directory.
+pub(crate) struct Fingerprint(u64);
+
+impl Fingerprint {
+    const OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01B3;
+
+    pub(crate) fn new() -> Self {
+        Self(Self::OFFSET)
+    }
+
+    pub(crate) fn push(&mut self, s: &str) {
+        for byte in (s.len() as u64).to_le_bytes().iter().chain(s.as_bytes()) {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
         }
     }
 
-    Ok(())
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
 }
 
-pub(crate) fn capitalise(s: &mut str, i: usize) {
-    if let Some(c) = s.get_mut(i..i + 1) {
-        c.make_ascii_uppercase();
+/// The amount of uppercase letters, of any script.
+pub(crate) fn count_upper(s: &str) -> usize {
+    s.chars().filter(|c| c.is_uppercase()).count()
+}
+
+/// The amount of lowercase letters, of any script.
+pub(crate) fn count_lower(s: &str) -> usize {
+    s.chars().filter(|c| c.is_lowercase()).count()
+}
+
+/// The amount of ASCII digits, the only ones that are inserted.
+pub(crate) fn count_digits(s: &str) -> usize {
+    s.chars().filter(char::is_ascii_digit).count()
+}
+
+/// The byte index at which each unit of the string starts.
+pub(crate) fn unit_indices(s: &str, unit: LengthUnit) -> Vec<usize> {
+    match unit {
+        LengthUnit::Chars => s.char_indices().map(|(i, _)| i).collect(),
+        LengthUnit::Graphemes => s.grapheme_indices(true).map(|(i, _)| i).collect(),
+    }
+}
+
+/// Overwrite a string that held part of a password before dropping it, with the `zeroize` feature.
+#[cfg(feature = "zeroize")]
+pub(crate) fn wipe(mut s: String) {
+    zeroize::Zeroize::zeroize(&mut s);
+}
+
+#[cfg(not(feature = "zeroize"))]
+pub(crate) fn wipe(_: String) {}
+
+/// Make room for `additional` more bytes in a string that holds part of a password.
+///
+/// With the `zeroize` feature, the string is moved to a larger buffer here so that
+/// the old one can be wiped, instead of leaving a copy behind when it reallocates.
+#[cfg(feature = "zeroize")]
+pub(crate) fn reserve(s: &mut String, additional: usize) {
+    if s.capacity() - s.len() < additional {
+        let mut grown = String::with_capacity((s.len() + additional).max(s.capacity() * 2));
+        grown.push_str(s);
+        wipe(std::mem::replace(s, grown));
+    }
+}
+
+#[cfg(not(feature = "zeroize"))]
+pub(crate) fn reserve(_: &mut String, _: usize) {}
+
+/// Shorten the string to at most `len` units without ever splitting a grapheme cluster.
+pub(crate) fn truncate_units(s: &mut String, len: usize, unit: LengthUnit) {
+    let end = match unit {
+        LengthUnit::Chars => s.char_indices().nth(len).map(|(i, _)| i),
+        LengthUnit::Graphemes => s.grapheme_indices(true).nth(len).map(|(i, _)| i),
+    };
+
+    if let Some(end) = end {
+        let end = s
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .take_while(|&i| i <= end)
+            .last()
+            .unwrap_or_default();
+        s.truncate(end);
     }
 }
 
-pub(crate) fn decapitalise(s: &mut str, i: usize) {
-    if let Some(c) = s.get_mut(i..i + 1) {
-        c.make_ascii_lowercase();
+/// Where the next word goes in a list of `len` words capped at `max`,
+/// with `seen` words extracted so far including it, if anywhere.
+pub(crate) fn capped_slot<R: Rng + ?Sized>(
+    len: usize,
+    seen: usize,
+    max: usize,
+    strategy: CapStrategy,
+    rng: &mut R,
+) -> Option<usize> {
+    if len < max {
+        return Some(len);
+    }
+
+    match strategy {
+        CapStrategy::Stop => None,
+        CapStrategy::Reservoir => {
+            let slot = rng.gen_range(0..seen);
+            (slot < max).then_some(slot)
+        }
     }
 }
@@ -0,0 +1,184 @@
+use crate::{
+    arena::{ShuffledWords, WordArena},
+    options::GenerateOptions,
+    password::{WordPicker, Words},
+    report::RolledParameters,
+    settings::{spend_pool, GenerateError, PasswordSettings},
+    sources::SourceMixing,
+};
+use rand::{rngs::ThreadRng, thread_rng, Rng};
+use std::{borrow::Cow, collections::HashSet, fmt, iter::FusedIterator, time::Instant};
+
+/// An endless iterator of passwords, made with [`PasswordSettings::iter()`].
+///
+/// It only ends early if a password can't be generated, like when it's
+/// [rejected by a policy](crate::RetryBudget::policy) too many times,
+/// and [`error()`](PasswordIter::error) tells why.
+/// With [`unique`](PasswordSettings#structfield.unique), it keeps every password
+/// it gave to never give it again, so it ends once it runs out of new ones.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::PasswordSettings;
+/// let mut settings = PasswordSettings::new();
+/// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+/// settings.seed = Some(3);
+///
+/// let mut passwords = settings.iter().unwrap();
+/// let first: Vec<String> = passwords.by_ref().take(5).collect();
+/// assert_eq!(first, settings.generate_n(5).unwrap());
+/// assert!(passwords.next().is_some());
+/// assert!(passwords.error().is_none());
+/// ```
+pub struct PasswordIter<'a> {
+    settings: &'a PasswordSettings,
+    words: IterWords<'a>,
+    picker: WordPicker,
+    options: GenerateOptions,
+    batch: Option<RolledParameters>,
+    pool: Option<usize>,
+    deadline: Option<Instant>,
+    rng: ThreadRng,
+    index: usize,
+    /// The assembly retries of the passwords generated so far.
+    resets: usize,
+    /// The passwords given so far, with [`unique`](PasswordSettings#structfield.unique).
+    seen: HashSet<String>,
+    error: Option<GenerateError>,
+}
+
+/// The words to select from, kept for as long as passwords are generated from them.
+pub(crate) enum IterWords<'a> {
+    Arena(&'a WordArena),
+    Shuffled(Cow<'a, ShuffledWords>),
+    Mixed(Vec<&'a str>),
+}
+
+impl<'a> IterWords<'a> {
+    /// The words to select from with the settings, along with the picker for them,
+    /// shuffled with `seed`, or with `rng` if there's none.
+    pub(crate) fn with_picker<R: Rng + ?Sized>(
+        settings: &'a PasswordSettings,
+        rng: &mut R,
+        seed: Option<u64>,
+    ) -> (Self, WordPicker) {
+        match settings.source_mixing {
+            SourceMixing::Concatenate if !settings.shuffles_view() => (
+                IterWords::Arena(settings.arena.get(&settings.words)),
+                WordPicker::new(settings, &settings.word_sources),
+            ),
+            SourceMixing::Concatenate => {
+                let shuffled = settings.shuffled_words(seed);
+                let picker = WordPicker::new(settings, &shuffled.sources);
+                (IterWords::Shuffled(shuffled), picker)
+            }
+            _ => {
+                let (words, sources) = settings.mixed_view(rng, seed);
+                (IterWords::Mixed(words), WordPicker::new(settings, &sources))
+            }
+        }
+    }
+
+    pub(crate) fn get(&self) -> Words<'_> {
+        match self {
+            IterWords::Arena(arena) => Words::Arena(arena),
+            IterWords::Shuffled(shuffled) => Words::Arena(&shuffled.arena),
+            IterWords::Mixed(words) => Words::Strs(words),
+        }
+    }
+}
+
+impl<'a> PasswordIter<'a> {
+    pub(crate) fn new(settings: &'a PasswordSettings) -> Self {
+        let mut rng = thread_rng();
+        let options = GenerateOptions::default();
+
+        let (words, picker) = IterWords::with_picker(settings, &mut rng, settings.seed);
+        let batch = settings.batch_roll(&options, &mut rng, settings.seed);
+
+        Self {
+            settings,
+            words,
+            picker,
+            options,
+            batch,
+            pool: settings.batch_retry.pool(),
+            deadline: None,
+            rng,
+            index: 0,
+            resets: 0,
+            seen: HashSet::new(),
+            error: None,
+        }
+    }
+
+    /// End with [`GenerateError::TimedOut`] once `deadline` has passed.
+    pub(crate) fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Why the iterator ended, if it did.
+    pub fn error(&self) -> Option<&GenerateError> {
+        self.error.as_ref()
+    }
+
+    pub(crate) fn take_error(&mut self) -> Option<GenerateError> {
+        self.error.take()
+    }
+}
+
+impl Iterator for PasswordIter<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        let words = self.words.get();
+        let settings = self.settings;
+        let details = settings.generate_unique(
+            self.index,
+            &mut self.seen,
+            settings.seed,
+            &mut self.rng,
+            |rng| {
+                settings
+                    .password_builder(words, &self.picker, &self.options, &self.batch, rng)
+                    .with_pool(self.pool)
+                    .with_deadline(self.deadline)
+                    .generate_checked(rng)
+            },
+        );
+
+        match details {
+            Ok(details) => {
+                spend_pool(&mut self.pool, details.retries.assembly, self.index);
+                self.index += 1;
+                self.resets += details.retries.assembly;
+                Some(details.password)
+            }
+            Err(mut error) => {
+                if let GenerateError::TimedOut { source } = &mut error {
+                    source.finished = self.index;
+                    source.resets += self.resets;
+                }
+                self.error = Some(error);
+                None
+            }
+        }
+    }
+}
+
+impl FusedIterator for PasswordIter<'_> {}
+
+impl fmt::Debug for PasswordIter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PasswordIter")
+            .field("generated", &self.index)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
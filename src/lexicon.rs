@@ -1,11 +1,25 @@
+use crate::{
+    helpers::{capped_slot, find_words, fingerprint, first_occurrences, words_page},
+    report::WordStats,
+    PasswordSettings,
+};
 use deunicode::deunicode;
-use rand::{seq::SliceRandom, thread_rng};
-use std::mem::{swap, take};
+use rand::{
+    seq::{index, SliceRandom},
+    thread_rng, Rng,
+};
+use std::{
+    fs::File,
+    io::{self, Read},
+    mem::take,
+    path::Path,
+};
 use unicode_segmentation::UnicodeSegmentation;
 
 /// A list of words used for password generation.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Lexicon {
     /// Name of this collection of words.
     pub name: String,
@@ -53,12 +67,108 @@ pub struct Lexicon {
     /// Flag for randomising all the words at the end of word extraction.
     pub randomise: bool,
 
+    /// Flag for reading the entries of each directory in the order of their names
+    /// in [`Lexicon::extract_words_from_path()`].
+    ///
+    /// The order in which the entries are listed otherwise depends on the platform
+    /// and file system, so the words could come out in a different order on another machine.
+    /// Sorting has the small cost of collecting the entries of each directory before reading them.
+    /// It's turned on by default.
+    pub sort_entries: bool,
+
+    /// Flag for reading hidden directories and files in [`Lexicon::extract_words_from_path()`],
+    /// which are otherwise skipped unless their path is passed directly.
+    pub include_hidden: bool,
+
+    /// Flag for telling hidden directories and files apart by their hidden attribute
+    /// instead of their name starting with `.`, which only has an effect on Windows.
+    pub hidden_by_attribute: bool,
+
+    /// The most words to keep, with [`cap_strategy`](Lexicon::cap_strategy) deciding
+    /// what happens to the words extracted after that. Words already in the lexicon
+    /// are never removed for it.
+    ///
+    /// ```
+    /// # use genrepass::{CapStrategy, Lexicon, Split};
+    /// let text = |words: std::ops::Range<usize>| {
+    ///     words.map(|i| format!("w{i}")).collect::<Vec<_>>().join(" ")
+    /// };
+    ///
+    /// let mut lexicon = Lexicon::new("capped", Split::UnicodeWords);
+    /// lexicon.max_words = Some(100);
+    /// lexicon.extract_words(&text(0..1000), |_| true);
+    /// assert_eq!(lexicon.words().len(), 100);
+    /// assert_eq!(lexicon.words()[99], "w99");
+    /// assert_eq!(lexicon.dropped_words(), 900);
+    ///
+    /// let mut lexicon = Lexicon::new("sampled", Split::UnicodeWords);
+    /// lexicon.max_words = Some(100);
+    /// lexicon.cap_strategy = CapStrategy::Reservoir;
+    /// lexicon.extract_words(&text(0..500), |_| true);
+    /// lexicon.extract_words(&text(500..1000), |_| true);
+    /// assert_eq!(lexicon.words().len(), 100);
+    /// assert_eq!(lexicon.dropped_words(), 900);
+    /// // Drawn from the whole input, not just the start of it.
+    /// let index = |w: &String| w[1..].parse::<usize>().unwrap();
+    /// assert!(lexicon.words().iter().any(|w| index(w) >= 500));
+    /// assert!(lexicon.words().iter().filter(|w| index(w) < 100).count() < 50);
+    /// ```
+    pub max_words: Option<usize>,
+
+    /// What happens to the words extracted after reaching [`max_words`](Lexicon::max_words).
+    pub cap_strategy: CapStrategy,
+
+    /// The amount of words extracted so far, including the ones not kept.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) words_seen: usize,
+
+    /// The amount of words left out or replaced for [`max_words`](Lexicon::max_words).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) words_dropped: usize,
+
     /// All the extracted words.
-    words: Vec<String>,
+    pub(crate) words: Vec<String>,
+}
+
+impl Default for Lexicon {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            split: Split::default(),
+            deunicode: Deunicode::default(),
+            randomise: false,
+            sort_entries: true,
+            include_hidden: false,
+            hidden_by_attribute: false,
+            max_words: None,
+            cap_strategy: CapStrategy::Stop,
+            words_seen: 0,
+            words_dropped: 0,
+            words: Vec::new(),
+        }
+    }
+}
+
+impl From<&PasswordSettings> for Lexicon {
+    /// A [default](Lexicon::default) [`Lexicon`] with a copy of the words of the settings,
+    /// in their stored order, for the conversion back from
+    /// [`PasswordSettings::from()`](crate::PasswordSettings#impl-From<Lexicon>-for-PasswordSettings).
+    ///
+    /// Nothing else carries over, not even the labels of the sources.
+    /// Being infallible, it also provides `Lexicon::try_from(&settings)`.
+    fn from(settings: &PasswordSettings) -> Self {
+        let words = settings.clone_words();
+        Self {
+            words_seen: words.len(),
+            words,
+            ..Default::default()
+        }
+    }
 }
 
 impl Lexicon {
-    /// Create a new [`Lexicon`] with a specific split mode and everything turned off.
+    /// Create a new [`Lexicon`] with a specific split mode, sorted directory entries
+    /// and everything else turned off.
     pub fn new<S>(name: S, split: Split) -> Self
     where
         S: Into<String>,
@@ -81,174 +191,245 @@ impl Lexicon {
     where
         F: FnMut(char) -> bool,
     {
-        if text.is_empty() {
-            return;
-        }
+        let old_len = self.words.len();
+        push_split_words(
+            &self.split,
+            self.deunicode,
+            text,
+            &mut filter,
+            &mut self.words,
+        );
+        self.cap_words_from(old_len);
 
-        let deunicoded;
-        let text = if let Deunicode::BeforeSplitting = self.deunicode {
-            deunicoded = deunicode(text);
-            &deunicoded
-        } else {
-            text
-        };
+        if self.randomise {
+            self.randomise();
+        }
+    }
 
-        let mut split_words: Vec<String> = match &self.split {
-            Split::UnicodeWords => text.unicode_words().map(str::to_string).collect(),
-            Split::WordBounds => text.split_word_bounds().map(str::to_string).collect(),
-            Split::UnicodeWhitespace => text.split_whitespace().map(str::to_string).collect(),
-            Split::AsciiWhitespace => text.split_ascii_whitespace().map(str::to_string).collect(),
-            Split::Chars(chars) => text.split(&chars[..]).map(str::to_string).collect(),
+    /// Apply [`max_words`](Lexicon::max_words) to the words pushed after `old_len`,
+    /// returning how many of them were kept.
+    fn cap_words_from(&mut self, old_len: usize) -> usize {
+        let added = self.words.len() - old_len;
+        let Some(max) = self.max_words else {
+            self.words_seen += added;
+            return added;
         };
 
-        for word in split_words.iter_mut() {
-            if word.is_empty() {
-                continue;
-            }
-
-            if let Deunicode::BeforeFiltering = self.deunicode {
-                let mut deunicoded = deunicode(word);
-                swap(word, &mut deunicoded);
+        let mut rng = thread_rng();
+        let mut kept = 0;
+        for word in self.words.split_off(old_len) {
+            self.words_seen += 1;
+            let len = self.words.len();
+            match capped_slot(len, self.words_seen, max, self.cap_strategy, &mut rng) {
+                Some(slot) if slot == len => self.words.push(word),
+                Some(slot) => {
+                    self.words[slot] = word;
+                    self.words_dropped += 1;
+                }
+                None => {
+                    self.words_dropped += 1;
+                    continue;
+                }
             }
+            kept += 1;
+        }
 
-            word.retain(&mut filter);
-
-            if word.is_empty() {
-                continue;
-            }
+        kept
+    }
 
-            if let Deunicode::AfterFiltering = self.deunicode {
-                let mut deunicoded = deunicode(word);
+    /// The amount of extracted words that were left out, or later replaced,
+    /// to keep to [`max_words`](Lexicon::max_words).
+    pub fn dropped_words(&self) -> usize {
+        self.words_dropped
+    }
 
-                if deunicoded.is_empty() {
-                    continue;
-                } else {
-                    self.words.push(take(&mut deunicoded));
-                }
-            } else {
-                self.words.push(take(word));
-            }
+    /// Start extracting words from text that comes in pieces, like a huge file read
+    /// in chunks, so that the work can be paced instead of blocking on it all at once.
+    ///
+    /// The words are split and filtered as with [`Lexicon::extract_words()`],
+    /// but only added to the lexicon by [`ExtractionSession::finish()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{Lexicon, Split};
+    /// let mut lexicon = Lexicon::new("chunks", Split::UnicodeWords);
+    /// let mut session = lexicon.begin_extraction(|_| true);
+    /// session.push_text("The quick br");
+    /// session.push_text("own fox jum");
+    /// session.push_text("ps over the lazy dog");
+    /// assert_eq!(session.finish(), 9);
+    ///
+    /// assert_eq!(
+    ///     lexicon.words(),
+    ///     ["The", "quick", "brown", "fox", "jumps", "over", "the", "lazy", "dog"]
+    /// );
+    /// ```
+    pub fn begin_extraction<F>(&mut self, filter: F) -> ExtractionSession<'_, F>
+    where
+        F: FnMut(char) -> bool,
+    {
+        ExtractionSession {
+            lexicon: self,
+            filter,
+            pending: String::new(),
+            words: Vec::new(),
         }
+    }
 
-        if self.randomise {
-            self.randomise();
+    /// Read texts from paths and extract the words,
+    /// walking directories as deep as `depth` and only reading files with one of the `extensions`.
+    ///
+    /// The same as [`Lexicon::extract_words_from_path_with()`] with the rest of the
+    /// [`WalkOptions`](crate::WalkOptions) left as the defaults.
+    #[cfg(feature = "from_path")]
+    pub fn extract_words_from_path<F>(
+        &mut self,
+        paths: &[impl AsRef<std::path::Path>],
+        depth: usize,
+        extensions: Option<&[&str]>,
+        filter: F,
+    ) -> crate::WalkReport
+    where
+        F: FnMut(char) -> bool,
+    {
+        let mut options = crate::WalkOptions::new().depth(depth);
+        if let Some(extensions) = extensions {
+            options = options.extensions(extensions.iter().copied());
         }
+        self.extract_words_from_path_with(paths, &options, filter)
     }
 
-    /// Read texts from paths and extract the words.
+    /// Read texts from paths and extract the words, walking directories as set in `options`.
+    ///
+    /// Returns which files were read and which were skipped.
     ///
     /// The way this method is configured:
-    /// * Symbolic links aren't followed
-    /// * Directories and files returning any kind of IO error are silently skipped
+    /// * Symbolic links aren't followed unless [`WalkOptions::follow_links()`](crate::WalkOptions::follow_links) is set
+    /// * Directories returning any kind of IO error are silently skipped,
+    ///   while files are skipped with a [`SkipReason`](crate::SkipReason) in the report
     /// * Hidden directories and files (meaning they start with `.`) are ignored,
     ///   except if you pass the path to the hidden directory or file directly
+    ///   or turn on [`include_hidden`](Lexicon::include_hidden)
     /// * Some common extensions are ignored by default because they can't be parsed to UTF-8 anyway
     /// * Extensions are compared ignoring ASCII case, with just the text after the last `.`
     /// * Passing a path to a file ignores all filtering
+    /// * Entries are read in the order of their names with [`sort_entries`](Lexicon::sort_entries)
     /// * All the files that pass the filtering are checked for if they are valid UTF-8
-    ///   by reading a few bytes at the start of the file
+    ///   by reading up to 8 KiB at the start of the file, skipping the ones that look binary
+    /// * A UTF-8 BOM is removed, while UTF-16 files are only read with the `utf16` feature
+    ///   and UTF-32 ones are skipped
     ///
     /// See [`Lexicon::extract_words()`] for how the words are extracted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CharFilter, Lexicon, Split, WalkOptions};
+    /// let mut lexicon = Lexicon::new("sources", Split::UnicodeWords);
+    /// let options = WalkOptions::new().depth(2).extensions(["rs"]);
+    /// lexicon.extract_words_from_path_with(&["src"], &options, CharFilter::Unicode.closure());
+    ///
+    /// assert!(lexicon.words().iter().any(|word| word == "WalkOptions"));
+    /// ```
     #[cfg(feature = "from_path")]
-    pub fn extract_words_from_path<F>(
+    pub fn extract_words_from_path_with<F>(
         &mut self,
         paths: &[impl AsRef<std::path::Path>],
-        depth: usize,
-        extensions: Option<&[&str]>,
+        options: &crate::WalkOptions,
         filter: F,
-    ) where
+    ) -> crate::WalkReport
+    where
         F: FnMut(char) -> bool,
     {
-        use simdutf8::compat::from_utf8;
-        use std::{
-            fs::{read_to_string, File},
-            io::Read,
-        };
+        use crate::helpers::{read_found, read_text, sniff_encoding};
+        use std::fs::File;
         use walkdir::{DirEntry, WalkDir};
 
-        // A list of extensions that could appear in something like ~/Documents
-        // but that are not able to be read as UTF-8 anyway,
-        // some even giving false positives like PDF and MP3.
-        let ignored_extensions = [
-            "pdf", "epub", "mobi", "azw3", "doc", "docx", "mp3", "mp4", "avi", "ogg", "jpg",
-            "jpeg", "png", "gif",
-        ];
-
+        let (include_hidden, by_attribute) = (self.include_hidden, self.hidden_by_attribute);
+        let extensions: Option<Vec<&str>> = options
+            .extensions
+            .as_ref()
+            .map(|extensions| extensions.iter().map(String::as_str).collect());
+        let extensions = extensions.as_deref();
         let filter_entry = |e: &DirEntry| {
             if e.depth() == 0 && e.file_type().is_file() {
                 true
-            } else if e.depth() != 0
-                && e.file_name()
-                    .to_str()
-                    .map(|s| s.starts_with("."))
-                    .unwrap_or_default()
-            {
+            } else if e.depth() != 0 && !include_hidden && is_hidden_entry(e, by_attribute) {
                 false
             } else if e.file_type().is_file() {
-                match e.file_name().to_str() {
-                    Some(s) => match s.rsplit_once('.') {
-                        Some((_, ext)) => {
-                            if ignored_extensions
-                                .iter()
-                                .any(|allowed_ext| allowed_ext.eq_ignore_ascii_case(ext))
-                            {
-                                false
-                            } else {
-                                match extensions {
-                                    Some(extensions) => extensions
-                                        .iter()
-                                        .any(|allowed_ext| allowed_ext.eq_ignore_ascii_case(ext)),
-                                    None => true,
-                                }
-                            }
-                        }
-                        None => !extensions.is_some(),
-                    },
-                    None => false,
-                }
+                has_text_extension(e.path(), extensions)
             } else {
                 true
             }
         };
 
         let mut texts = String::new();
-        let mut buf = [0; 64];
+        let mut report = crate::WalkReport::default();
 
         for path in paths {
-            for entry in WalkDir::new(&path)
-                .max_depth(depth)
+            let mut walk = WalkDir::new(path)
+                .max_depth(options.depth)
+                .follow_links(options.follow_links);
+            if self.sort_entries {
+                walk = walk.sort_by_file_name();
+            }
+
+            for entry in walk
                 .into_iter()
                 .filter_entry(|e| filter_entry(e))
                 .filter_map(|e| e.ok())
             {
                 if entry.file_type().is_file() {
-                    if let Ok(mut file) = File::open(entry.path()) {
-                        if let Ok(_) = file.read(&mut buf) {
-                            match from_utf8(&buf) {
-                                Ok(_) => {
-                                    if let Ok(text) = read_to_string(entry.path()) {
-                                        texts.push('\n');
-                                        texts.push_str(&text);
-                                    }
-                                }
-                                Err(e) => {
-                                    if e.valid_up_to() >= 56 {
-                                        if let Ok(text) = read_to_string(entry.path()) {
-                                            texts.push('\n');
-                                            texts.push_str(&text);
-                                        }
-                                    }
-                                }
+                    let text = read_found(entry.path(), |path| {
+                        match sniff_encoding(&mut File::open(path)?) {
+                            Some(encoding) => read_text(path, encoding),
+                            None => Ok(None),
+                        }
+                    });
+                    match text {
+                        Ok(text) => {
+                            #[cfg(feature = "tracing")]
+                            tracing::debug!(
+                                path = %entry.path().display(),
+                                bytes = text.len(),
+                                "read file"
+                            );
+                            report.read += 1;
+                            texts.push('\n');
+                            texts.push_str(&text);
+                        }
+                        Err(reason) => {
+                            #[cfg(feature = "tracing")]
+                            if reason == crate::SkipReason::NotText {
+                                tracing::debug!(
+                                    path = %entry.path().display(),
+                                    "skipping file that isn't text"
+                                );
+                            } else {
+                                tracing::debug!(
+                                    path = %entry.path().display(),
+                                    ?reason,
+                                    "skipping file"
+                                );
                             }
+                            report.skipped.push((entry.into_path(), reason));
                         }
                     }
-
-                    buf = [0; 64];
                 }
             }
         }
 
+        #[cfg(feature = "tracing")]
+        let before = self.words.len();
         self.extract_words(&texts, filter);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            lexicon = %self.name,
+            words_added = self.words.len() - before,
+            "extracted words from paths"
+        );
+        report
     }
 
     /// Shuffle the words.
@@ -261,8 +442,70 @@ impl Lexicon {
         &self.words
     }
 
+    /// A fingerprint of the words in their stored order, the same as
+    /// [`PasswordSettings::words_fingerprint()`](crate::PasswordSettings::words_fingerprint)
+    /// for the same words.
+    pub fn words_fingerprint(&self) -> u64 {
+        fingerprint(&self.words)
+    }
+
+    /// Summary numbers about the words, the same as
+    /// [`PasswordSettings::word_stats()`](crate::PasswordSettings::word_stats).
+    pub fn word_stats(&self, distinct: bool) -> WordStats {
+        WordStats::of(&self.words, distinct)
+    }
+
+    /// Pick `n` different words at random without changing the stored order,
+    /// or every word in a random order if there aren't that many.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{Lexicon, Split};
+    /// # use rand::{rngs::StdRng, SeedableRng};
+    /// let mut lexicon = Lexicon::new("sample", Split::UnicodeWords);
+    /// lexicon.extract_words("one two three four five six seven eight nine ten", |_| true);
+    ///
+    /// let sample = lexicon.sample_words(4, &mut rand::thread_rng());
+    /// assert_eq!(sample.len(), 4);
+    /// let mut unique = sample.clone();
+    /// unique.sort_unstable();
+    /// unique.dedup();
+    /// assert_eq!(unique.len(), 4);
+    ///
+    /// let mut everything = lexicon.sample_words(100, &mut rand::thread_rng());
+    /// everything.sort_unstable();
+    /// let mut words = lexicon.words().to_vec();
+    /// words.sort_unstable();
+    /// assert_eq!(everything, words);
+    ///
+    /// let seeded = |seed| lexicon.sample_words(5, &mut StdRng::seed_from_u64(seed));
+    /// assert_eq!(seeded(42), seeded(42));
+    /// assert_eq!(lexicon.words()[0], "one");
+    /// ```
+    pub fn sample_words<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Vec<&str> {
+        index::sample(rng, self.words.len(), n.min(self.words.len()))
+            .into_iter()
+            .map(|i| self.words[i].as_str())
+            .collect()
+    }
+
+    /// Get a copy of up to `limit` words starting at the index `offset`,
+    /// like [`PasswordSettings::words_page()`](crate::PasswordSettings::words_page).
+    pub fn words_page(&self, offset: usize, limit: usize) -> Vec<String> {
+        words_page(&self.words, offset, limit)
+    }
+
+    /// Search for up to `limit` words that start with `prefix`,
+    /// like [`PasswordSettings::find_words()`](crate::PasswordSettings::find_words).
+    pub fn find_words(&self, prefix: &str, limit: usize) -> Vec<(usize, String)> {
+        find_words(&self.words, prefix, limit)
+    }
+
     /// Clear the vector of words.
     pub fn clear_words(&mut self) {
+        self.words_seen = 0;
+        self.words_dropped = 0;
         self.words.clear();
     }
 
@@ -275,6 +518,16 @@ impl Lexicon {
         self.words.remove(index);
     }
 
+    /// Remove the words that only differ in case, or also in accents with `transliterate`,
+    /// like [`PasswordSettings::dedupe_words_normalised()`](crate::PasswordSettings::dedupe_words_normalised).
+    pub fn dedupe_words_normalised(&mut self, transliterate: bool) -> usize {
+        let keep = first_occurrences(&self.words, transliterate);
+        let mut keep = keep.into_iter();
+        let len = self.words.len();
+        self.words.retain(|_| keep.next().unwrap());
+        len - self.words.len()
+    }
+
     /// Moves all the words of `lexicon` into `self`, leaving `lexicon` empty.
     ///
     /// # Panics
@@ -285,9 +538,314 @@ impl Lexicon {
     }
 }
 
+/// Words being extracted from text pushed in pieces, started by [`Lexicon::begin_extraction()`].
+///
+/// A piece of text can end in the middle of a word, so whatever comes after the last
+/// whitespace (or [character](Split::Chars) being split on) is held back until the
+/// next piece arrives. Dropping the session without finishing it discards the words.
+#[derive(Debug)]
+pub struct ExtractionSession<'a, F> {
+    lexicon: &'a mut Lexicon,
+    filter: F,
+    /// The end of the text so far, which might be the start of a word.
+    pending: String,
+    words: Vec<String>,
+}
+
+impl<F> ExtractionSession<'_, F>
+where
+    F: FnMut(char) -> bool,
+{
+    /// Extract the words from the next piece of text, which continues the previous one.
+    pub fn push_text(&mut self, text: &str) {
+        self.pending.push_str(text);
+
+        let is_boundary = |c: char| match &self.lexicon.split {
+            Split::AsciiWhitespace => c.is_ascii_whitespace(),
+            Split::Chars(chars) => chars.contains(&c),
+            _ => c.is_whitespace(),
+        };
+        // Cutting before a whole run of boundaries keeps it together for the next piece.
+        if let Some(last) = self.pending.rfind(is_boundary) {
+            let cut = self.pending[..last].trim_end_matches(is_boundary).len();
+            let rest = self.pending.split_off(cut);
+            self.extract_pending();
+            self.pending = rest;
+        }
+    }
+
+    /// Extract the words from a file, which ends any word left over from before.
+    ///
+    /// Returns the amount of words extracted from it. The file is read a chunk at a time,
+    /// and in case it isn't valid UTF-8 an error is returned while keeping
+    /// the words extracted up to the invalid part.
+    pub fn push_file(&mut self, path: impl AsRef<Path>) -> io::Result<usize> {
+        self.end_word();
+        let before = self.words.len();
+        let mut file = File::open(path)?;
+        let mut buf = vec![0; 64 * 1024];
+        let mut filled = 0;
+
+        loop {
+            let read = file.read(&mut buf[filled..])?;
+            if read == 0 && filled == 0 {
+                break;
+            }
+            filled += read;
+
+            let valid = match std::str::from_utf8(&buf[..filled]) {
+                Ok(text) => text.len(),
+                // Only a character cut off at the end of the chunk, unless the file ended.
+                Err(e) if e.error_len().is_none() && read != 0 => e.valid_up_to(),
+                Err(e) => {
+                    let valid = &buf[..e.valid_up_to()];
+                    self.push_text(std::str::from_utf8(valid).expect("checked to be valid UTF-8"));
+                    self.end_word();
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+                }
+            };
+
+            let text = std::str::from_utf8(&buf[..valid]).expect("checked to be valid UTF-8");
+            self.push_text(text);
+            buf.copy_within(valid..filled, 0);
+            filled -= valid;
+        }
+
+        self.end_word();
+        Ok(self.words.len() - before)
+    }
+
+    /// Add the words to the [`Lexicon`], including the one held back at the end,
+    /// and shuffle them if [`randomise`](Lexicon::randomise) is set.
+    ///
+    /// Returns the amount of words added, which can be fewer with [`max_words`](Lexicon::max_words).
+    pub fn finish(mut self) -> usize {
+        self.end_word();
+        let old_len = self.lexicon.words.len();
+        self.lexicon.words.append(&mut self.words);
+        let added = self.lexicon.cap_words_from(old_len);
+
+        if self.lexicon.randomise {
+            self.lexicon.randomise();
+        }
+
+        added
+    }
+
+    /// Treat whatever was held back as a finished word.
+    fn end_word(&mut self) {
+        self.extract_pending();
+        self.pending.clear();
+    }
+
+    fn extract_pending(&mut self) {
+        push_split_words(
+            &self.lexicon.split,
+            self.lexicon.deunicode,
+            &self.pending,
+            &mut self.filter,
+            &mut self.words,
+        );
+    }
+}
+
+/// Extensions that could appear in something like ~/Documents
+/// but that are not able to be read as UTF-8 anyway,
+/// some even giving false positives like PDF and MP3.
+///
+/// Files with these extensions are skipped by [`Lexicon::extract_words_from_path()`].
+pub const DEFAULT_IGNORED_EXTENSIONS: &[&str] = &[
+    "pdf", "epub", "mobi", "azw3", "doc", "docx", "mp3", "mp4", "avi", "ogg", "jpg", "jpeg", "png",
+    "gif",
+];
+
+/// Whether [`Lexicon::extract_words_from_path()`] would read a file with this path
+/// while walking a directory, going only by its name.
+///
+/// Hidden files (meaning they start with `.`) and the [`DEFAULT_IGNORED_EXTENSIONS`]
+/// are left out, and with `extensions`, so is everything without one of them.
+/// Extensions are compared ignoring ASCII case, with just the text after the last `.`.
+/// The file itself isn't looked at, so it could still turn out not to be text.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::is_probably_text_path;
+/// # use std::path::Path;
+/// assert!(is_probably_text_path(Path::new("notes/journal.md"), None));
+/// assert!(is_probably_text_path(Path::new("notes/README"), None));
+/// assert!(!is_probably_text_path(Path::new("notes/scan.PDF"), None));
+/// assert!(!is_probably_text_path(Path::new("notes/.hidden.md"), None));
+///
+/// let extensions = Some(&["md", "txt"][..]);
+/// assert!(is_probably_text_path(Path::new("journal.MD"), extensions));
+/// assert!(is_probably_text_path(Path::new("archive.tar.txt"), extensions));
+/// assert!(!is_probably_text_path(Path::new("README"), extensions));
+/// assert!(!is_probably_text_path(Path::new("main.rs"), extensions));
+/// ```
+pub fn is_probably_text_path(path: &Path, extensions: Option<&[&str]>) -> bool {
+    !is_hidden(path) && has_text_extension(path, extensions)
+}
+
+/// The part of [`is_probably_text_path()`] that goes by the extension.
+fn has_text_extension(path: &Path, extensions: Option<&[&str]>) -> bool {
+    match path.file_name().and_then(|name| name.to_str()) {
+        Some(name) => match name.rsplit_once('.') {
+            Some((_, ext)) => {
+                if DEFAULT_IGNORED_EXTENSIONS
+                    .iter()
+                    .any(|ignored_ext| ignored_ext.eq_ignore_ascii_case(ext))
+                {
+                    false
+                } else {
+                    match extensions {
+                        Some(extensions) => extensions
+                            .iter()
+                            .any(|allowed_ext| allowed_ext.eq_ignore_ascii_case(ext)),
+                        None => true,
+                    }
+                }
+            }
+            None => extensions.is_none(),
+        },
+        None => false,
+    }
+}
+
+/// Whether the file or directory is hidden, meaning its name starts with `.`.
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| name.starts_with('.'))
+        .unwrap_or_default()
+}
+
+/// Whether the entry is hidden, either by its name or, on Windows, its attributes.
+#[cfg(feature = "from_path")]
+fn is_hidden_entry(entry: &walkdir::DirEntry, by_attribute: bool) -> bool {
+    #[cfg(windows)]
+    if by_attribute {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        return entry
+            .metadata()
+            .map(|md| md.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+            .unwrap_or_default();
+    }
+    #[cfg(not(windows))]
+    let _ = by_attribute;
+
+    is_hidden(entry.path())
+}
+
+/// Split the text into words the way a [`Lexicon`] would, without needing one.
+///
+/// This is what [`Lexicon::extract_words()`] does with its [`Split`] and [`Deunicode`]
+/// settings, except that the words are returned instead of stored.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{split_words, CharFilter, Deunicode, Lexicon, Split};
+/// let text = "Ärger über den Straßen-Lärm, zweimal!";
+/// let words = split_words(
+///     text,
+///     &Split::UnicodeWords,
+///     &Deunicode::AfterFiltering,
+///     CharFilter::Unicode.closure(),
+/// );
+/// assert_eq!(words, ["Arger", "uber", "den", "Strassen", "Larm", "zweimal"]);
+///
+/// let mut lexicon = Lexicon::new("noise", Split::UnicodeWords);
+/// lexicon.deunicode = Deunicode::AfterFiltering;
+/// lexicon.extract_words(text, CharFilter::Unicode.closure());
+/// assert_eq!(lexicon.words(), words);
+/// ```
+pub fn split_words<F>(
+    text: &str,
+    split: &Split,
+    deunicode: &Deunicode,
+    mut filter: F,
+) -> Vec<String>
+where
+    F: FnMut(char) -> bool,
+{
+    let mut words = Vec::new();
+    push_split_words(split, *deunicode, text, &mut filter, &mut words);
+    words
+}
+
+/// Split the text into words the way a [`Lexicon`] is configured to, pushing them onto `words`.
+pub(crate) fn push_split_words<F>(
+    split: &Split,
+    deunicode_when: Deunicode,
+    text: &str,
+    filter: &mut F,
+    words: &mut Vec<String>,
+) where
+    F: FnMut(char) -> bool,
+{
+    if text.is_empty() {
+        return;
+    }
+
+    let deunicoded;
+    let text = if let Deunicode::BeforeSplitting = deunicode_when {
+        deunicoded = deunicode(text);
+        &deunicoded
+    } else {
+        text
+    };
+
+    let mut split_words: Vec<String> = match split {
+        Split::UnicodeWords => text.unicode_words().map(str::to_string).collect(),
+        Split::WordBounds => text.split_word_bounds().map(str::to_string).collect(),
+        Split::UnicodeWhitespace => text.split_whitespace().map(str::to_string).collect(),
+        Split::AsciiWhitespace => text.split_ascii_whitespace().map(str::to_string).collect(),
+        Split::Chars(chars) => text.split(&chars[..]).map(str::to_string).collect(),
+    };
+
+    for word in split_words.iter_mut() {
+        if word.is_empty() {
+            continue;
+        }
+
+        match deunicode_when {
+            Deunicode::BeforeFiltering => {
+                for part in deunicode(word).split_whitespace() {
+                    let mut part = part.to_string();
+                    part.retain(&mut *filter);
+                    if !part.is_empty() {
+                        words.push(part);
+                    }
+                }
+            }
+            Deunicode::AfterFiltering => {
+                word.retain(&mut *filter);
+                push_whitespace_free(&deunicode(word), words);
+            }
+            // The whole text was already deunicoded, so the split is left as it is.
+            Deunicode::BeforeSplitting | Deunicode::Deactivated => {
+                word.retain(&mut *filter);
+                if !word.is_empty() {
+                    words.push(take(word));
+                }
+            }
+        }
+    }
+}
+
+/// Push every whitespace-separated part of a deunicoded word,
+/// since transliterating can leave whitespace around and between the parts,
+/// like `北京` becoming `Bei Jing`.
+fn push_whitespace_free(word: &str, words: &mut Vec<String>) {
+    words.extend(word.split_whitespace().map(str::to_string));
+}
+
 /// The way to split the text into words.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Split {
     /// Splits the text into words based on on
     /// [UAX#29 word boundaries](http://www.unicode.org/reports/tr29/#Word_Boundaries).
@@ -309,7 +867,7 @@ pub enum Split {
     /// let text = "The ⚡quick⚡ (\"brown\") 🐒 can't❌jump 32.3 feet, right?";
     /// let expected = &["The", "quick", "brown", "can't", "jump", "32.3", "feet", "right"];
     ///
-    /// let mut lexicon = Lexicon::new(Split::UnicodeWords);
+    /// let mut lexicon = Lexicon::new("example", Split::UnicodeWords);
     /// lexicon.extract_words(text, |_| true);
     ///
     /// assert_eq!(lexicon.words(), expected);
@@ -323,7 +881,7 @@ pub enum Split {
     /// let text = "The ⚡quick⚡ (\"brown\") 🐒 can't❌jump 32.3 feet, right?";
     /// let expected = &["The", "zap", "quickzap", "brown", "monkey", "can'tx", "jump", "32.3", "feet", "right"];
     ///
-    /// let mut lexicon = Lexicon::new(Split::UnicodeWords);
+    /// let mut lexicon = Lexicon::new("example", Split::UnicodeWords);
     /// lexicon.deunicode = Deunicode::BeforeSplitting;
     /// lexicon.extract_words(text, |_| true);
     ///
@@ -346,7 +904,7 @@ pub enum Split {
     ///     "can't", "❌", "jump", " ", "too", " ", "high", ".",
     /// ];
     ///
-    /// let mut lexicon = Lexicon::new(Split::WordBounds);
+    /// let mut lexicon = Lexicon::new("example", Split::WordBounds);
     /// lexicon.extract_words(text, |_| true);
     ///
     /// assert_eq!(lexicon.words(), expected);
@@ -363,7 +921,7 @@ pub enum Split {
     /// let text = "The ⚡quick⚡ (\"brown\")    🐒 can't❌jump too high.";
     /// let expected = &["The", "⚡", "quick", "⚡", "brown", "🐒", "can't", "❌", "jump", "too", "high", "."];
     ///
-    /// let mut lexicon = Lexicon::new(Split::WordBounds);
+    /// let mut lexicon = Lexicon::new("example", Split::WordBounds);
     /// lexicon.extract_words(text, |c| c != '(' && c != ')' && c != '"' && !c.is_whitespace());
     ///
     /// assert_eq!(lexicon.words(), expected);
@@ -377,7 +935,7 @@ pub enum Split {
     /// let text = "The ⚡quick⚡ (\"brown\")    🐒 can't❌jump too high.";
     /// let expected = &["The", "zap", "quick", "zap", "brown", "monkey", "can't", "x", "jump", "too", "high", "."];
     ///
-    /// let mut lexicon = Lexicon::new(Split::WordBounds);
+    /// let mut lexicon = Lexicon::new("example", Split::WordBounds);
     /// lexicon.deunicode = Deunicode::BeforeFiltering;
     /// lexicon.extract_words(text, |c| c != '(' && c != ')' && c != '"' && !c.is_whitespace());
     ///
@@ -398,7 +956,7 @@ pub enum Split {
     /// let text = "The ⚡quick⚡  \u{2009}  (\"brown\")    🐒 can't❌jump 32.3\u{3000}feet, right?";
     /// let expected = &["The", "⚡quick⚡", "(\"brown\")", "🐒", "can't❌jump", "32.3", "feet,", "right?"];
     ///
-    /// let mut lexicon = Lexicon::new(Split::UnicodeWhitespace);
+    /// let mut lexicon = Lexicon::new("example", Split::UnicodeWhitespace);
     /// lexicon.extract_words(text, |_| true);
     ///
     /// assert_eq!(lexicon.words(), expected);
@@ -416,7 +974,7 @@ pub enum Split {
     /// let text = "The ⚡quick⚡  \u{2009}  (\"brown\")    🐒\tcan't❌jump\n\t32.3\u{3000}feet, right?";
     /// let expected = &["The", "⚡quick⚡", "\u{2009}", "(\"brown\")", "🐒", "can't❌jump", "32.3\u{3000}feet,", "right?"];
     ///
-    /// let mut lexicon = Lexicon::new(Split::AsciiWhitespace);
+    /// let mut lexicon = Lexicon::new("example", Split::AsciiWhitespace);
     /// lexicon.extract_words(text, |_| true);
     ///
     /// assert_eq!(lexicon.words(), expected);
@@ -432,7 +990,7 @@ pub enum Split {
     /// let text = "The ⚡quick⚡  \u{2009}  (\"brown\")    🐒\tcan't❌jump\n\t32.3\u{3000}feet, right?";
     /// let expected = &["The", "⚡quick⚡", "(\"brown\")", "🐒", "can't", "jump", "32.3", "feet,", "right?"];
     ///
-    /// let mut lexicon = Lexicon::new(Split::Chars(vec![' ', '\t', '\n', '\u{2009}', '\u{3000}', '❌']));
+    /// let mut lexicon = Lexicon::new("example", Split::Chars(vec![' ', '\t', '\n', '\u{2009}', '\u{3000}', '❌']));
     /// lexicon.extract_words(text, |_| true);
     ///
     /// assert_eq!(lexicon.words(), expected);
@@ -441,8 +999,28 @@ pub enum Split {
 }
 
 /// When the deunicoding happens.
-#[derive(Debug, Default)]
+///
+/// Deunicoding a word can leave it with whitespace, like `北京` becoming `Bei Jing`
+/// or a zero-width space becoming a space, so the words deunicoded one by one
+/// are split on it and the ones left empty are dropped.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{Deunicode, Lexicon, Split};
+/// let text = "北京 東京都 a\u{200b}b \u{feff} ①";
+///
+/// for deunicode in [Deunicode::BeforeFiltering, Deunicode::AfterFiltering] {
+///     let mut lexicon = Lexicon::new("example", Split::UnicodeWhitespace);
+///     lexicon.deunicode = deunicode;
+///     lexicon.extract_words(text, |_| true);
+///
+///     assert_eq!(lexicon.words(), ["Bei", "Jing", "Dong", "Jing", "Du", "a", "b"]);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Deunicode {
     /// No deunicoding takes place. The default when creating a [`Lexicon`].
     #[default]
@@ -458,8 +1036,32 @@ pub enum Deunicode {
     AfterFiltering,
 }
 
+/// What happens to the words extracted after reaching
+/// [`Lexicon::max_words`] or [`PasswordSettings::max_words`](crate::PasswordSettings#structfield.max_words).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CapStrategy {
+    /// Stop adding words, keeping the first ones extracted. The default.
+    #[default]
+    Stop,
+
+    /// Keep a uniform sample of every word extracted so far, with
+    /// [reservoir sampling](https://en.wikipedia.org/wiki/Reservoir_sampling):
+    /// each new word replaces a kept one at random, with a chance that shrinks
+    /// the more words have been seen.
+    ///
+    /// The sampling uses [`rand::thread_rng()`], so which words are kept changes from run
+    /// to run even with the same input. It also depends on the order the words come in,
+    /// so with a seeded generator the result would only repeat when the files are read
+    /// in the same order, as with sorted directory entries.
+    Reservoir,
+}
+
 /// Some reasonable character filtering options.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum CharFilter {
     /// Only characters in the ASCII range are allowed.
     ///
@@ -19,7 +19,7 @@ Written based on a Computerphile video:
 # Example
 
 ```no_run
-use genrepass::PasswordSettings;
+use genrepass::{PasswordSettings, WordCase};
 use std::{error::Error, process::exit};
 
 fn main() {
@@ -46,7 +46,7 @@ fn run() -> Result<(), Box<dyn Error>> {
 
     // Change the configuration by changing the fields.
     settings.pass_amount = 5;
-    settings.capitalise = true;
+    settings.word_case = WordCase::Capitalised;
     settings.length = 30..=50;
 
     // Generate the password/s.
@@ -56,6 +56,15 @@ fn run() -> Result<(), Box<dyn Error>> {
     // In this case we put each password on a separate line and print them.
     println!("{}", passwords.join("\n"));
 
+    // Or build the configuration in one go, which checks that it all fits together.
+    let settings = PasswordSettings::builder()
+        .words_from_path("/home/alex/Documents/notes")
+        .pass_amount(5)
+        .word_case(WordCase::Capitalised)
+        .length(30..=50)
+        .build()?;
+    println!("{}", settings.generate()?.join("\n"));
+
     Ok(())
 }
 ```
@@ -63,16 +72,78 @@ fn run() -> Result<(), Box<dyn Error>> {
 # Features
 
 - `serde` — Enables the serialisation and deserialisation of [`PasswordSettings`] and [`Lexicon`]
+- `json` — Enables [`PasswordSettings::from_untrusted_json()`] for validating settings from users
+- `schema` — Enables [`PasswordSettings::json_schema()`] for validating configuration files
 - `rayon` — Enables parallelisation with [`PasswordSettings::generate_parallel()`]
-- `from_path` — Enables [`Lexicon::extract_words_from_path()`]
+- `from_path` — Enables [`Lexicon::extract_words_from_path_with()`] and [`WalkOptions`]
+- `utf16` — Makes [`Lexicon::extract_words_from_path_with()`] read UTF-16 files with a BOM
+- `clipboard` — Enables the [`clipboard`] module for copying passwords
+- `derive` — Enables [`PasswordSettings::derive()`] for deriving the password of a site
+  from a master secret
+- `zeroize` — Enables [`PasswordSettings::generate_secure()`] for passwords that are
+  overwritten in memory when they're dropped
+- `profanity` — Enables [`WordValidator::Profanity`] for leaving out common profanity
+- `tracing` — Emits [`tracing`](https://docs.rs/tracing) events about the extraction of words
+  and the generation of each password, never including the password itself
 */
 
+mod arena;
+mod builder;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+mod compat;
+#[cfg(feature = "derive")]
+mod derive;
+mod draft;
+mod frozen;
 mod helpers;
+mod iter;
 mod lexicon;
+mod options;
 mod password;
+pub mod prelude;
+#[cfg(feature = "profanity")]
+mod profanity;
+mod report;
+#[cfg(feature = "schema")]
+mod schema;
 mod settings;
+mod sources;
+#[cfg(feature = "json")]
+mod untrusted;
+#[allow(deprecated)]
+pub use crate::compat::{PassConfig, ValidatedConfig};
+#[cfg(feature = "from_path")]
+pub use crate::options::WalkOptions;
+#[cfg(feature = "json")]
+pub use crate::untrusted::UntrustedSettingsError;
 pub use crate::{
+    builder::{ConfigError, PasswordSettingsBuilder},
+    draft::PasswordDraft,
+    frozen::FrozenSettings,
     helpers::{range_inc_from_str, ParseRangeError},
-    lexicon::{CharFilter, Deunicode, Lexicon, Split},
-    settings::{NonAsciiSpecialCharsError, NotEnoughWordsError, PasswordSettings},
+    iter::PasswordIter,
+    lexicon::{
+        is_probably_text_path, split_words, CapStrategy, CharFilter, Deunicode, ExtractionSession,
+        Lexicon, Split, DEFAULT_IGNORED_EXTENSIONS,
+    },
+    options::GenerateOptions,
+    password::PasswordBuilder,
+    report::{
+        composition, Composition, DryRunReport, GenerationReport, Nonconformity, PasswordDetails,
+        RolledParameters, SearchSpaceBreakdown, SkipReason, WalkReport, WordStats,
+    },
+    settings::{
+        extract_words, BatchRetryMode, CannotSatisfyUniquenessError, CaseRule, EmptyRangeError,
+        ExhaustedBehaviour, ExtractionBackend, GenerateError, GroupsExceedLengthError,
+        InsertPlacement, InsertZoneTooSmallError, InvalidProbabilityError, LengthMode,
+        LengthNotSatisfiedError, LengthUnit, NonAsciiSpecialCharsError,
+        NotEnoughDistinctInsertsError, NotEnoughWordsError, PasswordSettings, PolicyRejectedError,
+        RetryBudget, SettingsWarning, SpecialGroup, TimedOutError, WordCase, WordContext,
+        WordSelection, WordTransform, WordTransformFn, WordValidator, WordValidatorFn,
+        WordWeighting, WordWeightingFn,
+    },
+    sources::{Source, SourceId, SourceLoad, SourceMixing},
 };
+#[cfg(feature = "zeroize")]
+pub use zeroize::Zeroizing;
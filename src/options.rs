@@ -0,0 +1,104 @@
+use crate::settings::WordCase;
+use std::ops::RangeInclusive;
+
+/// Overrides for a single call to
+/// [`PasswordSettings::generate_with()`](crate::PasswordSettings::generate_with).
+///
+/// Every field left as `None` falls back to the value in the settings.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct GenerateOptions {
+    /// Overrides [`pass_amount`](crate::PasswordSettings#structfield.pass_amount).
+    pub pass_amount: Option<usize>,
+
+    /// Overrides [`length`](crate::PasswordSettings#structfield.length).
+    pub length: Option<RangeInclusive<usize>>,
+
+    /// Overrides [`number_amount`](crate::PasswordSettings#structfield.number_amount).
+    pub number_amount: Option<RangeInclusive<usize>>,
+
+    /// Overrides [`special_chars_amount`](crate::PasswordSettings#structfield.special_chars_amount).
+    pub special_chars_amount: Option<RangeInclusive<usize>>,
+
+    /// Overrides [`word_case`](crate::PasswordSettings#structfield.word_case).
+    pub word_case: Option<WordCase>,
+
+    /// Overrides [`word_case`](crate::PasswordSettings#structfield.word_case)
+    /// with [`WordCase::Capitalised`] or [`WordCase::AsIs`],
+    /// unless [`word_case`](GenerateOptions::word_case) is set.
+    #[deprecated(note = "use `word_case` instead")]
+    pub capitalise: Option<bool>,
+}
+
+impl GenerateOptions {
+    /// Create options that don't override anything.
+    pub fn new() -> Self {
+        GenerateOptions::default()
+    }
+}
+
+/// How [`Lexicon::extract_words_from_path_with()`](crate::Lexicon::extract_words_from_path_with)
+/// walks the paths it's given.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::WalkOptions;
+/// let options = WalkOptions::new()
+///     .depth(3)
+///     .extensions(["md", "txt"])
+///     .follow_links(true);
+/// ```
+#[cfg(feature = "from_path")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WalkOptions {
+    pub(crate) depth: usize,
+    pub(crate) extensions: Option<Vec<String>>,
+    pub(crate) follow_links: bool,
+}
+
+#[cfg(feature = "from_path")]
+impl Default for WalkOptions {
+    fn default() -> Self {
+        WalkOptions {
+            depth: usize::MAX,
+            extensions: None,
+            follow_links: false,
+        }
+    }
+}
+
+#[cfg(feature = "from_path")]
+impl WalkOptions {
+    /// Create options that walk every directory all the way down without following links,
+    /// reading any file that doesn't have one of the [`DEFAULT_IGNORED_EXTENSIONS`](crate::DEFAULT_IGNORED_EXTENSIONS).
+    pub fn new() -> Self {
+        WalkOptions::default()
+    }
+
+    /// How deep to go into the directories, with 0 being the paths themselves,
+    /// so a directory is only read with at least 1.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Only read the files with one of these extensions while walking a directory.
+    pub fn extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extensions = Some(extensions.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Follow symbolic links, both the ones passed and the ones found while walking.
+    pub fn follow_links(mut self, follow_links: bool) -> Self {
+        self.follow_links = follow_links;
+        self
+    }
+}
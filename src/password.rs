@@ -1,268 +1,1516 @@
 use crate::{
-    helpers::{capitalise, decapitalise},
-    settings::PasswordSettings,
+    arena::WordArena,
+    helpers::{
+        capitalise, count_lower, count_upper, decapitalise, reserve, truncate_units, unit_indices,
+        unit_len, wipe,
+    },
+    options::GenerateOptions,
+    report::{composition, PasswordDetails, RolledParameters},
+    settings::{
+        CaseRule, ExhaustedBehaviour, GenerateError, InsertPlacement, LengthMode,
+        LengthNotSatisfiedError, LengthUnit, PasswordSettings, PolicyRejectedError, RetryBudget,
+        TimedOutError, WordCase, WordSelection, WordTransform, WordWeighting,
+    },
+    sources::SourceId,
 };
-use rand::{distributions::Uniform, seq::SliceRandom, thread_rng, Rng};
-use std::mem::take;
+use rand::{
+    distributions::{Distribution, Uniform, WeightedIndex},
+    seq::{index, IteratorRandom, SliceRandom},
+    Rng,
+};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    ops::{Range, RangeInclusive},
+    time::Instant,
+};
+
+/// The most bytes a single character can turn into when its case changes.
+const MAX_CASE_BYTES: usize = 12;
+
+/// Decides which words are selected, shared by all the passwords of a batch.
+#[derive(Clone)]
+pub(crate) struct WordPicker {
+    selection: WordSelection,
+    /// The distribution of the sources along with the positions of their words,
+    /// in case the sources have different weights.
+    weighted: Option<(WeightedIndex<u32>, Vec<Vec<usize>>)>,
+}
+
+impl WordPicker {
+    pub(crate) fn new(config: &PasswordSettings, word_sources: &[SourceId]) -> Self {
+        let mut weighted = None;
+
+        if config.sources.iter().any(|s| s.weight != 1) {
+            let mut positions = vec![Vec::new(); config.sources.len()];
+            for (i, source) in word_sources.iter().enumerate() {
+                positions[source.0].push(i);
+            }
+
+            let weights = config
+                .sources
+                .iter()
+                .zip(&positions)
+                .map(|(source, positions)| {
+                    if positions.is_empty() {
+                        0
+                    } else {
+                        source.weight
+                    }
+                });
+
+            if let Ok(dist) = WeightedIndex::new(weights) {
+                weighted = Some((dist, positions));
+            }
+        }
+
+        Self {
+            selection: config.word_selection,
+            weighted,
+        }
+    }
+
+    /// A picker that ignores the sources of the words.
+    pub(crate) fn unweighted(config: &PasswordSettings) -> Self {
+        Self {
+            selection: config.word_selection,
+            weighted: None,
+        }
+    }
+
+    /// Pick a random word, taking the weights of the sources into account.
+    fn pick<R: Rng + ?Sized>(&self, len: usize, rng: &mut R) -> usize {
+        match &self.weighted {
+            Some((dist, positions)) => {
+                let positions = &positions[dist.sample(rng)];
+                positions[rng.gen_range(0..positions.len())]
+            }
+            None => rng.gen_range(0..len),
+        }
+    }
+
+    /// The word that comes after the one at `index`.
+    fn next<R: Rng + ?Sized>(&self, index: usize, len: usize, rng: &mut R) -> usize {
+        match self.selection {
+            WordSelection::Consecutive => (index + 1) % len,
+            WordSelection::Uniform => self.pick(len, rng),
+        }
+    }
+}
+
+/// The words to select from, either stored or a view of them.
+#[derive(Clone, Copy)]
+pub(crate) enum Words<'a> {
+    Strings(&'a [String]),
+    Strs(&'a [&'a str]),
+    Arena(&'a WordArena),
+}
+
+impl<'a> Words<'a> {
+    fn len(&self) -> usize {
+        match self {
+            Words::Strings(words) => words.len(),
+            Words::Strs(words) => words.len(),
+            Words::Arena(arena) => arena.len(),
+        }
+    }
 
-pub(crate) struct Password {
+    fn get(&self, index: usize) -> &'a str {
+        match self {
+            Words::Strings(words) => &words[index],
+            Words::Strs(words) => words[index],
+            Words::Arena(arena) => arena.get(index),
+        }
+    }
+}
+
+/// The password as it was after one of the stages of a [`PasswordBuilder`].
+#[derive(Clone, Debug)]
+pub(crate) struct Stage {
+    pub(crate) password: String,
+    spans: Vec<Range<usize>>,
+    initials: Vec<usize>,
+    boundaries: Vec<usize>,
+    truncated: bool,
+    reset_count: usize,
+    dropped_inserts: usize,
+    start_index: usize,
+    wrapped: bool,
+}
+
+/// The low-level password generation pipeline, split into stages.
+///
+/// This is what [`PasswordSettings::generate()`] uses for each password,
+/// so you can hook into it to run only some of the stages,
+/// like getting the assembled words before anything is inserted,
+/// or inserting characters into your own phrase.
+///
+/// The amounts of characters to insert and to case are picked
+/// from the settings when creating the builder.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{PasswordBuilder, PasswordSettings};
+/// let settings = PasswordSettings::new();
+/// let mut rng = rand::thread_rng();
+///
+/// let mut builder = PasswordBuilder::new(&settings, &mut rng).phrase("CorrectHorseBatteryStaple");
+/// builder.insert(&mut rng);
+/// let password = builder.finish();
+///
+/// let letters: String = password.chars().filter(char::is_ascii_alphabetic).collect();
+/// assert_eq!(letters, "CorrectHorseBatteryStaple");
+/// assert!((27..=29).contains(&password.len()));
+/// ```
+#[derive(Clone)]
+pub struct PasswordBuilder<'a> {
+    words: Words<'a>,
+    picker: Cow<'a, WordPicker>,
+    truncated: bool,
     password: String,
+    /// The byte ranges of the words that are still intact in the password.
+    spans: Vec<Range<usize>>,
+    /// The byte positions of the first characters of the words that are still in the password.
+    initials: Vec<usize>,
+    /// The byte positions where a word starts or ends that nothing was inserted at yet,
+    /// with [`insert_at_boundaries`](PasswordSettings#structfield.insert_at_boundaries).
+    boundaries: Vec<usize>,
     reset_amount: usize,
     reset_count: usize,
+    on_exhausted: ExhaustedBehaviour,
     min_len: usize,
     max_len: usize,
-    total_inserts: usize,
-    capitalise: bool,
+    unit: LengthUnit,
+    must_start_with_letter: bool,
+    skip_oversized_words: bool,
+    continue_probability: f64,
+    transform: Option<WordTransform>,
+    weighting: WordWeighting,
+    max_number_words: Option<usize>,
+    word_case: WordCase,
+    /// The capitalise exceptions, with their lowercase forms to match the words against.
+    capitalise_exceptions: Vec<(String, String)>,
+    separator: String,
     replace: bool,
+    leet: bool,
+    leet_substitutions: BTreeMap<char, String>,
+    insert_at_boundaries: bool,
+    placement: InsertPlacement,
     upper: usize,
     lower: usize,
-    force_upper: bool,
-    force_lower: bool,
-    dont_upper: bool,
-    dont_lower: bool,
-    insertables: Vec<char>,
+    upper_rule: CaseRule,
+    lower_rule: CaseRule,
+    /// The amounts of uppercase and lowercase letters the case was adjusted for.
+    upper_target: usize,
+    lower_target: usize,
+    strict_case: bool,
+    strict_inserts: bool,
+    no_adjacent_inserts: bool,
+    insert_zone: Option<RangeInclusive<usize>>,
+    /// The amount of insertables left out for lack of room.
+    dropped_inserts: usize,
+    protect_word_initials: bool,
+    insertables: Vec<String>,
+    /// Every character that counts as special, for the composition.
+    special_chars: String,
+    rolled: RolledParameters,
+    no_wrap: bool,
+    start_index: usize,
+    wrapped: bool,
+    exclusions: Vec<String>,
+    policy_retries: usize,
+    deadline: Option<Instant>,
+    timed_out: bool,
 }
 
-impl Password {
-    pub(crate) fn generate(&mut self, config: &PasswordSettings) -> String {
-        self.get_pass_string(config);
+impl<'a> PasswordBuilder<'a> {
+    /// Start building a password with the given settings, but without any words.
+    pub fn new<R: Rng + ?Sized>(settings: &PasswordSettings, rng: &mut R) -> Self {
+        Self::with_options(settings, &GenerateOptions::default(), rng)
+    }
+
+    /// Set the words to [assemble](PasswordBuilder::assemble) the password from.
+    pub fn words(mut self, words: &'a [String]) -> Self {
+        self.words = Words::Strings(words);
+        self
+    }
 
-        if self.replace {
-            self.replace_chars();
+    /// Start from your own phrase instead of assembling it from words.
+    ///
+    /// The whole phrase counts as a single word.
+    pub fn phrase(mut self, phrase: impl Into<String>) -> Self {
+        self.password = phrase.into();
+        self.spans.clear();
+        self.initials.clear();
+        self.boundaries.clear();
+        if !self.password.is_empty() {
+            self.spans.push(0..self.password.len());
+            self.initials.push(0);
+            self.boundaries.extend([0, self.password.len()]);
+        }
+        self
+    }
+
+    pub(crate) fn with_words(mut self, words: Words<'a>, picker: &'a WordPicker) -> Self {
+        self.words = words;
+        self.picker = Cow::Borrowed(picker);
+        self
+    }
+
+    /// Override the assembly retries with what's left of a shared pool, if any.
+    pub(crate) fn with_pool(mut self, pool: Option<usize>) -> Self {
+        if let Some(pool) = pool {
+            self.reset_amount = pool;
+        }
+        self
+    }
+
+    /// Give up on assembling the password once `deadline` has passed.
+    pub(crate) fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Select the words and join them into a password of the configured length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no words.
+    pub fn assemble<R: Rng + ?Sized>(&mut self, rng: &mut R) -> &mut Self {
+        self.password.clear();
+        self.spans.clear();
+        self.initials.clear();
+        self.boundaries.clear();
+        self.truncated = match self.rolled.words {
+            Some(amount) => {
+                self.get_words_string(amount, rng);
+                false
+            }
+            None => self.get_pass_string(rng),
+        };
+        self
+    }
+
+    /// Insert the numbers and special characters, or replace characters with them
+    /// if [`replace`](PasswordSettings#structfield.replace) is set, or substitute
+    /// as many letters instead if [`leet`](PasswordSettings#structfield.leet) is set.
+    pub fn insert<R: Rng + ?Sized>(&mut self, rng: &mut R) -> &mut Self {
+        if self.leet {
+            self.substitute_letters(rng);
+        } else if self.placement != InsertPlacement::Random {
+            self.insert_grouped();
+        } else if self.replace && self.insert_at_boundaries {
+            self.replace_at_boundaries(rng);
+        } else if self.replace {
+            self.replace_chars(rng);
         } else {
-            self.insert_chars();
+            self.insert_chars(rng);
         }
+        self
+    }
 
-        self.ensure_case();
+    /// Adjust the amount of uppercase and lowercase characters.
+    ///
+    /// When and how many letters are changed is up to the [`CaseRule`]s of
+    /// [`upper_handling`](PasswordSettings#structfield.upper_handling) and
+    /// [`lower_handling`](PasswordSettings#structfield.lower_handling).
+    /// Any letter with a case is taken into account, not only ASCII.
+    /// Some letters change length when their case changes, like `ß` becoming `SS`,
+    /// which makes the password longer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CaseRule, PasswordBuilder, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.upper_amount = 1..=1;
+    /// settings.lower_handling = CaseRule::Never;
+    /// let mut rng = rand::thread_rng();
+    ///
+    /// let mut builder = PasswordBuilder::new(&settings, &mut rng).phrase("ß");
+    /// builder.ensure_case(&mut rng);
+    /// assert_eq!(builder.finish(), "SS");
+    ///
+    /// settings.upper_amount = 2..=2;
+    /// let mut builder = PasswordBuilder::new(&settings, &mut rng).phrase("привет");
+    /// builder.ensure_case(&mut rng);
+    /// let password = builder.finish();
+    /// assert_eq!(password.chars().filter(|c| c.is_uppercase()).count(), 2);
+    /// assert_eq!(password.to_lowercase(), "привет");
+    ///
+    /// settings.lower_handling = CaseRule::Auto;
+    /// settings.upper_handling = CaseRule::Never;
+    /// settings.lower_amount = 3..=3;
+    /// let mut builder = PasswordBuilder::new(&settings, &mut rng).phrase("МИРМИР");
+    /// builder.ensure_case(&mut rng);
+    /// let password = builder.finish();
+    /// assert_eq!(password.chars().filter(|c| c.is_lowercase()).count(), 3);
+    /// assert_eq!(password.to_uppercase(), "МИРМИР");
+    /// ```
+    pub fn ensure_case<R: Rng + ?Sized>(&mut self, rng: &mut R) -> &mut Self {
+        self.adjust_case(rng);
+        self
+    }
 
-        take(&mut self.password)
+    /// Get the password as it currently is.
+    pub fn finish(self) -> String {
+        self.password
     }
 
-    pub(crate) fn new(config: &PasswordSettings) -> Self {
-        let mut rng = thread_rng();
+    /// Keep the password as it is after a stage, to continue from it later.
+    pub(crate) fn stage(&self) -> Stage {
+        Stage {
+            password: self.password.clone(),
+            spans: self.spans.clone(),
+            initials: self.initials.clone(),
+            boundaries: self.boundaries.clone(),
+            truncated: self.truncated,
+            reset_count: self.reset_count,
+            dropped_inserts: self.dropped_inserts,
+            start_index: self.start_index,
+            wrapped: self.wrapped,
+        }
+    }
 
-        let mut min_len = *config.length.start();
-        let mut max_len = *config.length.end();
-        if max_len - min_len > 50 {
-            min_len = rng.gen_range(min_len..=max_len - 50);
-            max_len = min_len + 50;
+    /// Continue from a password kept with [`stage()`](PasswordBuilder::stage).
+    pub(crate) fn resume(mut self, stage: &Stage) -> Self {
+        wipe(std::mem::take(&mut self.password));
+        self.password = stage.password.clone();
+        self.spans = stage.spans.clone();
+        self.initials = stage.initials.clone();
+        self.boundaries = stage.boundaries.clone();
+        self.truncated = stage.truncated;
+        self.reset_count = stage.reset_count;
+        self.dropped_inserts = stage.dropped_inserts;
+        self.start_index = stage.start_index;
+        self.wrapped = stage.wrapped;
+        self
+    }
+
+    pub(crate) fn finish_details(self) -> PasswordDetails {
+        let (achieved_upper, achieved_lower) =
+            (count_upper(&self.password), count_lower(&self.password));
+        let composition = composition(&self.password, &self.special_chars);
+        PasswordDetails {
+            password: self.password,
+            retries: RetryBudget {
+                assembly: self.reset_count,
+                policy: 0,
+                uniqueness: 0,
+            },
+            truncated: self.truncated,
+            word_spans: self.spans,
+            start_index: self.start_index,
+            wrapped: self.wrapped,
+            rolled: self.rolled,
+            requested_upper: self.upper_target,
+            achieved_upper,
+            requested_lower: self.lower_target,
+            achieved_lower,
+            dropped_inserts: self.dropped_inserts,
+            composition,
+        }
+    }
+
+    /// Run every stage, unless the deadline passes while assembling.
+    fn generate<R: Rng + ?Sized>(mut self, rng: &mut R) -> Result<PasswordDetails, TimedOutError> {
+        self.assemble(rng);
+        if self.timed_out {
+            wipe(std::mem::take(&mut self.password));
+            return Err(TimedOutError {
+                finished: 0,
+                resets: self.reset_count,
+            });
         }
+        self.insert(rng).ensure_case(rng);
+        // The password itself is left out on purpose.
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            resets = self.reset_count,
+            truncated = self.truncated,
+            "generated a password"
+        );
+        Ok(self.finish_details())
+    }
 
-        let num = rng.gen_range(config.number_amount.clone());
-        let special = rng.gen_range(config.special_chars_amount.clone());
-        let upper = rng.gen_range(config.upper_amount.clone());
-        let lower = rng.gen_range(config.lower_amount.clone());
+    /// Run every stage like [`generate_rejecting()`](PasswordBuilder::generate_rejecting),
+    /// erroring instead of returning a truncated password with [`ExhaustedBehaviour::Error`].
+    pub(crate) fn generate_checked<R: Rng + ?Sized>(
+        self,
+        rng: &mut R,
+    ) -> Result<PasswordDetails, GenerateError> {
+        let on_exhausted = self.on_exhausted;
+        let details = self.generate_rejecting(rng)?;
+        if details.truncated && on_exhausted == ExhaustedBehaviour::Error {
+            wipe(details.password);
+            return Err(LengthNotSatisfiedError {
+                length: details.rolled.length,
+                resets: details.retries.assembly,
+            }
+            .into());
+        }
+        Ok(details)
+    }
 
-        let mut total_inserts = num + special;
-        if total_inserts > max_len {
-            total_inserts = max_len;
+    /// Run every stage, starting over while the password contains
+    /// one of the context exclusions, is short of cased letters with `strict_case`,
+    /// or of inserted characters with `strict_inserts`.
+    fn generate_rejecting<R: Rng + ?Sized>(
+        mut self,
+        rng: &mut R,
+    ) -> Result<PasswordDetails, GenerateError> {
+        if self.exclusions.is_empty() && !self.strict_case && !self.strict_inserts {
+            return Ok(self.generate(rng)?);
         }
 
-        if !config.replace {
-            if min_len < total_inserts {
-                total_inserts = min_len;
+        let mut retries = 0;
+        let result = loop {
+            let mut details = match self.clone().generate(rng) {
+                Ok(details) => details,
+                Err(e) => break Err(e.into()),
+            };
+            if !self.is_rejected(&details) {
+                details.retries.policy = retries;
+                break Ok(details);
+            }
+            wipe(details.password);
+            if retries == self.policy_retries {
+                break Err(PolicyRejectedError { retries }.into());
             }
+            retries += 1;
+        };
+        self.insertables.drain(..).for_each(wipe);
+        result
+    }
+
+    /// Whether the password has to be generated again.
+    fn is_rejected(&self, details: &PasswordDetails) -> bool {
+        self.is_excluded(details)
+            || self.strict_case && is_short_of_case(details)
+            || self.strict_inserts && details.dropped_inserts > 0
+    }
+
+    /// Whether the password contains one of the context exclusions,
+    /// either as it is or without the inserted characters.
+    fn is_excluded(&self, details: &PasswordDetails) -> bool {
+        let password = details.password.to_lowercase();
+        let words: String = details
+            .word_spans
+            .iter()
+            .map(|span| &details.password[span.clone()])
+            .collect::<String>()
+            .to_lowercase();
+        self.exclusions
+            .iter()
+            .any(|exclusion| password.contains(exclusion) || words.contains(exclusion))
+    }
 
-            min_len -= total_inserts;
-            max_len -= total_inserts;
+    pub(crate) fn with_options<R: Rng + ?Sized>(
+        config: &PasswordSettings,
+        options: &GenerateOptions,
+        rng: &mut R,
+    ) -> Self {
+        let rolled = Self::roll(config, options, rng);
+        Self::with_rolled(config, options, rolled, rng)
+    }
+
+    /// Roll the length window and the amounts of characters for a single password.
+    pub(crate) fn roll<R: Rng + ?Sized>(
+        config: &PasswordSettings,
+        options: &GenerateOptions,
+        rng: &mut R,
+    ) -> RolledParameters {
+        let length = options.length.as_ref().unwrap_or(&config.length);
+        let number_amount = options
+            .number_amount
+            .as_ref()
+            .unwrap_or(&config.number_amount);
+        let special_chars_amount = options
+            .special_chars_amount
+            .as_ref()
+            .unwrap_or(&config.special_chars_amount);
+
+        let mut min_len = *length.start();
+        let mut max_len = *length.end();
+        if max_len - min_len > 50 {
+            min_len = rng.gen_range(min_len..=max_len - 50);
+            max_len = min_len + 50;
+        }
+
+        RolledParameters {
+            length: min_len..=max_len,
+            numbers: rng.gen_range(number_amount.clone()),
+            special_chars: rng.gen_range(special_chars_amount.clone()),
+            special_groups: config
+                .special_groups
+                .iter()
+                .map(|group| rng.gen_range(group.amount.clone()))
+                .collect(),
+            upper: rng.gen_range(config.upper_amount.clone()),
+            lower: rng.gen_range(config.lower_amount.clone()),
+            words: match &config.length_mode {
+                LengthMode::Units => None,
+                LengthMode::Words(range) => Some(rng.gen_range(range.clone())),
+            },
         }
+    }
+
+    pub(crate) fn with_rolled<R: Rng + ?Sized>(
+        config: &PasswordSettings,
+        options: &GenerateOptions,
+        rolled: RolledParameters,
+        rng: &mut R,
+    ) -> Self {
+        let (mut min_len, mut max_len) = (*rolled.length.start(), *rolled.length.end());
+        let (num, special) = (rolled.numbers, rolled.special_chars);
+        let (upper, lower) = (rolled.upper, rolled.lower);
+        let word_case = config.word_case_with(options);
 
-        let insertables = {
-            let mut chars = Vec::with_capacity(total_inserts);
+        let mut insertables = {
+            let mut strings = Vec::with_capacity(num + special);
             let num_range = Uniform::new(0, 10);
-            let char_range = Uniform::new(0, config.special_chars.len());
+            let char_count = config.special_chars.chars().count();
+            let special_count = char_count + config.special_sequences.len();
+
+            if config.distinct_inserts {
+                let digits = index::sample(rng, 10, num.min(10));
+                strings.extend(digits.iter().map(|d| d.to_string()));
+
+                let specials = config.distinct_specials();
+                let picked = index::sample(rng, specials.len(), special.min(specials.len()));
+                strings.extend(picked.iter().map(|i| specials[i].clone()));
+            } else {
+                for _ in 0..num {
+                    strings.push(rng.sample(num_range).to_string());
+                }
 
-            for _ in 0..num {
-                let num = rng.sample(&num_range).to_string().chars().next().unwrap();
-                chars.push(num);
+                if special_count > 0 {
+                    let special_range = Uniform::new(0, special_count);
+                    for _ in 0..special {
+                        let index = rng.sample(special_range);
+                        let special = match config.special_chars.chars().nth(index) {
+                            Some(c) => c.to_string(),
+                            None => config.special_sequences[index - char_count].clone(),
+                        };
+                        strings.push(special);
+                    }
+                }
             }
 
-            for _ in 0..special {
-                let index = rng.sample(&char_range);
-                let c = config.special_chars.chars().nth(index);
-                if let Some(c) = c {
-                    chars.push(c)
+            // Only dropped if they don't fit, which generating checks beforehand.
+            for (group, &amount) in config.special_groups.iter().zip(&rolled.special_groups) {
+                let chars = group.distinct_chars();
+                if chars.is_empty() {
+                    continue;
+                } else if config.distinct_inserts {
+                    let picked = index::sample(rng, chars.len(), amount.min(chars.len()));
+                    strings.extend(picked.iter().map(|i| chars[i].to_string()));
+                } else {
+                    let chars: Vec<char> = group.chars.chars().collect();
+                    for _ in 0..amount {
+                        strings.push(chars[rng.gen_range(0..chars.len())].to_string());
+                    }
                 }
             }
 
-            chars.shuffle(&mut rng);
-            chars
+            strings.shuffle(rng);
+            strings
+        };
+
+        // Sequences take up more than one character, so what matters is their total length.
+        let inserted_len = |insertables: &[String]| -> usize {
+            insertables
+                .iter()
+                .map(|s| unit_len(s, config.length_unit))
+                .sum()
         };
+        let limit = if rolled.words.is_some() {
+            usize::MAX
+        } else if config.replace || config.leet {
+            max_len
+        } else {
+            min_len
+        };
+        let requested = insertables.len();
+        while inserted_len(&insertables) > limit {
+            if let Some(insertable) = insertables.pop() {
+                wipe(insertable);
+            }
+        }
+        let dropped_inserts = requested - insertables.len();
+        #[cfg(feature = "tracing")]
+        if insertables.len() < requested {
+            tracing::debug!(
+                requested,
+                kept = insertables.len(),
+                limit,
+                "too many numbers and special characters for the length"
+            );
+        }
+
+        if !config.replace && !config.leet && rolled.words.is_none() {
+            let total = inserted_len(&insertables);
+            min_len -= total;
+            max_len -= total;
+        }
 
-        Password {
+        PasswordBuilder {
+            words: Words::Strings(&[]),
+            picker: Cow::Owned(WordPicker::unweighted(config)),
+            truncated: false,
             password: String::with_capacity(max_len),
-            reset_amount: config.reset_amount,
+            spans: Vec::new(),
+            initials: Vec::new(),
+            boundaries: Vec::new(),
+            reset_amount: config.retry_budget.assembly,
             reset_count: 0,
+            on_exhausted: config.on_exhausted,
             min_len,
             max_len,
-            total_inserts,
-            capitalise: config.capitalise,
+            unit: config.length_unit,
+            must_start_with_letter: config.must_start_with_letter,
+            skip_oversized_words: config.skip_oversized_words,
+            continue_probability: config.continue_probability,
+            transform: config.word_transform.clone(),
+            weighting: config.word_weighting.clone(),
+            max_number_words: config.max_number_words,
+            word_case,
+            capitalise_exceptions: config
+                .capitalise_exceptions
+                .iter()
+                .map(|exception| (exception.to_lowercase(), exception.clone()))
+                .collect(),
+            separator: config.separator.clone(),
             replace: config.replace,
+            leet: config.leet,
+            leet_substitutions: config.leet_substitutions.clone(),
+            insert_at_boundaries: config.insert_at_boundaries,
+            placement: config.insert_placement,
             upper,
             lower,
-            force_upper: config.force_upper,
-            force_lower: config.force_lower,
-            dont_upper: config.dont_upper,
-            dont_lower: config.dont_lower,
+            upper_rule: match word_case {
+                WordCase::Lower => CaseRule::Never,
+                _ => config.upper_handling,
+            },
+            lower_rule: match word_case {
+                WordCase::Upper => CaseRule::Never,
+                _ => config.lower_handling,
+            },
+            upper_target: 0,
+            lower_target: 0,
+            strict_case: config.strict_case,
+            strict_inserts: config.strict_inserts,
+            no_adjacent_inserts: config.no_adjacent_inserts,
+            insert_zone: config.insert_zone.clone(),
+            dropped_inserts,
+            protect_word_initials: config.protect_word_initials
+                || matches!(word_case, WordCase::Capitalised | WordCase::Camel),
             insertables,
+            special_chars: config.all_special_chars(),
+            rolled,
+            no_wrap: config.no_wrap,
+            start_index: 0,
+            wrapped: false,
+            exclusions: config.context_exclusions.clone(),
+            policy_retries: config.retry_budget.policy,
+            deadline: None,
+            timed_out: false,
         }
     }
 
-    fn get_pass_string(&mut self, config: &PasswordSettings) {
-        let mut rng = thread_rng();
-        let start_index = rng.gen_range(0..config.words.len());
-
-        let text = &config.words;
-        let mut words = text.iter().skip(start_index).peekable();
+    /// Returns whether the password had to be truncated.
+    fn get_pass_string<R: Rng + ?Sized>(&mut self, rng: &mut R) -> bool {
+        let text = self.words;
+        let picker = self.picker.clone();
+        let (skip, max_len, unit) = (self.skip_oversized_words, self.max_len, self.unit);
+        // Words that can never fit are passed over, unless all of them are like that.
+        let fitting = |mut index: usize, rng: &mut R| {
+            if skip {
+                for _ in 0..text.len() {
+                    if unit_len(text.get(index), unit) <= max_len {
+                        break;
+                    }
+                    index = picker.next(index, text.len(), rng);
+                }
+            }
+            index
+        };
+        let weighting = self.weighting.clone();
+        let max_number_words = self.max_number_words;
+        let numbers_full = |count: usize| max_number_words.is_some_and(|max| count >= max);
+        // Words that aren't kept are passed over the same way, but only for as long
+        // as there are others left to try.
+        let kept = |mut index: usize, numbers_full: bool, rng: &mut R| {
+            for _ in 0..text.len() {
+                if numbers_full && is_number_word(text.get(index)) {
+                    index = fitting(picker.next(index, text.len(), rng), rng);
+                    continue;
+                }
+                match weighting.weight(text.get(index)) {
+                    Some(weight) if !rng.gen_bool(weight) => {
+                        index = fitting(picker.next(index, text.len(), rng), rng);
+                    }
+                    _ => break,
+                }
+            }
+            index
+        };
+        let transform = self.transform.clone();
+        let transformed = |word: &'a str, position: usize| match &transform {
+            Some(transform) => Cow::Owned(transform.apply(word, position)),
+            None => Cow::Borrowed(word),
+        };
+        let mut index = kept(
+            fitting(picker.pick(text.len(), rng), rng),
+            numbers_full(0),
+            rng,
+        );
+        let mut skipped = 0;
+        // The words made only of numbers in the password.
+        let mut number_words = 0;
+        // The position of the next word in the password, along with the word
+        // when it's already been transformed to check whether it fits.
+        let mut position = 0;
+        let mut next = None;
+        let mut next_wraps = false;
 
         loop {
-            if let Some(w) = words.next() {
-                if self.capitalise {
-                    let w = w[0..1].to_ascii_uppercase() + &w[1..];
-                    self.password.push_str(w.as_str());
-                } else {
-                    self.password.push_str(w.as_str());
+            if self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                self.timed_out = true;
+                return true;
+            }
+
+            let w = next
+                .take()
+                .unwrap_or_else(|| transformed(text.get(index), position));
+
+            if self.must_start_with_letter
+                && self.password.is_empty()
+                && !w.starts_with(char::is_alphabetic)
+            {
+                if skipped < text.len() {
+                    skipped += 1;
+                    index = fitting(picker.next(index, text.len(), rng), rng);
+                    continue;
+                } else if self.reset_count < self.reset_amount {
+                    // Went through every word without finding one that starts with a letter.
+                    self.reset_count += 1;
+                    skipped = 0;
+                    index = fitting(picker.next(index, text.len(), rng), rng);
+                    continue;
+                }
+            }
+            skipped = 0;
+
+            if position == 0 {
+                self.start_index = index;
+                self.wrapped = false;
+            } else if next_wraps {
+                self.wrapped = true;
+            }
+
+            self.push_word(&w, position);
+            if is_number_word(&w) {
+                number_words += 1;
+            }
+            position += 1;
+
+            let previous = index;
+            index = kept(
+                fitting(picker.next(index, text.len(), rng), rng),
+                numbers_full(number_words),
+                rng,
+            );
+            next_wraps = picker.selection == WordSelection::Consecutive && index <= previous;
+            let len = self.len();
+
+            if next_wraps && self.no_wrap {
+                if len >= self.min_len && len <= self.max_len {
+                    return false;
+                } else if self.reset_count < self.reset_amount {
+                    self.reset_count += 1;
+                    self.password.clear();
+                    self.spans.clear();
+                    self.initials.clear();
+                    self.boundaries.clear();
+                    index = kept(
+                        fitting(picker.pick(text.len(), rng), rng),
+                        numbers_full(0),
+                        rng,
+                    );
+                    position = 0;
+                    number_words = 0;
+                    next = None;
+                    continue;
                 }
+            }
 
-                match words.peek() {
-                    Some(p) => {
-                        let mut allowance = 0;
-                        if self.password.len() < self.max_len {
-                            allowance = self.max_len - self.password.len();
-                        }
-
-                        if p.len() > allowance {
-                            if self.password.len() >= self.min_len
-                                && self.password.len() <= self.max_len
-                            {
-                                break;
-                            } else if self.reset_count >= self.reset_amount {
-                                self.password.truncate(self.max_len);
-                                break;
-                            } else {
-                                self.reset_count += 1;
-                                self.password.clear();
-                                continue;
-                            }
-                        } else if self.password.len() < self.min_len
-                            || p.len() <= allowance && rng.gen_bool(0.8)
-                        {
-                            continue;
-                        } else {
-                            break;
-                        }
+            let p = transformed(text.get(index), position);
+            let p_len = unit_len(&self.separator, self.unit) + unit_len(&p, self.unit);
+            next = Some(p);
+            let mut allowance = 0;
+            if len < self.max_len {
+                allowance = self.max_len - len;
+            }
+
+            if p_len > allowance {
+                if len >= self.min_len && len <= self.max_len {
+                    return false;
+                } else if self.reset_count >= self.reset_amount
+                    && len < self.min_len
+                    && self.on_exhausted == ExhaustedBehaviour::Truncate
+                {
+                    // Overshoot with the next word, so it's truncated to the maximum
+                    // instead of ending up shorter than the minimum.
+                    continue;
+                } else if self.reset_count >= self.reset_amount {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(
+                        resets = self.reset_count,
+                        max_len = self.max_len,
+                        "ran out of assembly retries, truncating the password"
+                    );
+                    self.truncate_exhausted();
+                    let len = self.password.len();
+                    self.spans.retain(|span| span.start < len);
+                    self.initials.retain(|&i| i < len);
+                    self.boundaries.retain(|&i| i <= len);
+                    if let Some(last) = self.spans.last_mut() {
+                        last.end = last.end.min(len);
                     }
-                    None => {
-                        words = text.iter().skip(0).peekable();
+                    return true;
+                } else {
+                    self.reset_count += 1;
+                    self.password.clear();
+                    self.spans.clear();
+                    self.initials.clear();
+                    self.boundaries.clear();
+                    position = 0;
+                    number_words = 0;
+                    next = None;
+                    continue;
+                }
+            } else if len < self.min_len
+                || p_len <= allowance && rng.gen_bool(self.continue_probability)
+            {
+                continue;
+            } else {
+                return false;
+            }
+        }
+    }
+
+    /// Cut the password to the maximum length after the last word that fits,
+    /// or in the middle of the first word if even that one doesn't fit.
+    fn truncate_exhausted(&mut self) {
+        let word_end = match self.on_exhausted {
+            ExhaustedBehaviour::TruncateAtWord => self
+                .spans
+                .iter()
+                .rev()
+                .map(|span| span.end)
+                .find(|&end| unit_len(&self.password[..end], self.unit) <= self.max_len),
+            ExhaustedBehaviour::Truncate | ExhaustedBehaviour::Error => None,
+        };
+        match word_end {
+            Some(end) => self.password.truncate(end),
+            None => truncate_units(&mut self.password, self.max_len, self.unit),
+        }
+    }
+
+    /// Add a word after the separator, unless it's the first one.
+    fn push_word(&mut self, word: &str, position: usize) {
+        if position > 0 {
+            reserve(&mut self.password, self.separator.len());
+            self.password.push_str(&self.separator);
+        }
+        let start = self.password.len();
+        let exception = match self.word_case {
+            WordCase::Capitalised | WordCase::Camel => {
+                capitalise_exception(&self.capitalise_exceptions, word)
+            }
+            _ => None,
+        };
+        if let Some(exception) = exception {
+            reserve(&mut self.password, exception.len());
+            self.password.push_str(exception);
+        } else {
+            match self.word_case {
+                WordCase::AsIs => {
+                    reserve(&mut self.password, word.len());
+                    self.password.push_str(word);
+                }
+                WordCase::Camel if position == 0 => self.push_cased(word, char::to_lowercase),
+                WordCase::Capitalised | WordCase::Camel => {
+                    let mut chars = word.chars();
+                    if let Some(first) = chars.next() {
+                        reserve(&mut self.password, word.len() + MAX_CASE_BYTES);
+                        self.password.extend(first.to_uppercase());
+                        self.password.push_str(chars.as_str());
                     }
                 }
+                WordCase::Lower => self.push_cased(word, char::to_lowercase),
+                WordCase::Upper => self.push_cased(word, char::to_uppercase),
+            }
+        }
+        if self.password.len() > start {
+            self.spans.push(start..self.password.len());
+            self.initials.push(start);
+            if self.boundaries.last() != Some(&start) {
+                self.boundaries.push(start);
             }
+            self.boundaries.push(self.password.len());
         }
     }
 
-    fn replace_chars(&mut self) {
-        let mut rng = thread_rng();
-        let range = Uniform::new(0, self.password.len());
-        let mut new_pass = String::with_capacity(self.max_len);
-        let mut pos = Vec::with_capacity(self.total_inserts);
+    /// Add every character of the word with its case changed.
+    fn push_cased<I: Iterator<Item = char>>(&mut self, word: &str, case: impl Fn(char) -> I) {
+        reserve(&mut self.password, word.len() * MAX_CASE_BYTES);
+        for c in word.chars() {
+            self.password.extend(case(c));
+        }
+    }
 
-        while pos.len() < self.total_inserts {
-            let num = rng.sample(&range);
+    /// Join the given amount of words, with [`LengthMode::Words`].
+    fn get_words_string<R: Rng + ?Sized>(&mut self, amount: usize, rng: &mut R) {
+        let text = self.words;
+        let picker = self.picker.clone();
+        let weighting = self.weighting.clone();
+        let transform = self.transform.clone();
+        let max_number_words = self.max_number_words;
+        let mut number_words = 0;
+        // Words that aren't kept are passed over for as long as there are others left to try.
+        let kept = |mut index: usize, number_words: usize, rng: &mut R| {
+            let numbers_full = max_number_words.is_some_and(|max| number_words >= max);
+            for _ in 0..text.len() {
+                let word = text.get(index);
+                let rejected = numbers_full && is_number_word(word)
+                    || weighting
+                        .weight(word)
+                        .is_some_and(|weight| !rng.gen_bool(weight));
+                if !rejected {
+                    break;
+                }
+                index = picker.next(index, text.len(), rng);
+            }
+            index
+        };
 
-            if !pos.contains(&num) {
-                pos.push(num);
+        let mut index = kept(picker.pick(text.len(), rng), 0, rng);
+        if self.must_start_with_letter {
+            for _ in 0..text.len() {
+                if text.get(index).starts_with(char::is_alphabetic) {
+                    break;
+                }
+                index = picker.next(index, text.len(), rng);
             }
         }
+        self.start_index = index;
+        self.wrapped = false;
 
-        for (i, c) in self.password.char_indices() {
-            if pos.contains(&i) {
-                new_pass.push(self.insertables.pop().unwrap());
-            } else {
-                new_pass.push(c);
+        for position in 0..amount {
+            if position > 0 {
+                let previous = index;
+                index = kept(picker.next(index, text.len(), rng), number_words, rng);
+                if picker.selection == WordSelection::Consecutive && index <= previous {
+                    self.wrapped = true;
+                }
+            }
+
+            let word = match &transform {
+                Some(transform) => Cow::Owned(transform.apply(text.get(index), position)),
+                None => Cow::Borrowed(text.get(index)),
+            };
+            self.push_word(&word, position);
+            if is_number_word(&word) {
+                number_words += 1;
             }
         }
+    }
 
-        self.password = new_pass;
+    /// The length of the password in the configured unit.
+    fn len(&self) -> usize {
+        unit_len(&self.password, self.unit)
     }
 
-    fn insert_chars(&mut self) {
-        let mut rng = thread_rng();
+    fn replace_chars<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let indices = unit_indices(&self.password, self.unit);
+        let zone = self.zone(indices.len());
+        let lowest = zone.start.max(usize::from(self.must_start_with_letter));
+        let available = zone.end.saturating_sub(lowest);
 
-        if self.password.is_empty() {
-            self.password.push(self.insertables.pop().unwrap());
-            self.total_inserts -= 1;
+        let mut lengths: Vec<usize> = self
+            .insertables
+            .iter()
+            .map(|s| unit_len(s, self.unit))
+            .collect();
+        // The characters to keep between two insertables.
+        let gap = usize::from(self.no_adjacent_inserts);
+        while lengths.iter().sum::<usize>() + gap * lengths.len().saturating_sub(1) > available {
+            if let Some(insertable) = self.insertables.pop() {
+                wipe(insertable);
+                self.dropped_inserts += 1;
+            }
+            lengths.pop();
         }
+        if self.insertables.is_empty() {
+            return;
+        }
+
+        // Pick the positions as if every insertable was a single character long,
+        // then spread them out by the extra length of the ones before them
+        // and the gaps, so that the replaced ranges never overlap.
+        let total: usize = lengths.iter().sum();
+        let slots = available - total - gap * (lengths.len() - 1) + lengths.len();
+        let mut starts = index::sample(rng, slots, lengths.len()).into_vec();
+        starts.sort_unstable();
 
-        for _ in 0..self.total_inserts {
-            let index = rng.gen_range(0..self.password.len());
-            let c = self.insertables.pop().unwrap();
+        let byte_at = |unit: usize| indices.get(unit).copied().unwrap_or(self.password.len());
+        let mut blocks = Vec::with_capacity(lengths.len());
+        let mut offset = lowest;
 
-            self.password.insert(index, c);
+        for (slot, len) in starts.into_iter().zip(lengths) {
+            let start = slot + offset;
+            blocks.push(byte_at(start)..byte_at(start + len));
+            offset += len - 1 + gap;
+        }
+
+        // Going from the end so that the replacements don't shift the blocks that are left.
+        let insertables = std::mem::take(&mut self.insertables);
+        for (block, insertable) in blocks.into_iter().zip(insertables).rev() {
+            reserve(&mut self.password, insertable.len());
+            self.password.replace_range(block.clone(), &insertable);
+            self.update_spans(block.start, block.len(), insertable.len(), true);
+            wipe(insertable);
         }
     }
 
-    fn ensure_case(&mut self) {
-        let mut rng = thread_rng();
+    /// Replace characters like [`replace_chars()`](PasswordBuilder::replace_chars),
+    /// starting at the boundaries of the words for as long as there are any.
+    fn replace_at_boundaries<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let lowest = usize::from(self.must_start_with_letter);
+        let no_adjacent = self.no_adjacent_inserts;
+        // The ranges replaced so far, which are never replaced again.
+        let mut replaced: Vec<Range<usize>> = Vec::new();
 
-        let u_amount = self
-            .password
-            .matches(|c: char| c.is_ascii_uppercase())
-            .count();
+        for insertable in std::mem::take(&mut self.insertables) {
+            let indices = unit_indices(&self.password, self.unit);
+            let zone = self.zone(indices.len());
+            let len = unit_len(&insertable, self.unit);
+            let byte_at = |unit: usize| indices.get(unit).copied().unwrap_or(self.password.len());
+            let block = |unit: usize| byte_at(unit)..byte_at(unit + len);
+            let free = |unit: usize| {
+                let block = block(unit);
+                unit >= lowest.max(zone.start)
+                    && unit + len <= zone.end
+                    && replaced.iter().all(|r| match no_adjacent {
+                        true => r.end < block.start || r.start > block.end,
+                        false => r.end <= block.start || r.start >= block.end,
+                    })
+            };
+
+            let at_boundary = self
+                .boundaries
+                .iter()
+                .filter_map(|&at| match at == self.password.len() {
+                    // The end of the last word is replaced up to the end.
+                    true => indices.len().checked_sub(len),
+                    false => indices.binary_search(&at).ok(),
+                })
+                .filter(|&unit| free(unit))
+                .choose(rng);
+            let Some(unit) =
+                at_boundary.or_else(|| (0..indices.len()).filter(|&u| free(u)).choose(rng))
+            else {
+                wipe(insertable);
+                self.dropped_inserts += 1;
+                continue;
+            };
+
+            let block = block(unit);
+            self.boundaries
+                .retain(|&at| at != block.start && at != block.end);
+            reserve(&mut self.password, insertable.len());
+            self.password.replace_range(block.clone(), &insertable);
+            self.update_spans(block.start, block.len(), insertable.len(), true);
+            for r in &mut replaced {
+                if r.start >= block.end {
+                    *r = r.start - block.len() + insertable.len()
+                        ..r.end - block.len() + insertable.len();
+                }
+            }
+            replaced.push(block.start..block.start + insertable.len());
+            wipe(insertable);
+        }
+    }
+
+    /// The units of a password `len` units long that are within the
+    /// [`insert_zone`](PasswordSettings#structfield.insert_zone), if any.
+    fn zone(&self, len: usize) -> Range<usize> {
+        match &self.insert_zone {
+            Some(zone) => (*zone.start()).min(len)..zone.end().saturating_add(1).min(len),
+            None => 0..len,
+        }
+    }
+
+    /// Put the insertables together at the start or the end of the password,
+    /// the numbers before the rest, inserted or replacing the characters there.
+    fn insert_grouped(&mut self) {
+        let lowest = usize::from(self.must_start_with_letter);
+        let indices = unit_indices(&self.password, self.unit);
+        let inserted_len = |insertables: &[String]| -> usize {
+            insertables.iter().map(|s| unit_len(s, self.unit)).sum()
+        };
+        if self.replace {
+            let available = indices.len().saturating_sub(lowest);
+            while inserted_len(&self.insertables) > available {
+                if let Some(insertable) = self.insertables.pop() {
+                    wipe(insertable);
+                    self.dropped_inserts += 1;
+                }
+            }
+        }
+        if self.insertables.is_empty() {
+            return;
+        }
+
+        // Stable, so the numbers and the rest each keep the order they were picked in.
+        self.insertables
+            .sort_by_key(|s| !s.bytes().all(|b| b.is_ascii_digit()));
+        let len = inserted_len(&self.insertables);
+        let group = self.insertables.concat();
+        self.insertables.drain(..).for_each(wipe);
 
+        let start = match self.placement {
+            InsertPlacement::Prefix => lowest.min(indices.len()),
+            _ if self.replace => indices.len() - len,
+            _ => indices.len(),
+        };
+        let byte_at = |unit: usize| indices.get(unit).copied().unwrap_or(self.password.len());
+        let block = match self.replace {
+            true => byte_at(start)..byte_at(start + len),
+            false => byte_at(start)..byte_at(start),
+        };
+
+        reserve(&mut self.password, group.len());
+        self.password.replace_range(block.clone(), &group);
+        self.update_spans(block.start, block.len(), group.len(), true);
+        wipe(group);
+    }
+
+    /// Take one of the boundaries of the words that `allowed` accepts at random, if any.
+    fn take_boundary<R: Rng + ?Sized>(
+        &mut self,
+        allowed: impl Fn(usize) -> bool,
+        rng: &mut R,
+    ) -> Option<usize> {
+        let i = (0..self.boundaries.len())
+            .filter(|&i| allowed(self.boundaries[i]))
+            .choose(rng)?;
+        Some(self.boundaries.swap_remove(i))
+    }
+
+    /// Substitute a letter for every insertable, for as long as there are letters
+    /// with a substitution that are whole units.
+    fn substitute_letters<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let lowest = usize::from(self.must_start_with_letter);
+        let indices = unit_indices(&self.password, self.unit);
+        let substitutions = |letter: char| {
+            self.leet_substitutions.get(&letter).or_else(|| {
+                let mut lower = letter.to_lowercase();
+                match (lower.next(), lower.next()) {
+                    (Some(lower), None) => self.leet_substitutions.get(&lower),
+                    _ => None,
+                }
+            })
+        };
+
+        let eligible: Vec<(usize, char, &str)> = indices
+            .iter()
+            .enumerate()
+            .skip(lowest)
+            .filter_map(|(unit, &at)| {
+                let end = indices
+                    .get(unit + 1)
+                    .copied()
+                    .unwrap_or(self.password.len());
+                let mut chars = self.password[at..end].chars();
+                match (chars.next(), chars.next()) {
+                    (Some(letter), None) => substitutions(letter)
+                        .filter(|with| !with.is_empty())
+                        .map(|with| (at, letter, with.as_str())),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        let amount = self.insertables.len().min(eligible.len());
+        self.dropped_inserts += self.insertables.len() - amount;
+        let mut picked = index::sample(rng, eligible.len(), amount).into_vec();
+        picked.sort_unstable();
+
+        let mut changes = Vec::with_capacity(amount);
+        for i in picked {
+            let (at, letter, with) = eligible[i];
+            let with = with.chars().choose(rng).unwrap_or(letter);
+            changes.push((at, letter.len_utf8(), with));
+        }
+
+        // Going from the end so that the substitutions don't shift the letters that are left.
+        for (at, len, with) in changes.into_iter().rev() {
+            let mut buffer = [0; 4];
+            let with = with.encode_utf8(&mut buffer);
+            reserve(&mut self.password, with.len());
+            self.password.replace_range(at..at + len, with);
+            self.update_spans(at, len, with.len(), true);
+        }
+        self.insertables.drain(..).for_each(wipe);
+    }
+
+    fn insert_chars<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let lowest = usize::from(self.must_start_with_letter);
+        let no_adjacent = self.no_adjacent_inserts;
+        // The ranges inserted so far, to keep apart from with `no_adjacent_inserts`.
+        let mut inserted: Vec<Range<usize>> = Vec::new();
+
+        while let Some(insertable) = self.insertables.pop() {
+            let indices = unit_indices(&self.password, self.unit);
+            let apart =
+                |at: usize| !no_adjacent || inserted.iter().all(|r| at < r.start || at > r.end);
+            let unit_of = |at: usize| indices.binary_search(&at).unwrap_or_else(|unit| unit);
+            // The units it can go at so that, once the rest are inserted before it,
+            // it's still within the zone. The end of the password is one of them too.
+            let window = self.insert_zone.as_ref().map(|zone| {
+                let rest: usize = self
+                    .insertables
+                    .iter()
+                    .map(|s| unit_len(s, self.unit))
+                    .sum();
+                let len = unit_len(&insertable, self.unit);
+                let start = (*zone.start()).min(indices.len());
+                start..(zone.end() + 2).saturating_sub(len + rest)
+            });
+            let in_zone = |unit: usize| window.as_ref().is_none_or(|window| window.contains(&unit));
+            let boundary = if self.insert_at_boundaries {
+                self.take_boundary(
+                    |at| (lowest == 0 || at > 0) && apart(at) && in_zone(unit_of(at)),
+                    rng,
+                )
+            } else {
+                None
+            };
+            let index = if let Some(boundary) = boundary {
+                boundary
+            } else if no_adjacent || window.is_some() {
+                let last = match window {
+                    Some(_) => indices.len(),
+                    None => indices.len().saturating_sub(1).max(lowest),
+                };
+                let byte_at =
+                    |unit: usize| indices.get(unit).copied().unwrap_or(self.password.len());
+                match (lowest..=last)
+                    .filter(|&unit| in_zone(unit) && apart(byte_at(unit)))
+                    .map(byte_at)
+                    .choose(rng)
+                {
+                    Some(index) => index,
+                    None => {
+                        wipe(insertable);
+                        self.dropped_inserts += 1;
+                        continue;
+                    }
+                }
+            } else if lowest < indices.len() {
+                indices[rng.gen_range(lowest..indices.len())]
+            } else {
+                self.password.len()
+            };
+
+            reserve(&mut self.password, insertable.len());
+            self.password.insert_str(index, &insertable);
+            self.update_spans(index, 0, insertable.len(), true);
+            for r in &mut inserted {
+                if r.start >= index {
+                    *r = r.start + insertable.len()..r.end + insertable.len();
+                }
+            }
+            inserted.push(index..index + insertable.len());
+            wipe(insertable);
+        }
+    }
+
+    /// Keep the word spans and initials in place after `removed` bytes at `at` were replaced by `added` bytes.
+    ///
+    /// Inserted text cuts through the spans it lands on, while anything else,
+    /// like a change of case, only resizes them.
+    fn update_spans(&mut self, at: usize, removed: usize, added: usize, inserted: bool) {
+        let end = at + removed;
+        let shift = |i: usize| i - removed + added;
+        let mut spans = Vec::with_capacity(self.spans.len() + 1);
+
+        for span in self.spans.drain(..) {
+            if span.end <= at {
+                spans.push(span);
+            } else if span.start >= end {
+                spans.push(shift(span.start)..shift(span.end));
+            } else if inserted {
+                if span.start < at {
+                    spans.push(span.start..at);
+                }
+                if span.end > end {
+                    spans.push(at + added..shift(span.end));
+                }
+            } else {
+                spans.push(span.start..shift(span.end));
+            }
+        }
+
+        self.spans = spans;
+
+        // An initial that's replaced is gone, but one that only changes case stays where it is.
+        self.initials.retain(|&i| !(inserted && i >= at && i < end));
+        for i in &mut self.initials {
+            if *i >= end {
+                *i = shift(*i);
+            }
+        }
+
+        // A boundary right before the change stays before it, and one inside it is gone.
+        self.boundaries
+            .retain(|&i| !(inserted && i > at && i < end));
+        for i in &mut self.boundaries {
+            if *i > at && *i >= end {
+                *i = shift(*i);
+            }
+        }
+    }
+
+    fn adjust_case<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let (requested_upper, requested_lower) = (self.upper, self.lower);
+        let u_amount = count_upper(&self.password);
+        let mut force_upper = self.upper_rule == CaseRule::Force;
+        let mut force_lower = self.lower_rule == CaseRule::Force;
+        let (never_upper, never_lower) = (
+            self.upper_rule == CaseRule::Never,
+            self.lower_rule == CaseRule::Never,
+        );
+
+        // Only letters with a case are candidates, never the numbers, whether they
+        // were inserted or kept as words with `keep_numbers`.
         let mut l_indices: Vec<usize> = self
             .password
-            .char_indices()
-            .filter(|(_, c)| c.is_ascii_lowercase())
-            .collect::<Vec<(usize, char)>>()
-            .into_iter()
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| c.is_lowercase())
             .map(|(i, _)| i)
             .collect();
 
         if u_amount == 0 {
-            self.force_upper = true;
+            force_upper = true;
         } else if u_amount >= self.upper {
-            self.force_upper = false;
+            force_upper = false;
         } else {
             self.upper -= u_amount;
         }
 
+        self.upper_target = if never_upper {
+            0
+        } else if force_upper {
+            requested_upper
+        } else {
+            requested_upper.min(u_amount)
+        };
+
         if self.upper > l_indices.len() {
             self.upper = l_indices.len();
         }
 
-        if self.force_upper && !self.dont_upper {
+        if force_upper && !never_upper {
+            let mut chosen = Vec::with_capacity(self.upper);
             for _ in 0..self.upper {
-                let i = l_indices.remove(rng.gen_range(0..l_indices.len()));
-                capitalise(self.password.as_mut_str(), i)
+                chosen.push(l_indices.remove(rng.gen_range(0..l_indices.len())));
+            }
+
+            // Going from the end so that a character that changes length
+            // doesn't shift the ones that are left.
+            chosen.sort_unstable();
+            for i in chosen.into_iter().rev() {
+                reserve(&mut self.password, MAX_CASE_BYTES);
+                if let Some((range, len)) = capitalise(&mut self.password, i) {
+                    self.update_spans(range.start, range.len(), len, false);
+                }
             }
         }
 
+        let protected = |byte: usize| self.protect_word_initials && self.initials.contains(&byte);
         let mut u_indices: Vec<usize> = self
             .password
             .char_indices()
-            .filter(|(_, c)| c.is_ascii_uppercase())
-            .collect::<Vec<(usize, char)>>()
-            .into_iter()
+            .enumerate()
+            .filter(|(_, (byte, c))| c.is_uppercase() && !protected(*byte))
             .map(|(i, _)| i)
             .collect();
 
         if l_indices.is_empty() {
-            self.force_lower = true;
+            force_lower = true;
         } else if l_indices.len() >= self.lower {
-            self.force_lower = false;
+            force_lower = false;
         } else {
             self.lower -= l_indices.len();
         }
 
+        self.lower_target = if never_lower {
+            0
+        } else if force_lower {
+            requested_lower
+        } else {
+            requested_lower.min(l_indices.len())
+        };
+
         if self.lower > u_indices.len() {
             self.lower = u_indices.len();
         }
 
-        if self.force_lower && !self.dont_lower {
+        if force_lower && !never_lower {
+            let mut chosen = Vec::with_capacity(self.lower);
             for _ in 0..self.lower {
-                let i = u_indices.remove(rng.gen_range(0..u_indices.len()));
-                decapitalise(self.password.as_mut_str(), i)
+                chosen.push(u_indices.remove(rng.gen_range(0..u_indices.len())));
+            }
+
+            chosen.sort_unstable();
+            for i in chosen.into_iter().rev() {
+                reserve(&mut self.password, MAX_CASE_BYTES);
+                if let Some((range, len)) = decapitalise(&mut self.password, i) {
+                    self.update_spans(range.start, range.len(), len, false);
+                }
             }
         }
     }
 }
+
+/// Whether the password ended up with fewer uppercase or lowercase letters than the case was adjusted for.
+fn is_short_of_case(details: &PasswordDetails) -> bool {
+    details.achieved_upper < details.requested_upper
+        || details.achieved_lower < details.requested_lower
+}
+
+/// Whether the word is made only of numbers, as kept with `keep_numbers`.
+fn is_number_word(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(char::is_numeric)
+}
+
+/// The form of the capitalise exception matching the word, if any.
+fn capitalise_exception<'e>(exceptions: &'e [(String, String)], word: &str) -> Option<&'e str> {
+    if exceptions.is_empty() {
+        return None;
+    }
+    let lowercase = word.to_lowercase();
+    exceptions
+        .iter()
+        .find(|(matched, _)| *matched == lowercase)
+        .map(|(_, exception)| exception.as_str())
+}
@@ -0,0 +1,25 @@
+//! The types needed for most uses of the crate, to import all at once.
+//!
+//! # Example
+//!
+//! ```
+//! use genrepass::prelude::*;
+//!
+//! let mut lexicon = Lexicon::new("fox", Split::UnicodeWords);
+//! lexicon.deunicode = Deunicode::AfterFiltering;
+//! lexicon.extract_words("The quick brown fox jumps over the lazy dog", CharFilter::Unicode.closure());
+//!
+//! let mut settings = PasswordSettings::new();
+//! settings.set_words(lexicon.words().to_vec());
+//! settings.length = range_inc_from_str("16-20")?;
+//! assert_eq!(settings.generate()?.len(), 1);
+//!
+//! settings.clear_words();
+//! assert!(matches!(settings.generate(), Err(GenerateError::NotEnoughWords { .. })));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub use crate::{
+    range_inc_from_str, CharFilter, Deunicode, GenerateError, GenerateOptions, Lexicon,
+    NonAsciiSpecialCharsError, ParseRangeError, PasswordSettings, Split,
+};
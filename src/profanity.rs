@@ -0,0 +1,66 @@
+//! The blocklist of [`WordValidator::Profanity`](crate::WordValidator::Profanity).
+
+/// Common English profanity and slurs, in lowercase ASCII.
+///
+/// Only meant as a basic starting point, since no list like this is ever complete.
+const BLOCKLIST: &[&str] = &[
+    "arse",
+    "arsehole",
+    "ass",
+    "asshole",
+    "bastard",
+    "bitch",
+    "bitches",
+    "bollocks",
+    "bullshit",
+    "chink",
+    "cock",
+    "cocks",
+    "crap",
+    "cunt",
+    "cunts",
+    "damn",
+    "dick",
+    "dickhead",
+    "dicks",
+    "dyke",
+    "fag",
+    "faggot",
+    "fags",
+    "fuck",
+    "fucked",
+    "fucker",
+    "fuckers",
+    "fucking",
+    "fucks",
+    "gook",
+    "jizz",
+    "kike",
+    "motherfucker",
+    "nigga",
+    "nigger",
+    "piss",
+    "pissed",
+    "prick",
+    "pussy",
+    "retard",
+    "shit",
+    "shits",
+    "shitty",
+    "slut",
+    "sluts",
+    "spic",
+    "tits",
+    "twat",
+    "wank",
+    "wanker",
+    "whore",
+    "whores",
+];
+
+/// Whether the word is in the blocklist, ignoring case.
+pub(crate) fn is_profane(word: &str) -> bool {
+    BLOCKLIST
+        .iter()
+        .any(|blocked| blocked.eq_ignore_ascii_case(word))
+}
@@ -0,0 +1,434 @@
+use crate::settings::{RetryBudget, SettingsWarning};
+use std::{
+    collections::HashSet,
+    ops::{Range, RangeInclusive},
+    path::PathBuf,
+};
+
+/// The result of [`PasswordSettings::generate_detailed()`](crate::PasswordSettings::generate_detailed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationReport {
+    /// Every generated password along with how it came to be.
+    pub passwords: Vec<PasswordDetails>,
+
+    /// The advisory messages for the settings used, collected once per call.
+    ///
+    /// See [`PasswordSettings::warnings()`](crate::PasswordSettings::warnings).
+    pub warnings: Vec<SettingsWarning>,
+
+    /// The [fingerprint of the words](crate::PasswordSettings::words_fingerprint)
+    /// the passwords were generated from.
+    pub words_fingerprint: u64,
+}
+
+impl GenerationReport {
+    /// Consume the report, keeping only the passwords.
+    pub fn into_passwords(self) -> Vec<String> {
+        self.passwords.into_iter().map(|d| d.password).collect()
+    }
+}
+
+/// A single generated password and some information about its generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordDetails {
+    /// The generated password.
+    pub password: String,
+
+    /// How much of each [`RetryBudget`] was used.
+    pub retries: RetryBudget,
+
+    /// Whether the password had to be truncated to the maximum length
+    /// after running out of [assembly retries](RetryBudget::assembly),
+    /// as set by [`on_exhausted`](crate::PasswordSettings#structfield.on_exhausted).
+    pub truncated: bool,
+
+    /// The byte ranges of the words in the password, in order.
+    ///
+    /// They always lie on char boundaries and cover everything but the inserted
+    /// numbers and special characters and the separators, so a word with something inserted
+    /// in the middle of it is split into two spans. Case changes are kept within the span.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let text = "ärger über straße квас hund";
+    /// let words: Vec<String> = text.split(' ').map(str::to_uppercase).collect();
+    /// let mut settings = PasswordSettings::new();
+    /// settings.transliterate = false;
+    /// settings.get_words_from_str(text);
+    /// settings.pass_amount = 100;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_amount = 3..=5;
+    /// settings.lower_amount = 3..=5;
+    ///
+    /// for details in settings.generate_detailed().unwrap().passwords {
+    ///     for (i, span) in details.word_spans.iter().enumerate() {
+    ///         let word = details.password[span.clone()].to_uppercase();
+    ///         let last = i + 1 == details.word_spans.len();
+    ///         assert!(
+    ///             words.contains(&word) || last && details.truncated,
+    ///             "{word} in {}",
+    ///             details.password,
+    ///         );
+    ///     }
+    /// }
+    ///
+    /// settings.number_amount = 2..=4;
+    /// settings.special_chars_amount = 2..=4;
+    /// for replace in [false, true] {
+    ///     settings.replace = replace;
+    ///     for details in settings.generate_detailed().unwrap().passwords {
+    ///         let spans: String = details.word_spans.iter().map(|s| &details.password[s.clone()]).collect();
+    ///         let letters: String = details.password.chars().filter(|c| c.is_alphabetic()).collect();
+    ///         assert_eq!(spans, letters);
+    ///         assert!(details.word_spans.windows(2).all(|w| w[0].end <= w[1].start));
+    ///     }
+    /// }
+    /// ```
+    pub word_spans: Vec<Range<usize>>,
+
+    /// The position of the first word of the password in the words it was selected from,
+    /// which are in the order of [`mixed_words()`](crate::PasswordSettings::mixed_words).
+    pub start_index: usize,
+
+    /// Whether the [consecutive](crate::WordSelection::Consecutive) words ran off the end
+    /// and continued from the first one, see
+    /// [`no_wrap`](crate::PasswordSettings#structfield.no_wrap).
+    pub wrapped: bool,
+
+    /// The values rolled from the settings' ranges for this password.
+    ///
+    /// With [`consistent_batch`](crate::PasswordSettings#structfield.consistent_batch)
+    /// they're the same for every password of a call.
+    pub rolled: RolledParameters,
+
+    /// The amount of uppercase letters the case was adjusted for.
+    ///
+    /// That's the [rolled](RolledParameters::upper) amount when letters had to be uppercased,
+    /// the amount already there otherwise, and 0 with
+    /// [`CaseRule::Never`](crate::CaseRule::Never).
+    /// With fewer letters than that in the password there's a shortfall,
+    /// which [`strict_case`](crate::PasswordSettings#structfield.strict_case) rejects.
+    pub requested_upper: usize,
+
+    /// The amount of uppercase letters in the password.
+    pub achieved_upper: usize,
+
+    /// The same as [`requested_upper`](PasswordDetails::requested_upper) for lowercase letters.
+    pub requested_lower: usize,
+
+    /// The amount of lowercase letters in the password.
+    pub achieved_lower: usize,
+
+    /// The amount of the [rolled](RolledParameters) numbers and special characters
+    /// that were left out for lack of room, which
+    /// [`strict_inserts`](crate::PasswordSettings#structfield.strict_inserts) rejects.
+    pub dropped_inserts: usize,
+
+    /// The kinds of characters the password ended up with,
+    /// counting the characters of the special sequences and groups as special.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{composition, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 50;
+    /// settings.number_amount = 1..=4;
+    /// settings.special_chars_amount = 1..=4;
+    ///
+    /// for details in settings.generate_detailed().unwrap().passwords {
+    ///     let password = &details.password;
+    ///     assert_eq!(details.composition, composition(password, settings.get_special_chars()));
+    ///     assert_eq!(details.composition.len(), password.chars().count());
+    ///     assert_eq!(details.composition.upper, details.achieved_upper);
+    ///     assert_eq!(details.composition.other, 0);
+    /// }
+    /// ```
+    pub composition: Composition,
+}
+
+/// The amount of each kind of character in a password.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Composition {
+    /// Uppercase letters, of any script.
+    pub upper: usize,
+
+    /// Lowercase letters, of any script.
+    pub lower: usize,
+
+    /// ASCII digits.
+    pub digits: usize,
+
+    /// Characters from the special characters.
+    pub special: usize,
+
+    /// Everything else, like letters without case.
+    pub other: usize,
+}
+
+impl Composition {
+    /// The amount of characters counted.
+    pub fn len(&self) -> usize {
+        self.upper + self.lower + self.digits + self.special + self.other
+    }
+
+    /// Whether there were no characters to count.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Count the kinds of characters in `password`, taking the ones in `special_chars`
+/// as special even if they're also digits or letters.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{composition, Composition};
+/// assert_eq!(
+///     composition("Über9&straße_", "&"),
+///     Composition {
+///         upper: 1,
+///         lower: 9,
+///         digits: 1,
+///         special: 1,
+///         other: 1,
+///     }
+/// );
+/// ```
+pub fn composition(password: &str, special_chars: &str) -> Composition {
+    let mut composition = Composition::default();
+    for c in password.chars() {
+        if special_chars.contains(c) {
+            composition.special += 1;
+        } else if c.is_uppercase() {
+            composition.upper += 1;
+        } else if c.is_lowercase() {
+            composition.lower += 1;
+        } else if c.is_ascii_digit() {
+            composition.digits += 1;
+        } else {
+            composition.other += 1;
+        }
+    }
+    composition
+}
+
+/// The values rolled from the ranges in [`PasswordSettings`](crate::PasswordSettings)
+/// before generating a password.
+///
+/// The amounts are the ones rolled, even if some of the numbers and special characters
+/// were then dropped for not fitting the length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolledParameters {
+    /// The length window the password was built for, never wider than 50.
+    pub length: RangeInclusive<usize>,
+
+    /// The amount of numbers to insert.
+    pub numbers: usize,
+
+    /// The amount of special characters to insert.
+    pub special_chars: usize,
+
+    /// The amount of special characters to insert from each of the
+    /// [special groups](crate::PasswordSettings::add_special_group), in order.
+    pub special_groups: Vec<usize>,
+
+    /// The amount of uppercase letters to ensure.
+    pub upper: usize,
+
+    /// The amount of lowercase letters to ensure.
+    pub lower: usize,
+
+    /// The amount of words, when the length is measured in [words](crate::LengthMode::Words).
+    pub words: Option<usize>,
+}
+
+/// A way in which a password doesn't match what its
+/// [`PasswordSettings`](crate::PasswordSettings) promise,
+/// as found by [`PasswordSettings::conforms()`](crate::PasswordSettings::conforms).
+///
+/// Ranges without an upper limit end at `usize::MAX`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Nonconformity {
+    /// The length, measured in the
+    /// [`length_unit`](crate::PasswordSettings#structfield.length_unit).
+    Length {
+        observed: usize,
+        expected: RangeInclusive<usize>,
+    },
+    /// The amount of ASCII digits.
+    Numbers {
+        observed: usize,
+        expected: RangeInclusive<usize>,
+    },
+    /// The amount of characters from the special characters, sequences and groups.
+    SpecialChars {
+        observed: usize,
+        expected: RangeInclusive<usize>,
+    },
+    /// The amount of uppercase letters.
+    Upper {
+        observed: usize,
+        expected: RangeInclusive<usize>,
+    },
+    /// The amount of lowercase letters.
+    Lower {
+        observed: usize,
+        expected: RangeInclusive<usize>,
+    },
+}
+
+/// The result of [`PasswordSettings::dry_run()`](crate::PasswordSettings::dry_run).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRunReport {
+    /// The amount of words available.
+    pub words: usize,
+
+    /// See [`PasswordSettings::effective_length()`](crate::PasswordSettings::effective_length).
+    pub effective_length: RangeInclusive<usize>,
+
+    /// The amount of numbers and special characters to insert,
+    /// after dropping the ones that wouldn't fit the length.
+    pub inserts: RangeInclusive<usize>,
+
+    /// A rough estimate of the bits of entropy of each password,
+    /// see [`PasswordSettings::search_space_bits()`](crate::PasswordSettings::search_space_bits).
+    pub entropy: f64,
+
+    /// See [`PasswordSettings::warnings()`](crate::PasswordSettings::warnings).
+    pub warnings: Vec<SettingsWarning>,
+}
+
+/// The factors of [`PasswordSettings::search_space_bits()`](crate::PasswordSettings::search_space_bits),
+/// each in bits, to see which of the settings matters the most.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SearchSpaceBreakdown {
+    /// The choice of words.
+    pub words: f64,
+
+    /// The choice of how many words there are.
+    pub word_amount: f64,
+
+    /// The values and positions of the numbers.
+    pub numbers: f64,
+
+    /// The values and positions of the special characters, including the ones from the groups.
+    pub special_chars: f64,
+
+    /// The choice of letters to uppercase.
+    pub case: f64,
+}
+
+impl SearchSpaceBreakdown {
+    /// The bits of every factor together.
+    pub fn total(&self) -> f64 {
+        self.words + self.word_amount + self.numbers + self.special_chars + self.case
+    }
+}
+
+/// What happened to the files found while extracting words from a path.
+///
+/// Returned by [`PasswordSettings::get_words_from_path_with_report()`](crate::PasswordSettings::get_words_from_path_with_report)
+/// and [`Lexicon::extract_words_from_path_with()`](crate::Lexicon::extract_words_from_path_with).
+/// The files left out by the filtering, like the hidden ones, aren't counted.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WalkReport {
+    /// The amount of files read.
+    pub read: usize,
+
+    /// The files that were found but not read, in the order they were found.
+    pub skipped: Vec<(PathBuf, SkipReason)>,
+}
+
+/// Why a file found while extracting words from a path wasn't read.
+///
+/// The files that vanish or change while being read, like in a directory that's
+/// being synchronised, are retried once before being skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// It was removed after being found.
+    Vanished,
+
+    /// The amount of bytes read differs from the size it had by more than half,
+    /// so it was most likely being written to.
+    ChangedDuringRead,
+
+    /// It isn't text in a supported encoding.
+    NotText,
+
+    /// Any other error, like lacking the permissions to read it.
+    Unreadable,
+}
+
+/// Summary numbers about a list of words, for deciding how to show them.
+///
+/// Returned by [`PasswordSettings::word_stats()`](crate::PasswordSettings::word_stats)
+/// and [`Lexicon::word_stats()`](crate::Lexicon::word_stats).
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{PasswordSettings, WordStats};
+/// let mut settings = PasswordSettings::new();
+/// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+///
+/// assert_eq!(
+///     settings.word_stats(true),
+///     WordStats {
+///         count: 9,
+///         total_bytes: 35,
+///         shortest: 3,
+///         longest: 5,
+///         distinct: Some(8),
+///     }
+/// );
+/// assert_eq!(settings.word_stats(false).distinct, None);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WordStats {
+    /// The amount of words.
+    pub count: usize,
+
+    /// The size of all the words together, in bytes.
+    pub total_bytes: usize,
+
+    /// The length of the shortest word in characters, or 0 without words.
+    pub shortest: usize,
+
+    /// The length of the longest word in characters, or 0 without words.
+    pub longest: usize,
+
+    /// The amount of different words, only counted when asked for
+    /// since it needs to keep track of every word.
+    pub distinct: Option<usize>,
+}
+
+impl WordStats {
+    pub(crate) fn of(words: &[String], distinct: bool) -> Self {
+        let mut stats = WordStats {
+            count: words.len(),
+            shortest: usize::MAX,
+            ..Default::default()
+        };
+
+        for word in words {
+            let len = word.chars().count();
+            stats.total_bytes += word.len();
+            stats.shortest = stats.shortest.min(len);
+            stats.longest = stats.longest.max(len);
+        }
+        if words.is_empty() {
+            stats.shortest = 0;
+        }
+        if distinct {
+            stats.distinct = Some(words.iter().collect::<HashSet<_>>().len());
+        }
+
+        stats
+    }
+}
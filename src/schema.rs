@@ -0,0 +1,68 @@
+use crate::settings::PasswordSettings;
+use schemars::{
+    schema::{RootSchema, Schema, SchemaObject},
+    schema_for,
+    visit::{visit_schema_object, Visitor},
+};
+
+impl PasswordSettings {
+    /// The [JSON Schema](https://json-schema.org) of the settings as they're serialised,
+    /// for validating configuration files in an editor.
+    ///
+    /// Every field is described with its documentation, leaving out the examples,
+    /// and marked with its [default](PasswordSettings::default).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let schema = serde_json::to_value(PasswordSettings::json_schema()).unwrap();
+    /// let length = &schema["properties"]["length"];
+    /// assert_eq!(length["title"], "Set the length of the password");
+    /// assert_eq!(length["default"], serde_json::json!({ "start": 24, "end": 30 }));
+    /// ```
+    pub fn json_schema() -> RootSchema {
+        let mut root = schema_for!(PasswordSettings);
+
+        let defaults = serde_json::to_value(PasswordSettings::default())
+            .expect("the default settings should always serialise");
+        for (name, property) in &mut root.schema.object().properties {
+            if let (Schema::Object(property), Some(default)) = (property, defaults.get(name)) {
+                with_metadata(property).metadata().default = Some(default.clone());
+            }
+        }
+
+        WithoutExamples.visit_root_schema(&mut root);
+        root
+    }
+}
+
+/// Wrap a reference, since anything next to a `$ref` is ignored.
+fn with_metadata(schema: &mut SchemaObject) -> &mut SchemaObject {
+    if schema.is_ref() {
+        let reference = std::mem::take(schema);
+        schema.subschemas().all_of = Some(vec![reference.into()]);
+    }
+    schema
+}
+
+/// Cuts the descriptions at their first section, like `# Example`,
+/// which only make sense as Rust.
+struct WithoutExamples;
+
+impl Visitor for WithoutExamples {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if let Some(description) = schema
+            .metadata
+            .as_mut()
+            .and_then(|metadata| metadata.description.as_mut())
+        {
+            if description.starts_with("# ") {
+                description.clear();
+            } else if let Some(end) = description.find("\n\n# ") {
+                description.truncate(end);
+            }
+        }
+        visit_schema_object(self, schema);
+    }
+}
@@ -1,23 +1,176 @@
-use crate::{helpers::get_text_from_dir, password::Password};
+use crate::{
+    arena::{LazyArena, LazyShuffle, ShuffledWords},
+    frozen::FrozenSettings,
+    helpers::{
+        capped_slot, count_digits, count_lower, count_upper, find_words, fingerprint,
+        first_occurrences, for_each_text_in_dir, unit_len, words_page, Fingerprint,
+    },
+    iter::{IterWords, PasswordIter},
+    lexicon::{push_split_words, CapStrategy, CharFilter, Deunicode, Lexicon, Split},
+    options::GenerateOptions,
+    password::{PasswordBuilder, WordPicker, Words},
+    report::{
+        composition, DryRunReport, GenerationReport, Nonconformity, PasswordDetails,
+        RolledParameters, SearchSpaceBreakdown, WalkReport, WordStats,
+    },
+    sources::{Source, SourceId, SourceLoad, SourceMixing},
+};
 use deunicode::deunicode;
-use rand::{seq::SliceRandom, thread_rng};
+use rand::{
+    rngs::StdRng,
+    seq::{index, SliceRandom},
+    thread_rng, Rng, RngCore, SeedableRng,
+};
 use regex::Regex;
 use snafu::{ensure, Snafu};
-use std::{fs, fs::metadata, ops::RangeInclusive, path::Path};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashSet},
+    fmt, fs,
+    fs::metadata,
+    mem::take,
+    ops::{ControlFlow, RangeInclusive},
+    panic::{RefUnwindSafe, UnwindSafe},
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroizing;
 
 /// Used for configuring the password generator.
-#[derive(Debug)]
+///
+/// Settings can be cloned, like to keep a copy to reset to, and compared.
+/// Custom [word transforms](WordTransform::Custom), [weightings](WordWeighting::Custom)
+/// and [validators](WordValidator::Custom) are only equal to themselves and their clones, since closures can't be compared.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{PasswordSettings, WordCase};
+/// let mut settings = PasswordSettings::new();
+/// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+/// let saved = settings.clone();
+/// assert_eq!(settings, saved);
+///
+/// settings.word_case = WordCase::Capitalised;
+/// settings.get_words_from_str("sphinx of black quartz");
+/// assert_ne!(settings, saved);
+///
+/// settings = saved.clone();
+/// assert_eq!(settings, saved);
+///
+/// settings.set_word_transform(|word, _| word.to_uppercase());
+/// let transformed = settings.clone();
+/// assert_eq!(settings, transformed);
+/// settings.set_word_transform(|word, _| word.to_uppercase());
+/// assert_ne!(settings, transformed);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(remote = "Self", default))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PasswordSettings {
     /// ### Uppercase the first character of every word
     ///
-    /// Makes the password much easier to read, but also slightly less secure
-    /// due to the predictability of having capitalised words. Still, the
-    /// highly improved readability makes it worth it to always have it on.
+    /// The same as [`WordCase::Capitalised`], which is used instead when
+    /// [`word_case`](PasswordSettings#structfield.word_case) is left as [`WordCase::AsIs`].
     ///
     /// **Default: false**
+    #[deprecated(note = "use `word_case` with `WordCase::Capitalised` instead")]
     pub capitalise: bool,
 
+    /// ### Change the case of the words
+    ///
+    /// Capitalising makes the password much easier to read, but also slightly less
+    /// secure due to the predictability of the case. Still, the highly improved
+    /// readability makes it worth it to always have it on. Along with the
+    /// [`separator`](PasswordSettings#structfield.separator), it gives styles
+    /// like `correct-horse-battery` or `correctHorseBattery`.
+    ///
+    /// The [case adjustments](PasswordSettings#structfield.upper_amount) keep to it, so
+    /// no letters are uppercased with [`WordCase::Lower`] or lowercased with [`WordCase::Upper`],
+    /// and the initials aren't lowercased with [`WordCase::Capitalised`] and [`WordCase::Camel`].
+    ///
+    /// **Default: [`WordCase::AsIs`]**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{PasswordSettings, WordCase};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("Correct horse BATTERY");
+    /// settings.length = 21..=21;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.separator = String::from("-");
+    ///
+    /// // Starting from any of the words.
+    /// let generate = |settings: &PasswordSettings| settings.generate().unwrap().remove(0);
+    /// settings.word_case = WordCase::Lower;
+    /// let password = generate(&settings);
+    /// assert!(password.contains("battery") && password.matches('-').count() == 2);
+    /// assert_eq!(password, password.to_lowercase());
+    ///
+    /// settings.separator = String::from("_");
+    /// settings.word_case = WordCase::Upper;
+    /// let password = generate(&settings);
+    /// assert!(password.contains("CORRECT") && password.matches('_').count() == 2);
+    /// assert_eq!(password, password.to_uppercase());
+    ///
+    /// settings.separator = String::new();
+    /// settings.length = 19..=19;
+    /// settings.word_case = WordCase::Camel;
+    /// assert!(["correctHorseBATTERY", "horseBATTERYCorrect", "batteryCorrectHorse"]
+    ///     .contains(&generate(&settings).as_str()));
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub word_case: WordCase,
+
+    /// ### Text put between the words
+    ///
+    /// Makes the words easier to tell apart when typing, like `Correct-Horse-Battery`.
+    /// It counts towards the [`length`](PasswordSettings#structfield.length),
+    /// but it isn't one of the special characters to insert,
+    /// even if it's made of the same characters.
+    ///
+    /// **Default: empty**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{PasswordSettings, WordCase};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("correct horse battery staple");
+    /// settings.word_case = WordCase::Capitalised;
+    /// settings.separator = "-".to_string();
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_amount = 0..=0;
+    /// settings.length = 20..=30;
+    /// settings.pass_amount = 50;
+    ///
+    /// for password in settings.generate().unwrap() {
+    ///     assert!((20..=30).contains(&password.len()), "{password}");
+    ///     assert!(!password.starts_with('-') && !password.contains("--"), "{password}");
+    ///     for word in password.split('-') {
+    ///         let last = password.ends_with(word);
+    ///         assert!(
+    ///             ["Correct", "Horse", "Battery", "Staple"].contains(&word) || last,
+    ///             "{password}",
+    ///         );
+    ///     }
+    ///     assert_eq!(settings.conforms(&password), Ok(()));
+    /// }
+    ///
+    /// // Too long to fit between any two words, so there's only ever one.
+    /// settings.separator = "-".repeat(20);
+    /// for password in settings.generate().unwrap() {
+    ///     assert!(["Correct", "Horse", "Battery", "Staple"].contains(&password.as_str()));
+    /// }
+    /// ```
+    pub separator: String,
+
     /// ### Replace the original characters
     ///
     /// Instead of inserting the numbers and special characters (which preserves
@@ -26,31 +179,392 @@ pub struct PasswordSettings {
     /// **Default: false**
     pub replace: bool,
 
+    /// ### Most of the minimum length that should be replaced
+    ///
+    /// With [`replace`](PasswordSettings#structfield.replace), the numbers and special
+    /// characters don't make the password longer, so too many of them leave little of
+    /// the words in the shortest passwords. [`warnings()`](PasswordSettings::warnings)
+    /// reports when the most that can be replaced is more than this fraction
+    /// of the minimum [length](PasswordSettings#structfield.length).
+    ///
+    /// **Default: 0.5**
+    pub(crate) max_replaced_fraction: f64,
+
+    /// ### Substitute letters with what they look like
+    ///
+    /// Instead of inserting the numbers and special characters, substitute letters
+    /// at random with one of their [leet substitutions](PasswordSettings::set_leet_substitution),
+    /// like `P@ssw0rd`, which is easier to remember. It takes precedence over
+    /// [`replace`](PasswordSettings#structfield.replace).
+    ///
+    /// As many letters are substituted as numbers and special characters would have been
+    /// inserted, but only letters with a substitution can be, so there can be fewer,
+    /// down to none at all. The password keeps its length.
+    ///
+    /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("password");
+    /// settings.length = 8..=8;
+    /// settings.upper_amount = 0..=0;
+    /// settings.number_amount = 1..=1;
+    /// settings.special_chars_amount = 1..=1;
+    /// settings.leet = true;
+    ///
+    /// let password = settings.generate().unwrap().remove(0);
+    /// assert_eq!(password.len(), 8);
+    /// let substituted = password.chars().zip("password".chars()).filter(|(a, b)| a != b);
+    /// assert_eq!(substituted.count(), 2);
+    ///
+    /// // Nothing in these words has a substitution.
+    /// settings.get_words_from_str("lymph");
+    /// settings.remove_word_at(0);
+    /// settings.length = 5..=5;
+    /// assert_eq!(settings.generate().unwrap(), ["lymph"]);
+    /// ```
+    pub leet: bool,
+
+    /// ### The substitutions of the letters with [`leet`](PasswordSettings#structfield.leet)
+    ///
+    /// Set with [`set_leet_substitution()`](PasswordSettings::set_leet_substitution).
+    #[cfg_attr(feature = "serde", serde(default = "default_leet_substitutions"))]
+    pub(crate) leet_substitutions: BTreeMap<char, String>,
+
+    /// ### Insert the numbers and special characters between the words
+    ///
+    /// A character inside a word like `Cor7rect` makes it much harder to read than one
+    /// between the words like `Correct7Horse`. With this set, the numbers and special
+    /// characters are inserted, or [replace](PasswordSettings#structfield.replace)
+    /// the characters, only where a word starts or ends, each of those at most once.
+    /// Once there are no such places left, the rest go at random positions like usual.
+    /// It doesn't change where [`leet`](PasswordSettings#structfield.leet) substitutes letters.
+    ///
+    /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("correct horse battery staple");
+    /// settings.length = 20..=24;
+    /// settings.number_amount = 2..=2;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_amount = 0..=0;
+    /// settings.insert_at_boundaries = true;
+    /// settings.pass_amount = 50;
+    ///
+    /// let words = ["correct", "horse", "battery", "staple"];
+    /// for password in settings.generate().unwrap() {
+    ///     for part in password.split(|c: char| c.is_ascii_digit()) {
+    ///         let mut rest = part;
+    ///         while let Some(word) = words.iter().find(|word| rest.starts_with(*word)) {
+    ///             rest = &rest[word.len()..];
+    ///         }
+    ///         // Only the word cut off at the end can be partial.
+    ///         assert!(rest.is_empty() || password.ends_with(rest), "{password}");
+    ///     }
+    /// }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub insert_at_boundaries: bool,
+
+    /// ### Where the numbers and special characters go
+    ///
+    /// Anywhere in the password, or all together at its start or its end
+    /// like `CorrectHorse42!`. See [`InsertPlacement`].
+    ///
+    /// **Default: [`InsertPlacement::Random`]**
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub insert_placement: InsertPlacement,
+
+    /// ### Keep the numbers and special characters apart
+    ///
+    /// Two of them next to each other like `wo^%rd` are the hardest to type on a phone.
+    /// With this set, each one is inserted, or [replaces](PasswordSettings#structfield.replace)
+    /// the characters, with at least one other character between it and the rest.
+    /// The ones there isn't room for are left out, which is recorded in
+    /// [`dropped_inserts`](PasswordDetails::dropped_inserts) and rejected with
+    /// [`strict_inserts`](PasswordSettings#structfield.strict_inserts).
+    /// It doesn't apply to [`leet`](PasswordSettings#structfield.leet) or when they're
+    /// grouped by the [`insert_placement`](PasswordSettings#structfield.insert_placement).
+    ///
+    /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.special_chars_amount = 4..=4;
+    /// settings.no_adjacent_inserts = true;
+    /// settings.pass_amount = 50;
+    ///
+    /// for password in settings.generate().unwrap() {
+    ///     let chars: Vec<char> = password.chars().collect();
+    ///     assert!(chars.windows(2).all(|pair| pair.iter().any(|c| c.is_alphabetic())));
+    /// }
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub no_adjacent_inserts: bool,
+
+    /// ### Keep the numbers and special characters within a range of characters
+    ///
+    /// For sites that silently cut passwords short, so that all of them land
+    /// in the first characters that are kept. The range is of the characters,
+    /// or the [`length_unit`](PasswordSettings#structfield.length_unit), of the finished
+    /// password, starting at 0, and is clamped to its length. The ones there isn't
+    /// room for are left out like with
+    /// [`no_adjacent_inserts`](PasswordSettings#structfield.no_adjacent_inserts),
+    /// and it's an error when the fewest of them can't be
+    /// [replaced](PasswordSettings#structfield.replace) within the zone.
+    /// It doesn't apply to [`leet`](PasswordSettings#structfield.leet) or when they're
+    /// grouped by the [`insert_placement`](PasswordSettings#structfield.insert_placement).
+    ///
+    /// **Default: none**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{GenerateError, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.length = 24..=32;
+    /// settings.number_amount = 2..=3;
+    /// settings.special_chars_amount = 2..=3;
+    /// settings.insert_zone = Some(0..=19);
+    /// settings.pass_amount = 50;
+    ///
+    /// for password in settings.generate().unwrap() {
+    ///     assert!(password[20..].chars().all(char::is_alphabetic), "{password}");
+    /// }
+    ///
+    /// settings.replace = true;
+    /// settings.insert_zone = Some(0..=2);
+    /// assert!(matches!(
+    ///     settings.generate(),
+    ///     Err(GenerateError::InsertZoneTooSmall { .. })
+    /// ));
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub insert_zone: Option<RangeInclusive<usize>>,
+
     /// ### Shuffle the words
     ///
     /// Useful if the source text is just a list of words without order anyway
     /// and you want to have a different order with each run.
     ///
+    /// Extracting words always adds them in order. The words added since the last call to
+    /// [`shuffle_words()`](PasswordSettings::shuffle_words) only make the generation
+    /// see them in a shuffled order, while [`words()`](PasswordSettings::words) keeps them
+    /// as they were added. That order is shuffled the first time it's needed, from the
+    /// [`seed`](PasswordSettings#structfield.seed) if there's one, and kept until the
+    /// words change, while [`shuffle_words()`](PasswordSettings::shuffle_words) shuffles
+    /// the stored words themselves.
+    ///
     /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.randomise = true;
+    /// settings.get_words_from_str("alpha bravo charlie delta echo foxtrot golf hotel india");
+    /// settings.get_words_from_str("juliett kilo lima mike november oscar papa quebec romeo");
+    /// let in_order: Vec<&str> = settings.words().iter().map(String::as_str).collect();
+    /// assert_eq!(in_order[..3], ["alpha", "bravo", "charlie"]);
+    /// assert_eq!(in_order[9..12], ["juliett", "kilo", "lima"]);
+    ///
+    /// // The order used for generation, which is kept until the words change.
+    /// let mut mixed = settings.mixed_words();
+    /// assert_ne!(mixed, in_order);
+    /// assert_eq!(settings.mixed_words(), mixed);
+    /// mixed.sort_unstable();
+    /// assert_eq!(mixed, in_order);
+    /// assert!(settings.generate().is_ok());
+    /// assert_eq!(settings.words()[0], "alpha");
+    ///
+    /// // Once shuffled for good, the order is kept.
+    /// settings.shuffle_words();
+    /// let shuffled = settings.words().to_vec();
+    /// assert_eq!(settings.mixed_words(), shuffled);
+    /// ```
     pub randomise: bool,
 
     /// ### Amount of passwords to generate
     ///
     /// Useful for providing a list of passwords to choose from.
+    /// When the amount changes from call to call, use
+    /// [`generate_n()`](PasswordSettings::generate_n) instead.
     ///
     /// **Default: 1**
     pub pass_amount: usize,
 
-    /// ### Amount of times to try generating password before truncating
+    /// ### Roll the amounts once for all the passwords of a call
+    ///
+    /// The length window and the amounts of numbers, special characters,
+    /// uppercase and lowercase letters are rolled once for each call to generate,
+    /// instead of once for each password, so that a batch of passwords all look alike.
+    /// The words and where the characters are inserted still vary.
+    ///
+    /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 20;
+    /// settings.number_amount = 1..=4;
+    /// settings.special_chars_amount = 1..=4;
+    /// settings.consistent_batch = true;
+    ///
+    /// let count = |password: &str, f: fn(&char) -> bool| password.chars().filter(f).count();
+    /// for _ in 0..10 {
+    ///     let report = settings.generate_detailed().unwrap();
+    ///     let rolled = &report.passwords[0].rolled;
+    ///     for details in &report.passwords {
+    ///         assert_eq!(&details.rolled, rolled);
+    ///         assert_eq!(count(&details.password, char::is_ascii_digit), rolled.numbers);
+    ///         assert_eq!(
+    ///             count(&details.password, char::is_ascii_punctuation),
+    ///             rolled.special_chars,
+    ///         );
+    ///     }
+    /// }
+    /// ```
+    pub consistent_batch: bool,
+
+    /// ### Never give the same password twice in one call
+    ///
+    /// A password that was already generated in the same call is generated again,
+    /// up to [`retry_budget.uniqueness`](RetryBudget::uniqueness) times, before
+    /// giving up with [`GenerateError::CannotSatisfyUniqueness`]. Duplicates are only
+    /// likely with few words, a narrow length and few characters to insert.
+    ///
+    /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{GenerateError, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 50;
+    /// settings.unique = true;
+    ///
+    /// let mut passwords = settings.generate().unwrap();
+    /// passwords.sort();
+    /// passwords.dedup();
+    /// assert_eq!(passwords.len(), 50);
+    ///
+    /// // Only "ab" and "ba" can be made from these.
+    /// settings.set_words(vec![String::from("a"), String::from("b")]);
+    /// settings.length = 2..=2;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_amount = 0..=0;
+    /// settings.pass_amount = 3;
+    /// assert!(matches!(
+    ///     settings.generate(),
+    ///     Err(GenerateError::CannotSatisfyUniqueness { .. })
+    /// ));
+    /// ```
+    pub unique: bool,
+
+    /// ### Seed for the generation
+    ///
+    /// When set, generating derives all of its randomness from this seed,
+    /// so the same seed with the same words and settings always gives the same passwords.
+    /// The password at each index only depends on the seed and the index,
+    /// so [`generate_parallel()`](PasswordSettings::generate_parallel) gives the same
+    /// passwords as [`generate()`](PasswordSettings::generate).
+    ///
+    /// The same holds for settings that were serialised and deserialised elsewhere.
+    /// The stored words are never shuffled by [`randomise`](PasswordSettings#structfield.randomise),
+    /// only the order the generation sees, which is derived from the seed, and the words
+    /// added since the last [`shuffle_words()`](PasswordSettings::shuffle_words) are
+    /// serialised as pending that shuffle. Only the
+    /// [`word_transform`](PasswordSettings#structfield.word_transform) isn't serialised,
+    /// so it has to be set again.
+    ///
+    /// Only meant for tests and reproducible examples, since anyone with the seed
+    /// can generate the same passwords.
+    ///
+    /// **Default: none**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 10;
+    /// settings.randomise = true;
+    /// settings.seed = Some(42);
+    ///
+    /// let passwords = settings.generate().unwrap();
+    /// assert_eq!(settings.generate().unwrap(), passwords);
+    /// assert_eq!(settings.generate_n(3).unwrap(), passwords[..3]);
+    ///
+    /// settings.seed = Some(43);
+    /// assert_ne!(settings.generate().unwrap(), passwords);
+    /// ```
+    pub seed: Option<u64>,
+
+    /// ### How many times to retry generating each password
+    ///
+    /// See [`RetryBudget`] for each of the reasons to retry.
+    ///
+    /// **Default: 10 for each**
+    pub retry_budget: RetryBudget,
+
+    /// ### What to do when a password still doesn't fit the length
+    ///
+    /// After running out of [assembly retries](RetryBudget::assembly), the password
+    /// is either truncated after the last word that fits the maximum length, truncated
+    /// to the maximum length even in the middle of a word, or the generation errors
+    /// with [`GenerateError::LengthNotSatisfied`].
+    ///
+    /// **Default: [`ExhaustedBehaviour::TruncateAtWord`]**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{ExhaustedBehaviour, GenerateError, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("incomprehensibilities counterrevolutionaries");
+    /// settings.length = 10..=10;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.pass_amount = 5;
+    ///
+    /// for password in settings.generate().unwrap() {
+    ///     assert_eq!(password.len(), 10);
+    /// }
+    ///
+    /// settings.on_exhausted = ExhaustedBehaviour::Error;
+    /// assert!(matches!(
+    ///     settings.generate(),
+    ///     Err(GenerateError::LengthNotSatisfied { .. })
+    /// ));
+    /// ```
+    pub on_exhausted: ExhaustedBehaviour,
+
+    /// ### Whether the passwords of a call share their assembly retries
     ///
-    /// If the range is too small or an exact number, it'll be harder
-    /// to get a fitting set of words, so the word selection will restart if
-    /// the password exceeds the maximum length. But since it would keep
-    /// looping if it doesn't find the right length it needs a way to stop,
-    /// which in this case is simply truncating the password to the maximum length.
+    /// With [`BatchRetryMode::SharedPool`], the passwords that are hard to fit
+    /// can use the retries that the easy ones didn't need.
     ///
-    /// **Default: 10**
-    pub reset_amount: usize,
+    /// **Default: [`BatchRetryMode::PerPassword`]**
+    pub batch_retry: BatchRetryMode,
 
     /// ### Set the length of the password
     ///
@@ -65,6 +579,33 @@ pub struct PasswordSettings {
     /// Panics upon generation if the inclusive range is empty (i.e. end < start).
     pub length: RangeInclusive<usize>,
 
+    /// ### What the length of the password is measured in
+    ///
+    /// Regardless of the unit, truncation never splits a grapheme cluster,
+    /// so the password could end up slightly shorter than the minimum length instead.
+    ///
+    /// **Default: [`LengthUnit::Chars`]**
+    pub length_unit: LengthUnit,
+
+    /// ### Whether the length is a length or an amount of words
+    ///
+    /// See [`LengthMode`].
+    ///
+    /// **Default: [`LengthMode::Units`]**
+    pub length_mode: LengthMode,
+
+    /// ### Chance of adding another word once the password is long enough
+    ///
+    /// Words keep being added until the minimum length is reached, and from then on
+    /// each word that still fits is added with this probability. Lower values
+    /// make the passwords cluster at the short end of the
+    /// [length](PasswordSettings#structfield.length), higher ones at the long end,
+    /// with 0 stopping at the first word that reaches the minimum
+    /// and 1 adding words for as long as they fit.
+    ///
+    /// **Default: 0.8**
+    pub(crate) continue_probability: f64,
+
     /// ### Amount of numbers to insert
     ///
     /// Can take either a range like 2-4 or an exact amount like 2.
@@ -96,13 +637,88 @@ pub struct PasswordSettings {
     /// **Default: ^!(-_=)$<\[@.#\]>%{~,+}&\***
     pub(crate) special_chars: String,
 
+    /// ### The special sequences to insert
+    ///
+    /// Strings like `!!` that are inserted whole, picked from the same pool as
+    /// the [special characters](PasswordSettings::set_special_chars), so each one
+    /// counts as a single special character towards
+    /// [`special_chars_amount`](PasswordSettings#structfield.special_chars_amount).
+    /// Non-ASCII characters are not supported and will error.
+    ///
+    /// **Default: none**
+    pub(crate) special_sequences: Vec<String>,
+
+    /// ### Named groups of special characters with their own amounts
+    ///
+    /// Set with [`add_special_group()`](PasswordSettings::add_special_group).
+    ///
+    /// **Default: none**
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) special_groups: Vec<SpecialGroup>,
+
+    /// ### Words that must not appear in the passwords
+    ///
+    /// Added with [`add_context_exclusion()`](PasswordSettings::add_context_exclusion).
+    ///
+    /// **Default: none**
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) context_exclusions: Vec<String>,
+
+    /// ### Words kept as they're written instead of capitalised
+    ///
+    /// Added with [`add_capitalise_exception()`](PasswordSettings::add_capitalise_exception).
+    ///
+    /// **Default: none**
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) capitalise_exceptions: Vec<String>,
+
+    /// ### Don't insert the same character twice
+    ///
+    /// Numbers and special characters are picked without replacement,
+    /// so every inserted digit differs from the others and so does every special character
+    /// or [sequence](PasswordSettings::set_special_sequences).
+    /// Generating errors if the upper end of either amount exceeds what's available.
+    ///
+    /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{GenerateError, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.distinct_inserts = true;
+    /// settings.set_special_chars("!?#%").unwrap();
+    /// settings.special_chars_amount = 5..=5;
+    /// assert!(matches!(
+    ///     settings.generate(),
+    ///     Err(GenerateError::NotEnoughDistinctInserts { .. })
+    /// ));
+    ///
+    /// settings.special_chars_amount = 4..=4;
+    /// settings.number_amount = 5..=8;
+    /// settings.pass_amount = 100;
+    /// for replace in [false, true] {
+    ///     settings.replace = replace;
+    ///     for password in settings.generate().unwrap() {
+    ///         let mut specials: Vec<char> = password.chars().filter(|c| "!?#%".contains(*c)).collect();
+    ///         let mut digits: Vec<char> = password.chars().filter(char::is_ascii_digit).collect();
+    ///         let amount = (specials.len(), digits.len());
+    ///         specials.sort_unstable();
+    ///         specials.dedup();
+    ///         digits.sort_unstable();
+    ///         digits.dedup();
+    ///         assert_eq!((specials.len(), digits.len()), amount, "{password}");
+    ///     }
+    /// }
+    /// ```
+    pub distinct_inserts: bool,
+
     /// ### Amount of uppercase characters
     ///
-    /// Can take either a range like 2-4 or an exact amount like 2. If there are no
-    /// uppercase characters, the [`force_upper`](PasswordSettings#structfield.force_upper)
-    /// flag is turned on automatically to capitalise up to the specified amount of alphabetic characters.
-    /// But if there's at least one uppercase character there won't be any capitalisation
-    /// unless [`force_upper`](PasswordSettings#structfield.force_upper) is turned on manually.
+    /// Can take either a range like 2-4 or an exact amount like 2.
+    /// Whether letters are uppercased to reach it is up to
+    /// [`upper_handling`](PasswordSettings#structfield.upper_handling).
     ///
     /// **Default: 1-2**
     ///
@@ -113,11 +729,9 @@ pub struct PasswordSettings {
 
     /// ### Amount of lowercase characters
     ///
-    /// Can take either a range like 2-4 or an exact amount like 2. If there are no
-    /// lowercase characters, the [`force_lower`](PasswordSettings#structfield.force_lower)
-    /// flag is turned on automatically to decapitalise up to the specified amount of alphabetic characters.
-    /// But if there's at least one lowercase character there won't be any decapitalisation
-    /// unless [`force_lower`](PasswordSettings#structfield.force_lower) is turned on manually.
+    /// Can take either a range like 2-4 or an exact amount like 2.
+    /// Whether letters are lowercased to reach it is up to
+    /// [`lower_handling`](PasswordSettings#structfield.lower_handling).
     ///
     /// **Default: 1-2**
     ///
@@ -134,59 +748,617 @@ pub struct PasswordSettings {
     /// **Default: false**
     pub keep_numbers: bool,
 
-    /// ### Force the specified amount of uppercase characters
+    /// ### Most words made only of numbers in each password
+    ///
+    /// With [`keep_numbers`](PasswordSettings#structfield.keep_numbers), blocks of numbers
+    /// like `2023` are words of their own, so a text full of dates can fill the passwords
+    /// with them. Once a password has this many, the rest are passed over the same way as with
+    /// [`word_weighting`](PasswordSettings#structfield.word_weighting). Having no letters,
+    /// they're never uppercased or lowercased, so the case is adjusted on the other words.
+    ///
+    /// **Default: none, for no limit**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CaseRule, PasswordSettings};
+    /// let journal = "2023 03 14 went to the market 2023 03 15 rained all day \
+    ///                2023 03 16 read a book by the window 2023 03 17 visited grandma";
+    /// let mut settings = PasswordSettings::new();
+    /// settings.keep_numbers = true;
+    /// settings.get_words_from_str(journal);
+    /// settings.max_number_words = Some(1);
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_amount = 2..=2;
+    /// settings.upper_handling = CaseRule::Force;
+    /// settings.pass_amount = 200;
+    ///
+    /// for details in settings.generate_detailed().unwrap().passwords {
+    ///     let number_words = details
+    ///         .word_spans
+    ///         .iter()
+    ///         .filter(|span| details.password[(*span).clone()].chars().all(|c| c.is_ascii_digit()))
+    ///         .count();
+    ///     assert!(number_words <= 1, "{}", details.password);
+    ///     assert!(details.password.chars().filter(char::is_ascii_uppercase).count() >= 2);
+    /// }
+    /// ```
+    pub max_number_words: Option<usize>,
+
+    /// ### When to uppercase letters to reach the amount
+    ///
+    /// See [`CaseRule`] for what each rule does with
+    /// [`upper_amount`](PasswordSettings#structfield.upper_amount).
+    ///
+    /// **Default: [`CaseRule::Auto`]**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CaseRule, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("Alpha Bravo Charlie Delta Echo Foxtrot");
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.lower_amount = 0..=0;
+    /// settings.upper_amount = 5..=5;
+    /// settings.pass_amount = 50;
+    /// let uppercase = |password: &String| password.chars().filter(|c| c.is_uppercase()).count();
+    ///
+    /// // There are already uppercase letters, so nothing is changed.
+    /// settings.upper_handling = CaseRule::Auto;
+    /// for details in settings.generate_detailed().unwrap().passwords {
+    ///     let initials = details.word_spans.len();
+    ///     assert!(uppercase(&details.password) <= initials.max(1), "{}", details.password);
+    /// }
+    ///
+    /// settings.upper_handling = CaseRule::Force;
+    /// for password in settings.generate().unwrap() {
+    ///     assert!(uppercase(&password) >= 5, "{password}");
+    /// }
+    ///
+    /// settings.get_words_from_str("golf hotel india juliett kilo lima");
+    /// settings.upper_handling = CaseRule::Never;
+    /// assert!(settings.generate().unwrap().iter().any(|password| uppercase(password) == 0));
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub upper_handling: CaseRule,
+
+    /// ### When to lowercase letters to reach the amount
+    ///
+    /// See [`CaseRule`] for what each rule does with
+    /// [`lower_amount`](PasswordSettings#structfield.lower_amount).
+    ///
+    /// **Default: [`CaseRule::Auto`]**
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lower_handling: CaseRule,
+
+    /// ### Reject passwords with too few uppercase or lowercase letters
+    ///
+    /// A password can end up with fewer cased letters than
+    /// [`upper_amount`](PasswordSettings#structfield.upper_amount) or
+    /// [`lower_amount`](PasswordSettings#structfield.lower_amount) asked for when there
+    /// aren't enough letters left between the inserted characters. Those passwords are
+    /// generated again, up to [`retry_budget.policy`](RetryBudget::policy) times before
+    /// erroring with [`GenerateError::PolicyRejected`].
+    /// See [`PasswordDetails::requested_upper`] for what counts as too few.
+    ///
+    /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{GenerateError, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("abcdefgh ijklmnop");
+    /// settings.length = 6..=6;
+    /// settings.number_amount = 3..=3;
+    /// settings.special_chars_amount = 2..=2;
+    /// settings.upper_amount = 3..=3;
+    /// settings.lower_amount = 0..=0;
+    ///
+    /// let details = &settings.generate_detailed().unwrap().passwords[0];
+    /// assert_eq!(details.requested_upper, 3);
+    /// assert_eq!(details.achieved_upper, 1);
+    ///
+    /// settings.strict_case = true;
+    /// assert!(matches!(
+    ///     settings.generate(),
+    ///     Err(GenerateError::PolicyRejected { .. })
+    /// ));
+    /// ```
+    pub strict_case: bool,
+
+    /// ### Reject passwords missing some of the numbers or special characters
+    ///
+    /// Numbers and special characters are left out when there's no room for them,
+    /// like when they're more than the length with [`replace`](PasswordSettings#structfield.replace)
+    /// or can't be kept apart with [`no_adjacent_inserts`](PasswordSettings#structfield.no_adjacent_inserts).
+    /// With this set, such passwords are generated again like for the
+    /// [context exclusions](PasswordSettings::add_context_exclusion), and give a
+    /// [`GenerateError::PolicyRejected`] once the [policy retries](RetryBudget::policy) run out.
+    ///
+    /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{GenerateError, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("abc def");
+    /// settings.length = 6..=6;
+    /// settings.replace = true;
+    /// settings.number_amount = 3..=3;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.no_adjacent_inserts = true;
+    ///
+    /// // Only 3 of the 6 characters can be replaced apart from each other.
+    /// let details = &settings.generate_detailed().unwrap().passwords[0];
+    /// assert_eq!(details.dropped_inserts, 0);
+    ///
+    /// settings.number_amount = 4..=4;
+    /// let details = &settings.generate_detailed().unwrap().passwords[0];
+    /// assert_eq!(details.dropped_inserts, 1);
+    ///
+    /// settings.strict_inserts = true;
+    /// assert!(matches!(
+    ///     settings.generate(),
+    ///     Err(GenerateError::PolicyRejected { .. })
+    /// ));
+    /// ```
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub strict_inserts: bool,
+
+    /// ### Never lowercase the first character of a word
+    ///
+    /// Keeps [lowercasing](PasswordSettings#structfield.lower_handling) away from
+    /// word initials, so proper nouns from the source don't end up as `london`.
+    /// It's always the case with [`WordCase::Capitalised`] and [`WordCase::Camel`].
+    /// Fewer characters are lowercased if there aren't enough others to choose from.
+    ///
+    /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CaseRule, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("London Paris Berlin Madrid Vienna Lisbon Prague Dublin");
+    /// settings.lower_handling = CaseRule::Force;
+    /// settings.lower_amount = 40..=40;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.pass_amount = 50;
+    ///
+    /// let initials_kept = |settings: &PasswordSettings| {
+    ///     settings.generate_detailed().unwrap().passwords.iter().all(|details| {
+    ///         details.word_spans.iter().all(|span| {
+    ///             details.password[span.clone()].starts_with(char::is_uppercase)
+    ///         })
+    ///     })
+    /// };
+    /// assert!(!initials_kept(&settings));
+    ///
+    /// settings.protect_word_initials = true;
+    /// assert!(initials_kept(&settings));
+    /// ```
+    pub protect_word_initials: bool,
+
+    /// ### Make sure the password starts with a letter
+    ///
+    /// Some systems reject passwords that don't start with a letter.
+    /// Numbers and special characters are never inserted at the start,
+    /// and words that don't start with a letter (possible with
+    /// [`keep_numbers`](PasswordSettings#structfield.keep_numbers)) are
+    /// skipped when starting the word selection. If none of the words start
+    /// with a letter it counts as an [assembly retry](RetryBudget::assembly).
+    ///
+    /// **Default: false**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.keep_numbers = true;
+    /// settings.must_start_with_letter = true;
+    /// settings.get_words_from_str("2023 was the year 42 things happened in 365 days");
+    /// settings.number_amount = 5..=8;
+    /// settings.special_chars_amount = 5..=8;
+    /// settings.pass_amount = 500;
+    ///
+    /// for password in settings.generate().unwrap() {
+    ///     assert!(password.starts_with(char::is_alphabetic), "{password}");
+    /// }
+    ///
+    /// settings.replace = true;
+    /// for password in settings.generate().unwrap() {
+    ///     assert!(password.starts_with(char::is_alphabetic), "{password}");
+    /// }
+    /// ```
+    pub must_start_with_letter: bool,
+
+    /// ### Skip words that can never fit
+    ///
+    /// Words longer than the whole password (such as URLs or hashes that slipped into
+    /// the source) are passed over when selecting words, instead of ending the password
+    /// early and leaving it to be truncated. If every word is too long, the password
+    /// is truncated as if this was turned off.
+    ///
+    /// **Default: true**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let huge = "x".repeat(100);
+    /// let text = format!("the quick {huge} brown fox {huge} jumps over {huge} the lazy dog {huge}");
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str(&text);
+    /// settings.pass_amount = 200;
+    ///
+    /// let report = settings.generate_detailed().unwrap();
+    /// for details in &report.passwords {
+    ///     assert!(!details.truncated, "{}", details.password);
+    ///     assert!(!details.password.contains("xxx"), "{}", details.password);
+    /// }
+    ///
+    /// settings.skip_oversized_words = false;
+    /// let report = settings.generate_detailed().unwrap();
+    /// assert!(report.passwords.iter().any(|details| details.truncated));
+    /// ```
+    pub skip_oversized_words: bool,
+
+    /// ### Transliterate Unicode words into ASCII
+    ///
+    /// When turned off, words like `straße` or `日本` are kept as they are,
+    /// which adds entropy if you're able to type them. The length of the
+    /// password is always counted in characters, not bytes.
+    ///
+    /// **Default: true**
+    pub transliterate: bool,
+
+    /// ### How the words are selected
+    ///
+    /// **Default: [`WordSelection::Consecutive`]**
+    pub word_selection: WordSelection,
+
+    /// ### Don't wrap around to the first word
     ///
-    /// Gets ignored if [`dont_upper`](PasswordSettings#structfield.dont_upper) is also set.
+    /// With [`WordSelection::Consecutive`], running off the end of the words
+    /// continues from the first one, stitching together unrelated parts of the text.
+    /// Instead, the password is finished there if it's long enough, or else it's retried
+    /// from another word, counting as an [assembly retry](RetryBudget::assembly).
+    /// It still wraps around after running out of them.
     ///
     /// **Default: false**
-    pub force_upper: bool,
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("aaaa bbbb cccc dddd");
+    /// settings.length = 16..=16;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_amount = 0..=0;
+    /// settings.lower_amount = 0..=0;
+    /// settings.pass_amount = 100;
+    ///
+    /// // Only starting from the first word gets there without wrapping around.
+    /// let report = settings.generate_detailed().unwrap();
+    /// assert!(report.passwords.iter().any(|details| details.wrapped));
+    /// for details in &report.passwords {
+    ///     assert_eq!(details.wrapped, details.start_index != 0);
+    /// }
+    ///
+    /// settings.no_wrap = true;
+    /// settings.retry_budget.assembly = 1000;
+    /// for details in settings.generate_detailed().unwrap().passwords {
+    ///     assert!(!details.wrapped);
+    ///     assert_eq!(details.start_index, 0);
+    ///     assert_eq!(details.password, "aaaabbbbccccdddd");
+    /// }
+    /// ```
+    pub no_wrap: bool,
+
+    /// ### How readily each word is taken
+    ///
+    /// Every word selected is kept with the probability given by [`WordWeighting`],
+    /// or else it's passed over for the next one, the same as with
+    /// [`word_selection`](PasswordSettings#structfield.word_selection).
+    /// Words are only passed over for as long as there are others left to try,
+    /// so the length and the [assembly retries](RetryBudget::assembly) work the same.
+    ///
+    /// **Default: [`WordWeighting::Uniform`]**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{PasswordSettings, WordWeighting};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("a to elephant of by hippopotamus in at crocodile an it rhinoceros");
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.pass_amount = 500;
+    ///
+    /// let mean_word_len = |settings: &PasswordSettings| {
+    ///     let (mut total, mut count) = (0, 0);
+    ///     for details in settings.generate_detailed().unwrap().passwords {
+    ///         total += details.word_spans.iter().map(|span| span.len()).sum::<usize>();
+    ///         count += details.word_spans.len();
+    ///     }
+    ///     total as f64 / count as f64
+    /// };
+    ///
+    /// let uniform = mean_word_len(&settings);
+    /// settings.word_weighting = WordWeighting::ByLength;
+    /// let by_length = mean_word_len(&settings);
+    /// assert!(by_length > uniform + 1.0, "{by_length} {uniform}");
+    /// ```
+    pub word_weighting: WordWeighting,
+
+    /// ### How the words from different sources are mixed
+    ///
+    /// Each call to [`get_words_from_path()`](PasswordSettings::get_words_from_path)
+    /// or [`get_words_from_str()`](PasswordSettings::get_words_from_str) adds a new source.
+    ///
+    /// **Default: [`SourceMixing::Concatenate`]**
+    pub source_mixing: SourceMixing,
+
+    /// ### How the words are extracted from text
+    ///
+    /// Used by [`get_words_from_path()`](PasswordSettings::get_words_from_path)
+    /// and [`get_words_from_str()`](PasswordSettings::get_words_from_str).
+    ///
+    /// **Default: [`ExtractionBackend::Legacy`]**
+    pub extraction: ExtractionBackend,
 
-    /// ### Force the specified amount of lowercase characters
+    /// ### Read the files of a directory in the order of their names
     ///
-    /// Gets ignored if [`dont_lower`](PasswordSettings#structfield.dont_lower) is also set.
+    /// The order in which the files are listed depends on the platform and file system,
+    /// so the same directory can give the words in a different order on another machine.
+    /// Sorting the entries of each directory makes the order the same everywhere,
+    /// at the cost of collecting and sorting them before reading.
     ///
     /// **Default: false**
-    pub force_lower: bool,
+    pub sort_entries: bool,
 
-    /// ### Don't uppercase at all to keep original casing
+    /// ### Exclude the words of the file names that words are extracted from
     ///
-    /// Ignores [`force_upper`](PasswordSettings#structfield.force_upper), both manual and automatic.
+    /// Every word of at least 3 characters in the name (without the extension) of the
+    /// path given to [`get_words_from_path()`](PasswordSettings::get_words_from_path),
+    /// and of every file read from it, is added to the
+    /// [context exclusions](PasswordSettings::add_context_exclusion).
+    /// So generating from `github_recovery_codes.txt` never gives a password with `github`.
     ///
     /// **Default: false**
-    pub dont_upper: bool,
+    pub auto_context_exclusions: bool,
 
-    /// ### Don't lowercase at all to keep original casing
+    /// ### Skip a text that was already loaded
     ///
-    /// Ignores [`force_lower`](PasswordSettings#structfield.force_lower), both manual and automatic.
+    /// Loading the same file twice by accident doubles every word of it,
+    /// making the passwords more likely to come from it.
+    /// With this set, a text that's exactly the same as the one of a source
+    /// that's still loaded is skipped, and [`SourceLoad::AlreadyLoaded`] tells
+    /// which source has its words. For a directory, it's all of its files together.
     ///
     /// **Default: false**
-    pub dont_lower: bool,
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{PasswordSettings, SourceLoad};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.dedupe_sources = true;
+    /// let first = settings.get_words_from_str("the quick brown fox").unwrap();
+    ///
+    /// let again = settings.get_words_from_str("the quick brown fox").unwrap();
+    /// assert_eq!(again, SourceLoad::AlreadyLoaded(first.id()));
+    /// assert_eq!(settings.words().len(), 4);
+    /// assert_eq!(settings.sources().len(), 1);
+    /// ```
+    pub dedupe_sources: bool,
+
+    /// ### The most words to keep
+    ///
+    /// Once reached, [`cap_strategy`](PasswordSettings#structfield.cap_strategy)
+    /// decides what happens to the words extracted after that. Words already
+    /// stored are never removed for it, and neither are the ones given to
+    /// [`set_words()`](PasswordSettings::set_words).
+    ///
+    /// **Default: none**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CapStrategy, PasswordSettings};
+    /// let text = |words: std::ops::Range<usize>| {
+    ///     words.map(|i| format!("w{i}")).collect::<Vec<_>>().join(" ")
+    /// };
+    ///
+    /// let mut settings = PasswordSettings::new();
+    /// settings.keep_numbers = true;
+    /// settings.max_words = Some(100);
+    /// settings.get_words_from_str(&text(0..1000));
+    /// assert_eq!(settings.words().len(), 100);
+    /// assert_eq!(settings.dropped_words(), 900);
+    /// assert!(settings.get_words_from_str(&text(0..10)).is_none());
+    ///
+    /// let mut settings = PasswordSettings::new();
+    /// settings.keep_numbers = true;
+    /// settings.max_words = Some(100);
+    /// settings.cap_strategy = CapStrategy::Reservoir;
+    /// settings.get_words_from_str(&text(0..500));
+    /// let second = settings.get_words_from_str(&text(500..1000)).unwrap().id();
+    /// assert_eq!(settings.words().len(), 100);
+    /// assert_eq!(settings.dropped_words(), 900);
+    /// // Drawn from the whole input, with the sources kept track of.
+    /// let from_second = (0..100).filter(|&i| settings.word_source(i) == Some(second)).count();
+    /// assert!((20..=80).contains(&from_second));
+    /// ```
+    pub max_words: Option<usize>,
+
+    /// ### What happens to the words extracted after reaching the maximum
+    ///
+    /// **Default: [`CapStrategy::Stop`]**
+    pub cap_strategy: CapStrategy,
+
+    /// ### Change each word as it's selected
+    ///
+    /// Either a ready-made [`WordTransform`] or a closure set with
+    /// [`set_word_transform()`](PasswordSettings::set_word_transform).
+    /// It isn't serialised.
+    ///
+    /// **Default: none**
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub word_transform: Option<WordTransform>,
+
+    /// ### Leave out unwanted words when extracting them
+    ///
+    /// Either a ready-made [`WordValidator`] or a closure set with
+    /// [`set_word_validator()`](PasswordSettings::set_word_validator).
+    /// The words it rejects aren't stored, and since it's given the words as they're
+    /// extracted, they're already [transliterated](PasswordSettings#structfield.transliterate).
+    /// The words that were stored before it was set are only checked with
+    /// [`retain_valid_words()`](PasswordSettings::retain_valid_words).
+    /// It isn't serialised.
+    ///
+    /// **Default: none**
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub word_validator: Option<WordValidator>,
 
     pub(crate) words: Vec<String>,
+    pub(crate) word_sources: Vec<SourceId>,
+    pub(crate) sources: Vec<Source>,
+    /// Whether words were added since they were last shuffled, which has the generation
+    /// see them shuffled once until [`PasswordSettings::shuffle_words()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    shuffle_pending: bool,
+    /// The amount of words extracted so far, including the ones not kept.
+    #[cfg_attr(feature = "serde", serde(default))]
+    words_seen: usize,
+    /// The amount of words left out or replaced for `max_words`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    words_dropped: usize,
+    /// The fingerprint of the words, kept up to date so that it's serialised along with them,
+    /// and missing from settings serialised before it existed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    words_fingerprint: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) arena: LazyArena,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    shuffle: LazyShuffle,
 }
 
 impl Default for PasswordSettings {
     /// A set of recommended settings for generating a password.
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             capitalise: false,
+            word_case: WordCase::AsIs,
+            separator: String::new(),
             replace: false,
+            max_replaced_fraction: 0.5,
+            leet: false,
+            insert_at_boundaries: false,
+            insert_placement: InsertPlacement::Random,
+            no_adjacent_inserts: false,
+            insert_zone: None,
+            leet_substitutions: default_leet_substitutions(),
             randomise: false,
             pass_amount: 1,
-            reset_amount: 10,
+            consistent_batch: false,
+            unique: false,
+            seed: None,
+            retry_budget: RetryBudget::default(),
+            on_exhausted: ExhaustedBehaviour::TruncateAtWord,
+            batch_retry: BatchRetryMode::PerPassword,
             length: 24..=30,
+            length_unit: LengthUnit::Chars,
+            length_mode: LengthMode::Units,
+            continue_probability: 0.8,
             number_amount: 1..=2,
             special_chars_amount: 1..=2,
             special_chars: String::from("^!(-_=)$<[@.#]>%{~,+}&*"),
+            special_sequences: Vec::new(),
+            special_groups: Vec::new(),
+            context_exclusions: Vec::new(),
+            capitalise_exceptions: Vec::new(),
+            distinct_inserts: false,
             upper_amount: 1..=2,
             lower_amount: 1..=2,
             keep_numbers: false,
-            force_upper: false,
-            force_lower: false,
-            dont_upper: false,
-            dont_lower: false,
+            max_number_words: None,
+            upper_handling: CaseRule::Auto,
+            lower_handling: CaseRule::Auto,
+            strict_case: false,
+            strict_inserts: false,
+            protect_word_initials: false,
+            must_start_with_letter: false,
+            skip_oversized_words: true,
+            transliterate: true,
+            word_selection: WordSelection::Consecutive,
+            no_wrap: false,
+            word_weighting: WordWeighting::Uniform,
+            source_mixing: SourceMixing::Concatenate,
+            extraction: ExtractionBackend::Legacy,
+            sort_entries: false,
+            auto_context_exclusions: false,
+            dedupe_sources: false,
+            max_words: None,
+            cap_strategy: CapStrategy::Stop,
+            word_transform: None,
+            word_validator: None,
             words: Vec::new(),
+            word_sources: Vec::new(),
+            sources: Vec::new(),
+            shuffle_pending: false,
+            words_seen: 0,
+            words_dropped: 0,
+            words_fingerprint: Some(fingerprint(&[])),
+            arena: LazyArena::default(),
+            shuffle: LazyShuffle::default(),
+        }
+    }
+}
+
+impl From<Lexicon> for PasswordSettings {
+    /// The [default settings](PasswordSettings::default) with the words of the [`Lexicon`]
+    /// moved in as they are, like with [`set_words()`](PasswordSettings::set_words),
+    /// under a single [`Source`] labelled with its [name](Lexicon::name).
+    ///
+    /// Nothing else carries over, so the words aren't transliterated or shuffled
+    /// even if the [`Lexicon`] was set to do that for the words it extracts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{Lexicon, PasswordSettings, Split};
+    /// let text = "ärger über straße квас 北京 hund";
+    /// let mut lexicon = Lexicon::new("unicode", Split::UnicodeWords);
+    /// lexicon.extract_words(text, |_| true);
+    /// let words = lexicon.words().to_vec();
+    /// assert!(words.contains(&String::from("квас")));
+    ///
+    /// let settings = PasswordSettings::from(lexicon);
+    /// assert_eq!(settings.words(), words);
+    /// assert_eq!(settings.sources()[0].label, "unicode");
+    /// assert_eq!(settings.pass_amount, PasswordSettings::default().pass_amount);
+    ///
+    /// let lexicon = Lexicon::try_from(&settings).unwrap();
+    /// assert_eq!(lexicon.words(), words);
+    /// assert_eq!(PasswordSettings::from(lexicon).words(), words);
+    /// ```
+    fn from(lexicon: Lexicon) -> Self {
+        let mut settings = Self::new();
+        if let Some(id) = settings.set_words(lexicon.words) {
+            settings.set_source_label(id, lexicon.name);
         }
+        settings
     }
 }
 
@@ -212,192 +1384,4063 @@ impl PasswordSettings {
         &self.special_chars
     }
 
-    /// Extract words from file or directory with text files.
+    /// ### The special sequences to insert
     ///
-    /// In case of a directory, it recursively parses every file inside it while
-    /// following links and ignoring non-plaintext files.
+    /// Strings like `!!` that are inserted whole, picked from the same pool as
+    /// the [special characters](PasswordSettings::set_special_chars), so each one
+    /// counts as a single special character towards
+    /// [`special_chars_amount`](PasswordSettings#structfield.special_chars_amount).
+    /// Empty sequences are ignored and non-ASCII characters are not supported and will error.
     ///
-    /// In case no words were extracted nothing is added and no error is given.
+    /// The length of the password stays within bounds,
+    /// no matter how long the inserted sequences are.
     ///
-    /// Accepts UTF-8 characters, but translates them to ASCII for use in the password.
-    /// So if a word in another language is encountered, it will be transformed into a
-    /// kind of phonetic spelling in ASCII, and if an emoji is encountered, it will be
-    /// translated into its meaning, for example, :D would become 'grinning'.
+    /// **Default: none**
     ///
-    /// # Errors:
+    /// # Example
     ///
-    /// This method will return an IO error in the following situations,
-    /// but is not limited to just these cases:
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.set_special_chars("#").unwrap();
+    /// settings.set_special_sequences(&["!!", "$$$"]).unwrap();
+    /// settings.special_chars_amount = 3..=3;
+    /// settings.number_amount = 0..=0;
+    /// settings.length = 12..=16;
+    /// settings.pass_amount = 50;
     ///
-    /// - `path` does not exist.
-    /// - The user lacks permissions to perform metadata call on path.
-    /// - The process lacks permissions to view the contents.
-    pub fn get_words_from_path(&mut self, path: impl AsRef<Path>) -> std::io::Result<()> {
-        let md = metadata(&path)?;
-        let mut text = String::new();
+    /// for password in settings.generate().unwrap() {
+    ///     assert!((12..=16).contains(&password.len()));
+    ///     let specials = password.chars().filter(|c| "#!$".contains(*c)).count();
+    ///     assert!((3..=9).contains(&specials));
+    /// }
+    ///
+    /// settings.replace = true;
+    /// for password in settings.generate().unwrap() {
+    ///     assert!((12..=16).contains(&password.len()));
+    /// }
+    ///
+    /// assert!(settings.set_special_sequences(&["€€"]).is_err());
+    /// ```
+    pub fn set_special_sequences(
+        &mut self,
+        sequences: &[&str],
+    ) -> Result<(), NonAsciiSpecialCharsError> {
+        ensure!(
+            sequences.iter().all(|s| s.is_ascii()),
+            NonAsciiSpecialCharsSnafu
+        );
 
-        if md.is_file() {
-            text = fs::read_to_string(&path)?;
-        } else if md.is_dir() {
-            get_text_from_dir(&path, &mut text)?;
+        self.special_sequences = sequences
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect();
+        Ok(())
+    }
+
+    pub fn get_special_sequences(&self) -> &[String] {
+        &self.special_sequences
+    }
+
+    /// ### Add a named group of special characters with its own amount
+    ///
+    /// Every password gets an amount within `amount` of the characters in `chars`,
+    /// rolled separately for each group, on top of the
+    /// [special characters](PasswordSettings::set_special_chars), which act as a
+    /// default group with [`special_chars_amount`](PasswordSettings#structfield.special_chars_amount).
+    /// Useful for policies that ask for, say, a punctuation mark and a symbol.
+    /// A group with the same name is replaced.
+    ///
+    /// To be sure every group gets its amount, generating errors with
+    /// [`GenerateError::GroupsExceedLength`] when the maximum amounts of all the
+    /// numbers and special characters together don't fit the minimum length.
+    ///
+    /// Non-ASCII characters are not supported and will error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.add_special_group("punct", ".,;:", 1..=2).unwrap();
+    /// settings.add_special_group("symbols", "#$%&", 1..=1).unwrap();
+    /// settings.pass_amount = 200;
+    ///
+    /// let count = |password: &str, chars: &str| password.chars().filter(|c| chars.contains(*c)).count();
+    /// for password in settings.generate().unwrap() {
+    ///     assert!((1..=2).contains(&count(&password, ".,;:")), "{password}");
+    ///     assert_eq!(count(&password, "#$%&"), 1, "{password}");
+    /// }
+    ///
+    /// settings.length = 3..=3;
+    /// assert!(settings.generate().is_err());
+    /// assert!(settings.add_special_group("currency", "€£", 1..=1).is_err());
+    /// ```
+    pub fn add_special_group(
+        &mut self,
+        name: impl Into<String>,
+        chars: &str,
+        amount: RangeInclusive<usize>,
+    ) -> Result<(), NonAsciiSpecialCharsError> {
+        ensure!(chars.is_ascii(), NonAsciiSpecialCharsSnafu);
+
+        let group = SpecialGroup {
+            name: name.into(),
+            chars: chars.to_owned(),
+            amount,
+        };
+        match self
+            .special_groups
+            .iter_mut()
+            .find(|g| g.name == group.name)
+        {
+            Some(existing) => *existing = group,
+            None => self.special_groups.push(group),
+        }
+        Ok(())
+    }
+
+    /// Remove the special group with the given name, returning whether there was one.
+    pub fn remove_special_group(&mut self, name: &str) -> bool {
+        let len = self.special_groups.len();
+        self.special_groups.retain(|g| g.name != name);
+        self.special_groups.len() != len
+    }
+
+    pub fn get_special_groups(&self) -> &[SpecialGroup] {
+        &self.special_groups
+    }
+
+    /// ### Add a word that must not appear in the passwords
+    ///
+    /// Passwords containing any of these words, ignoring case and the inserted
+    /// numbers and special characters, are generated again, up to
+    /// [`retry_budget.policy`](RetryBudget::policy) times before erroring
+    /// with [`GenerateError::PolicyRejected`]. Useful for keeping the name of a site
+    /// out of its password. Empty words are ignored.
+    ///
+    /// **Default: none**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.add_context_exclusion("Fox");
+    /// settings.pass_amount = 100;
+    /// settings.retry_budget.policy = 1000;
+    ///
+    /// let report = settings.generate_detailed().unwrap();
+    /// assert!(report.passwords.iter().any(|details| details.retries.policy > 0));
+    /// for details in report.passwords {
+    ///     let letters: String = details.password.chars().filter(char::is_ascii_alphabetic).collect();
+    ///     assert!(!letters.to_lowercase().contains("fox"), "{}", details.password);
+    /// }
+    ///
+    /// // Most of them would contain it at first.
+    /// settings.retry_budget.policy = 0;
+    /// assert!(settings.generate().is_err());
+    /// ```
+    pub fn add_context_exclusion(&mut self, word: &str) {
+        let word = word.to_lowercase();
+        if !word.is_empty() && !self.context_exclusions.contains(&word) {
+            self.context_exclusions.push(word);
+        }
+    }
+
+    /// The [context exclusions](PasswordSettings::add_context_exclusion), in lowercase.
+    pub fn get_context_exclusions(&self) -> &[String] {
+        &self.context_exclusions
+    }
+
+    pub fn clear_context_exclusions(&mut self) {
+        self.context_exclusions.clear();
+    }
+
+    /// ### Add a word to write as it is instead of capitalising it
+    ///
+    /// With [`WordCase::Capitalised`] and [`WordCase::Camel`], the words matching it
+    /// while ignoring case are written like the exception instead of having their first
+    /// letter uppercased, which keeps names like `iPhone` from turning into `IPhone`.
+    /// Adding a word that matches an existing exception replaces it.
+    /// Empty words are ignored.
+    ///
+    /// **Default: none**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{PasswordSettings, WordCase};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("iphone ebay market");
+    /// settings.word_case = WordCase::Capitalised;
+    /// settings.add_capitalise_exception("iPhone");
+    /// settings.add_capitalise_exception("EBAY");
+    /// settings.add_capitalise_exception("eBay");
+    /// assert_eq!(settings.get_capitalise_exceptions(), ["iPhone", "eBay"]);
+    ///
+    /// settings.length = 16..=16;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_amount = 0..=0;
+    /// settings.lower_amount = 0..=0;
+    /// settings.pass_amount = 20;
+    /// for password in settings.generate().unwrap() {
+    ///     assert!(["iPhoneeBayMarket", "eBayMarketiPhone", "MarketiPhoneeBay"]
+    ///         .contains(&password.as_str()), "{password}");
+    /// }
+    /// ```
+    pub fn add_capitalise_exception(&mut self, word: &str) {
+        if word.is_empty() {
+            return;
+        }
+        let lowercase = word.to_lowercase();
+        match self
+            .capitalise_exceptions
+            .iter_mut()
+            .find(|exception| exception.to_lowercase() == lowercase)
+        {
+            Some(exception) => *exception = word.to_string(),
+            None => self.capitalise_exceptions.push(word.to_string()),
+        }
+    }
+
+    /// The [capitalise exceptions](PasswordSettings::add_capitalise_exception),
+    /// as they were added.
+    pub fn get_capitalise_exceptions(&self) -> &[String] {
+        &self.capitalise_exceptions
+    }
+
+    pub fn clear_capitalise_exceptions(&mut self) {
+        self.capitalise_exceptions.clear();
+    }
+
+    /// ### Chance of adding another word once the password is long enough
+    ///
+    /// Anything outside of 0.0 to 1.0, including NaN, will error.
+    /// See [`continue_probability`](PasswordSettings#structfield.continue_probability)
+    /// for its effect.
+    ///
+    /// **Default: 0.8**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("abcd efgh ijkl mnop qrst uvwx");
+    /// settings.length = 10..=30;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.pass_amount = 200;
+    ///
+    /// // Three words are the fewest to reach 10 characters.
+    /// settings.set_continue_probability(0.0).unwrap();
+    /// assert!(settings.generate().unwrap().iter().all(|p| p.len() == 12));
+    ///
+    /// // Seven words are the most that fit in 30 characters.
+    /// settings.set_continue_probability(1.0).unwrap();
+    /// assert!(settings.generate().unwrap().iter().all(|p| p.len() == 28));
+    ///
+    /// assert!(settings.set_continue_probability(1.5).is_err());
+    /// assert!(settings.set_continue_probability(f64::NAN).is_err());
+    /// assert_eq!(settings.get_continue_probability(), 1.0);
+    /// ```
+    pub fn set_continue_probability(
+        &mut self,
+        probability: f64,
+    ) -> Result<(), InvalidProbabilityError> {
+        ensure!(
+            (0.0..=1.0).contains(&probability),
+            InvalidProbabilitySnafu { probability }
+        );
+
+        self.continue_probability = probability;
+        Ok(())
+    }
+
+    pub fn get_continue_probability(&self) -> f64 {
+        self.continue_probability
+    }
+
+    /// ### Most of the minimum length that should be replaced
+    ///
+    /// Anything outside of 0.0 to 1.0, including NaN, will error.
+    /// See [`max_replaced_fraction`](PasswordSettings#structfield.max_replaced_fraction)
+    /// for its effect.
+    ///
+    /// **Default: 0.5**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{PasswordSettings, SettingsWarning};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.replace = true;
+    /// settings.length = 10..=20;
+    /// settings.number_amount = 3..=3;
+    /// settings.special_chars_amount = 3..=3;
+    /// assert_eq!(
+    ///     settings.warnings(),
+    ///     [SettingsWarning::ReplaceOverwhelmsWords { inserts: 6, min_len: 10 }]
+    /// );
+    ///
+    /// settings.set_max_replaced_fraction(0.6).unwrap();
+    /// assert!(settings.warnings().is_empty());
+    ///
+    /// assert!(settings.set_max_replaced_fraction(-0.1).is_err());
+    /// assert_eq!(settings.get_max_replaced_fraction(), 0.6);
+    /// ```
+    pub fn set_max_replaced_fraction(
+        &mut self,
+        fraction: f64,
+    ) -> Result<(), InvalidProbabilityError> {
+        ensure!(
+            (0.0..=1.0).contains(&fraction),
+            InvalidProbabilitySnafu {
+                probability: fraction
+            }
+        );
+
+        self.max_replaced_fraction = fraction;
+        Ok(())
+    }
+
+    pub fn get_max_replaced_fraction(&self) -> f64 {
+        self.max_replaced_fraction
+    }
+
+    /// ### The leet substitutions of a letter
+    ///
+    /// Any of the characters of `with` can substitute `letter` with
+    /// [`leet`](PasswordSettings#structfield.leet), picked at random every time.
+    /// A lowercase letter is substituted in either case, and an empty `with` removes
+    /// the substitutions of the letter.
+    ///
+    /// **Default: `a` with `@`, `e` with `3`, `i` with `1` or `!`, `o` with `0`,
+    /// `s` with `$` and `t` with `7`**
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.set_leet_substitution('a', "4@");
+    /// settings.set_leet_substitution('i', "");
+    /// assert_eq!(settings.get_leet_substitutions()[&'a'], "4@");
+    /// assert!(!settings.get_leet_substitutions().contains_key(&'i'));
+    /// ```
+    pub fn set_leet_substitution(&mut self, letter: char, with: &str) {
+        if with.is_empty() {
+            self.leet_substitutions.remove(&letter);
+        } else {
+            self.leet_substitutions.insert(letter, with.to_owned());
+        }
+    }
+
+    pub fn get_leet_substitutions(&self) -> &BTreeMap<char, String> {
+        &self.leet_substitutions
+    }
+
+    /// The case of the words with the overrides of a call, falling back to
+    /// the deprecated `capitalise` when it's left as [`WordCase::AsIs`].
+    #[allow(deprecated)]
+    pub(crate) fn word_case_with(&self, options: &GenerateOptions) -> WordCase {
+        match (options.word_case, options.capitalise) {
+            (Some(case), _) => case,
+            (None, Some(true)) => WordCase::Capitalised,
+            (None, Some(false)) => WordCase::AsIs,
+            (None, None) if self.word_case == WordCase::AsIs && self.capitalise => {
+                WordCase::Capitalised
+            }
+            (None, None) => self.word_case,
+        }
+    }
+
+    /// The [assembly retries](RetryBudget::assembly), which used to be called the reset amount.
+    #[deprecated(note = "use `retry_budget.assembly` instead")]
+    pub fn reset_amount(&self) -> usize {
+        self.retry_budget.assembly
+    }
+
+    /// Set the [assembly retries](RetryBudget::assembly), which used to be called the reset amount.
+    #[deprecated(note = "use `retry_budget.assembly` instead")]
+    pub fn set_reset_amount(&mut self, amount: usize) {
+        self.retry_budget.assembly = amount;
+    }
+
+    /// Whether the uppercase letters are forced, which is now [`CaseRule::Force`].
+    #[deprecated(note = "use `upper_handling` instead")]
+    pub fn force_upper(&self) -> bool {
+        self.upper_handling == CaseRule::Force
+    }
+
+    /// Force the uppercase letters, which is now [`CaseRule::Force`].
+    ///
+    /// It's ignored with [`CaseRule::Never`], and turning it off goes back to [`CaseRule::Auto`].
+    #[deprecated(note = "use `upper_handling` instead")]
+    pub fn set_force_upper(&mut self, force: bool) {
+        self.upper_handling = self.upper_handling.forced(force);
+    }
+
+    /// Whether the lowercase letters are forced, which is now [`CaseRule::Force`].
+    #[deprecated(note = "use `lower_handling` instead")]
+    pub fn force_lower(&self) -> bool {
+        self.lower_handling == CaseRule::Force
+    }
+
+    /// Force the lowercase letters, which is now [`CaseRule::Force`].
+    ///
+    /// It's ignored with [`CaseRule::Never`], and turning it off goes back to [`CaseRule::Auto`].
+    #[deprecated(note = "use `lower_handling` instead")]
+    pub fn set_force_lower(&mut self, force: bool) {
+        self.lower_handling = self.lower_handling.forced(force);
+    }
+
+    /// Whether letters are never uppercased, which is now [`CaseRule::Never`].
+    #[deprecated(note = "use `upper_handling` instead")]
+    pub fn dont_upper(&self) -> bool {
+        self.upper_handling == CaseRule::Never
+    }
+
+    /// Never uppercase letters, which is now [`CaseRule::Never`].
+    ///
+    /// Turning it off goes back to [`CaseRule::Auto`].
+    #[deprecated(note = "use `upper_handling` instead")]
+    pub fn set_dont_upper(&mut self, never: bool) {
+        self.upper_handling = self.upper_handling.never(never);
+    }
+
+    /// Whether letters are never lowercased, which is now [`CaseRule::Never`].
+    #[deprecated(note = "use `lower_handling` instead")]
+    pub fn dont_lower(&self) -> bool {
+        self.lower_handling == CaseRule::Never
+    }
+
+    /// Never lowercase letters, which is now [`CaseRule::Never`].
+    ///
+    /// Turning it off goes back to [`CaseRule::Auto`].
+    #[deprecated(note = "use `lower_handling` instead")]
+    pub fn set_dont_lower(&mut self, never: bool) {
+        self.lower_handling = self.lower_handling.never(never);
+    }
+
+    /// Change each word with a closure as it's selected, without touching the [words](PasswordSettings::words) themselves.
+    ///
+    /// The length of the password is measured after the transform, which is applied before
+    /// [changing the case](PasswordSettings#structfield.word_case) and isn't serialised.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("alpha bravo charlie delta echo foxtrot");
+    /// settings.set_word_transform(|word, _| word.chars().rev().collect());
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.pass_amount = 50;
+    ///
+    /// let reversed = ["ahpla", "ovarb", "eilrahc", "atled", "ohce", "tortxof"];
+    /// for details in settings.generate_detailed().unwrap().passwords {
+    ///     for span in &details.word_spans {
+    ///         let word = details.password[span.clone()].to_lowercase();
+    ///         assert!(reversed.contains(&word.as_str()), "{}", details.password);
+    ///     }
+    /// }
+    /// assert_eq!(settings.words()[0], "alpha");
+    ///
+    /// // Reverse only every third word of the password.
+    /// settings.set_word_transform(|word, context| {
+    ///     if context.index % 3 == 2 {
+    ///         word.chars().rev().collect()
+    ///     } else {
+    ///         word.to_string()
+    ///     }
+    /// });
+    /// ```
+    pub fn set_word_transform(
+        &mut self,
+        transform: impl Fn(&str, WordContext) -> String + Send + Sync + 'static,
+    ) {
+        self.word_transform = Some(WordTransform::Custom(Arc::new(transform)));
+    }
+
+    /// Stop transforming the words, undoing [`set_word_transform()`](PasswordSettings::set_word_transform).
+    pub fn clear_word_transform(&mut self) {
+        self.word_transform = None;
+    }
+
+    /// Leave out the words for which the closure returns `false` when extracting them,
+    /// see [`word_validator`](PasswordSettings#structfield.word_validator).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.set_word_validator(|word| !word.eq_ignore_ascii_case("moist"));
+    /// settings.get_words_from_str("A moist cake, a MOIST towel and a möist sponge");
+    /// assert_eq!(settings.words(), ["A", "cake", "a", "towel", "and", "a", "sponge"]);
+    /// ```
+    pub fn set_word_validator(&mut self, validator: impl Fn(&str) -> bool + Send + Sync + 'static) {
+        self.word_validator = Some(WordValidator::Custom(Arc::new(validator)));
+    }
+
+    /// Stop leaving out words, undoing [`set_word_validator()`](PasswordSettings::set_word_validator).
+    pub fn clear_word_validator(&mut self) {
+        self.word_validator = None;
+    }
+
+    /// The amount of [`distinct_specials()`](PasswordSettings::distinct_specials),
+    /// counted without allocating.
+    pub(crate) fn distinct_special_count(&self) -> usize {
+        let chars = distinct_char_count(&self.special_chars);
+        let sequences = self
+            .special_sequences
+            .iter()
+            .enumerate()
+            .filter(|&(i, sequence)| {
+                let mut sequence_chars = sequence.chars();
+                let single_char = match (sequence_chars.next(), sequence_chars.next()) {
+                    (Some(c), None) => self.special_chars.contains(c),
+                    _ => false,
+                };
+                !single_char && !self.special_sequences[..i].contains(sequence)
+            })
+            .count();
+        chars + sequences
+    }
+
+    /// Every character of the special characters, sequences and groups.
+    pub(crate) fn all_special_chars(&self) -> String {
+        let sequences = self.special_sequences.iter().map(String::as_str);
+        let groups = self.special_groups.iter().map(|g| g.chars.as_str());
+        let mut chars = self.special_chars.clone();
+        chars.extend(sequences.chain(groups));
+        chars
+    }
+
+    /// The special characters and sequences without duplicates.
+    pub(crate) fn distinct_specials(&self) -> Vec<String> {
+        let mut specials: Vec<String> = Vec::new();
+        let chars = self.special_chars.chars().map(String::from);
+        for special in chars.chain(self.special_sequences.iter().cloned()) {
+            if !specials.contains(&special) {
+                specials.push(special);
+            }
+        }
+        specials
+    }
+
+    /// Extract words from file or directory with text files.
+    ///
+    /// In case of a directory, it recursively parses every file inside it while
+    /// following links and ignoring non-plaintext files.
+    ///
+    /// Every call adds a new [`Source`] and returns its id,
+    /// but in case no words were extracted nothing is added and no error is given.
+    /// With [`dedupe_sources`](PasswordSettings#structfield.dedupe_sources),
+    /// the text is skipped if it was already loaded.
+    ///
+    /// Accepts UTF-8 characters, but translates them to ASCII for use in the password.
+    /// So if a word in another language is encountered, it will be transformed into a
+    /// kind of phonetic spelling in ASCII, and if an emoji is encountered, it will be
+    /// translated into its meaning, for example, :D would become 'grinning'.
+    /// Turn off [`transliterate`](PasswordSettings#structfield.transliterate) to keep them as they are.
+    ///
+    /// # Errors:
+    ///
+    /// This method will return an IO error in the following situations,
+    /// but is not limited to just these cases:
+    ///
+    /// - `path` does not exist.
+    /// - The user lacks permissions to perform metadata call on path.
+    /// - The process lacks permissions to view the contents.
+    pub fn get_words_from_path(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> std::io::Result<Option<SourceLoad>> {
+        self.get_words_from_path_with_report(path)
+            .map(|(loaded, _)| loaded)
+    }
+
+    /// Extract words from a file or directory like
+    /// [`get_words_from_path()`](PasswordSettings::get_words_from_path), also returning
+    /// which files were read and which were skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{PasswordSettings, SkipReason};
+    /// # use std::fs;
+    /// let dir = std::env::temp_dir().join(format!("genrepass-report-{}", std::process::id()));
+    /// fs::create_dir_all(&dir).unwrap();
+    /// fs::write(dir.join("text.txt"), "hello world").unwrap();
+    /// fs::write(dir.join("binary"), [0xFF, 0xFE, 0x00, 0x89]).unwrap();
+    ///
+    /// let mut settings = PasswordSettings::new();
+    /// let (_, report) = settings.get_words_from_path_with_report(&dir).unwrap();
+    /// fs::remove_dir_all(&dir).unwrap();
+    ///
+    /// assert_eq!(report.read, 1);
+    /// assert_eq!(report.skipped, [(dir.join("binary"), SkipReason::NotText)]);
+    /// ```
+    pub fn get_words_from_path_with_report(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> std::io::Result<(Option<SourceLoad>, WalkReport)> {
+        let md = metadata(&path)?;
+        let re = self.word_regex();
+
+        // One file at a time, so the whole directory is never in memory at once.
+        let mut added = 0;
+        let mut report = WalkReport::default();
+        let mut hash = Fingerprint::new();
+        if md.is_file() {
+            let text = fs::read_to_string(&path)?;
+            hash.push(&text);
+            report.read = 1;
+            if let Some(id) = self.loaded_source(hash.finish()) {
+                return Ok((Some(SourceLoad::AlreadyLoaded(id)), report));
+            }
+            if self.auto_context_exclusions {
+                self.exclude_name_of(path.as_ref());
+            }
+            added = self.push_words(&text, &re);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                path = %path.as_ref().display(),
+                words_added = added,
+                "extracted words from file"
+            );
+        } else if md.is_dir() {
+            let sorted = self.sort_entries;
+            // The words can't be taken back once pushed, so the texts are hashed first.
+            if self.dedupe_sources {
+                let mut hashed = Fingerprint::new();
+                let walked = for_each_text_in_dir(&path, sorted, &mut |_, text| hashed.push(text))?;
+                if let Some(id) = self.loaded_source(hashed.finish()) {
+                    return Ok((Some(SourceLoad::AlreadyLoaded(id)), walked));
+                }
+            }
+            if self.auto_context_exclusions {
+                self.exclude_name_of(path.as_ref());
+            }
+            report = for_each_text_in_dir(&path, sorted, &mut |file, text| {
+                hash.push(text);
+                let words_added = self.push_words(text, &re);
+                added += words_added;
+                if self.auto_context_exclusions {
+                    self.exclude_name_of(file);
+                }
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    path = %file.display(),
+                    words_added,
+                    "extracted words from file"
+                );
+            })?;
+        } else {
+            unreachable!("Unexpected metadata error");
+        }
+
+        let loaded = self.add_source(added, path.as_ref().display().to_string(), hash.finish());
+        Ok((loaded, report))
+    }
+
+    /// Add the words of the name of the file or directory to the context exclusions.
+    fn exclude_name_of(&mut self, path: &Path) {
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            return;
+        };
+        // Underscores count as part of a word when extracting, but not in file names.
+        for word in extract_words(&stem.replace('_', " "), false) {
+            if word.chars().count() >= 3 {
+                self.add_context_exclusion(&word);
+            }
+        }
+    }
+
+    /// Extract words from a string.
+    ///
+    /// Every call adds a new [`Source`] and returns its id,
+    /// but in case no words were extracted nothing is added and no error is given.
+    /// With [`dedupe_sources`](PasswordSettings#structfield.dedupe_sources),
+    /// the text is skipped if it was already loaded.
+    ///
+    /// Accepts UTF-8 characters, but translates them to ASCII for use in the password.
+    /// So if a word in another language is encountered, it will be transformed into a
+    /// kind of phonetic spelling in ASCII, and if an emoji is encountered, it will be
+    /// translated into its meaning, for example, :D would become 'grinning'.
+    /// Turn off [`transliterate`](PasswordSettings#structfield.transliterate) to keep them as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.transliterate = false;
+    /// settings.length = 10..=16;
+    /// settings.get_words_from_str("Die Straße ist nass, aber die Füße bleiben trocken.");
+    /// settings.get_words_from_str("日本 の 首都 は 東京 です");
+    /// assert!(settings.words().iter().any(|w| w == "Straße"));
+    /// assert!(settings.words().iter().any(|w| w == "東京"));
+    ///
+    /// settings.pass_amount = 200;
+    /// for password in settings.generate().unwrap() {
+    ///     assert!((10..=16).contains(&password.chars().count()));
+    /// }
+    /// ```
+    pub fn get_words_from_str(&mut self, text: &str) -> Option<SourceLoad> {
+        self.extract_words(text, String::new())
+    }
+
+    fn extract_words(&mut self, text: &str, label: String) -> Option<SourceLoad> {
+        let mut hash = Fingerprint::new();
+        hash.push(text);
+        if let Some(id) = self.loaded_source(hash.finish()) {
+            return Some(SourceLoad::AlreadyLoaded(id));
+        }
+        let added = self.push_words(text, &self.word_regex());
+        self.add_source(added, label, hash.finish())
+    }
+
+    /// The source that was loaded from the text with this hash, if it's to be skipped
+    /// with [`dedupe_sources`](PasswordSettings#structfield.dedupe_sources).
+    fn loaded_source(&self, content_hash: u64) -> Option<SourceId> {
+        if !self.dedupe_sources {
+            return None;
+        }
+        self.sources
+            .iter()
+            .position(|source| source.content_hash == Some(content_hash))
+            .map(SourceId)
+    }
+
+    fn word_regex(&self) -> Regex {
+        word_regex(self.keep_numbers)
+    }
+
+    /// Push the words of the text with the configured [`ExtractionBackend`]
+    /// for the next source, returning how many were kept.
+    fn push_words(&mut self, text: &str, re: &Regex) -> usize {
+        let old_len = self.words.len();
+        match &self.extraction {
+            ExtractionBackend::Legacy => {
+                push_legacy_words(text, re, self.transliterate, &mut self.words)
+            }
+            ExtractionBackend::Lexicon {
+                split,
+                deunicode,
+                filter,
+            } => push_split_words(
+                split,
+                *deunicode,
+                text,
+                &mut filter.closure(),
+                &mut self.words,
+            ),
+        }
+        if let Some(validator) = &self.word_validator {
+            let extracted = self.words.split_off(old_len);
+            self.words
+                .extend(extracted.into_iter().filter(|word| validator.accepts(word)));
+        }
+        let kept = self.cap_words_from(old_len);
+        self.words_changed();
+        kept
+    }
+
+    /// Drop what was worked out from the words, which have just changed.
+    fn words_changed(&mut self) {
+        self.arena.invalidate();
+        self.shuffle.invalidate();
+        self.words_fingerprint = Some(fingerprint(&self.words));
+    }
+
+    /// Apply [`max_words`](PasswordSettings#structfield.max_words) to the words pushed
+    /// after `old_len`, giving them the next source and returning how many were kept.
+    fn cap_words_from(&mut self, old_len: usize) -> usize {
+        let id = SourceId(self.sources.len());
+        let added = self.words.len() - old_len;
+        let Some(max) = self.max_words else {
+            self.words_seen += added;
+            self.word_sources.resize(self.words.len(), id);
+            return added;
+        };
+
+        let mut rng = thread_rng();
+        let mut kept = 0;
+        for word in self.words.split_off(old_len) {
+            self.words_seen += 1;
+            let len = self.words.len();
+            match capped_slot(len, self.words_seen, max, self.cap_strategy, &mut rng) {
+                Some(slot) if slot == len => {
+                    self.words.push(word);
+                    self.word_sources.push(id);
+                }
+                Some(slot) => {
+                    self.words[slot] = word;
+                    self.word_sources[slot] = id;
+                    self.words_dropped += 1;
+                }
+                None => {
+                    self.words_dropped += 1;
+                    continue;
+                }
+            }
+            kept += 1;
+        }
+
+        kept
+    }
+
+    /// The amount of extracted words that were left out, or later replaced, to keep to
+    /// [`max_words`](PasswordSettings#structfield.max_words).
+    pub fn dropped_words(&self) -> usize {
+        self.words_dropped
+    }
+
+    /// Add the source that the `added` words were given, if any.
+    fn add_source(&mut self, added: usize, label: String, content_hash: u64) -> Option<SourceLoad> {
+        if added == 0 {
+            return None;
+        }
+
+        let id = SourceId(self.sources.len());
+        self.sources.push(Source {
+            label,
+            weight: 1,
+            content_hash: Some(content_hash),
+        });
+        self.shuffle_pending = true;
+
+        Some(SourceLoad::Added(id))
+    }
+
+    /// Shuffle the stored words, keeping track of their sources.
+    ///
+    /// With [`randomise`](PasswordSettings#structfield.randomise), the words are
+    /// then used in this order until more words are added.
+    pub fn shuffle_words(&mut self) {
+        self.shuffle_pending = false;
+        let mut pairs: Vec<(String, SourceId)> = take(&mut self.words)
+            .into_iter()
+            .zip(take(&mut self.word_sources))
+            .collect();
+        pairs.shuffle(&mut thread_rng());
+        (self.words, self.word_sources) = pairs.into_iter().unzip();
+        self.words_changed();
+    }
+
+    /// Get a reference to the sources the words were extracted from.
+    pub fn sources(&self) -> &[Source] {
+        &self.sources
+    }
+
+    /// Change the label of a source.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` doesn't belong to these settings.
+    pub fn set_source_label(&mut self, id: SourceId, label: impl Into<String>) {
+        self.sources[id.0].label = label.into();
+    }
+
+    /// Change how likely the words of a source are to be selected.
+    ///
+    /// With [`WordSelection::Uniform`] a source is picked according to the weights
+    /// and then a word from it. With [`WordSelection::Consecutive`] only the first
+    /// word of each password is picked that way, so it's just an approximation.
+    /// A weight of zero means the source is only used if the consecutive words reach it.
+    ///
+    /// **Default: 1**
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` doesn't belong to these settings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CaseRule, PasswordSettings, WordSelection};
+    /// let mut settings = PasswordSettings::new();
+    /// let curated = settings.get_words_from_str("alpha bravo charlie delta").unwrap().id();
+    /// settings.get_words_from_str("xray yankee zulu whiskey").unwrap();
+    /// settings.set_source_weight(curated, 3);
+    /// settings.word_selection = WordSelection::Uniform;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_handling = CaseRule::Never;
+    /// settings.lower_handling = CaseRule::Never;
+    /// settings.pass_amount = 2000;
+    ///
+    /// let (mut curated, mut bulk) = (0, 0);
+    /// for password in settings.generate().unwrap() {
+    ///     for word in ["alpha", "bravo", "charlie", "delta"] {
+    ///         curated += password.matches(word).count();
+    ///     }
+    ///     for word in ["xray", "yankee", "zulu", "whiskey"] {
+    ///         bulk += password.matches(word).count();
+    ///     }
+    /// }
+    ///
+    /// let ratio = curated as f64 / (curated + bulk) as f64;
+    /// assert!((0.72..=0.78).contains(&ratio), "{ratio}");
+    /// ```
+    pub fn set_source_weight(&mut self, id: SourceId, weight: u32) {
+        self.sources[id.0].weight = weight;
+    }
+
+    /// Get the source of the word at index.
+    pub fn word_source(&self, index: usize) -> Option<SourceId> {
+        self.word_sources.get(index).copied()
+    }
+
+    /// Get the words in the order they're used for generation,
+    /// according to [`source_mixing`](PasswordSettings#structfield.source_mixing).
+    pub fn mixed_words(&self) -> Vec<&str> {
+        self.mixed_indices(&mut thread_rng(), self.seed)
+            .into_iter()
+            .map(|i| self.words[i].as_str())
+            .collect()
+    }
+
+    /// Whether the generation has to shuffle its view of the words.
+    pub(crate) fn shuffles_view(&self) -> bool {
+        self.randomise && self.shuffle_pending
+    }
+
+    /// The words as they're shuffled for [`randomise`](PasswordSettings#structfield.randomise),
+    /// which is done once and kept until the words change.
+    pub(crate) fn shuffled_words(&self, seed: Option<u64>) -> Cow<'_, ShuffledWords> {
+        self.shuffle.get(&self.words, &self.word_sources, seed)
+    }
+
+    /// The order of the words for generation, shuffled with `seed`, or with `rng` if there's none.
+    fn mixed_indices<R: Rng + ?Sized>(&self, rng: &mut R, seed: Option<u64>) -> Vec<usize> {
+        // Shuffled on every call anyway, so there's no order to keep.
+        let mut indices: Vec<usize> =
+            if self.shuffles_view() && self.source_mixing != SourceMixing::Shuffled {
+                self.shuffled_words(seed).order.clone()
+            } else {
+                (0..self.words.len()).collect()
+            };
+
+        match self.source_mixing {
+            SourceMixing::Concatenate => indices,
+            SourceMixing::RoundRobin => {
+                let mut per_source = vec![Vec::new(); self.sources.len()];
+                for i in indices {
+                    per_source[self.word_sources[i].0].push(i);
+                }
+
+                let mut per_source: Vec<_> = per_source.into_iter().map(Vec::into_iter).collect();
+                let mut mixed = Vec::with_capacity(self.words.len());
+                while mixed.len() < self.words.len() {
+                    mixed.extend(per_source.iter_mut().filter_map(Iterator::next));
+                }
+
+                mixed
+            }
+            SourceMixing::Shuffled => {
+                match seed {
+                    Some(seed) => indices.shuffle(&mut StdRng::seed_from_u64(seed)),
+                    None => indices.shuffle(rng),
+                }
+                indices
+            }
+        }
+    }
+
+    /// Get a reference to the vector of words.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// A fingerprint of the words in their stored order, to check that two sets of
+    /// settings or a [`Lexicon`] have exactly the same words, like before
+    /// generating with a [`seed`](PasswordSettings#structfield.seed) on another machine.
+    ///
+    /// It changes with any change to the words, and it's the same on every platform
+    /// and version of this crate. It's serialised along with the settings, and
+    /// [`warnings()`](PasswordSettings::warnings) reports when it doesn't match the words
+    /// they were deserialised with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{Lexicon, PasswordSettings, Split};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox");
+    /// let fingerprint = settings.words_fingerprint();
+    ///
+    /// let mut lexicon = Lexicon::new("fox", Split::UnicodeWords);
+    /// lexicon.extract_words("the quick brown fox", |_| true);
+    /// assert_eq!(lexicon.words_fingerprint(), fingerprint);
+    ///
+    /// settings.remove_word_at(3);
+    /// assert_ne!(settings.words_fingerprint(), fingerprint);
+    /// settings.get_words_from_str("fox");
+    /// assert_eq!(settings.words_fingerprint(), fingerprint);
+    /// ```
+    pub fn words_fingerprint(&self) -> u64 {
+        fingerprint(&self.words)
+    }
+
+    /// Summary numbers about the words, see [`WordStats`].
+    ///
+    /// Counting the [distinct](WordStats::distinct) words is optional
+    /// as it takes memory for every word.
+    pub fn word_stats(&self, distinct: bool) -> WordStats {
+        WordStats::of(&self.words, distinct)
+    }
+
+    /// Pick a copy of `n` different words at random, without changing the stored order,
+    /// like [`Lexicon::sample_words()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// # use rand::{rngs::StdRng, SeedableRng};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("one two three four five six seven eight nine ten");
+    ///
+    /// assert_eq!(settings.sample_words(3, &mut rand::thread_rng()).len(), 3);
+    /// assert_eq!(settings.sample_words(11, &mut rand::thread_rng()).len(), 10);
+    ///
+    /// let seeded = |seed| settings.sample_words(5, &mut StdRng::seed_from_u64(seed));
+    /// assert_eq!(seeded(7), seeded(7));
+    /// ```
+    pub fn sample_words<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Vec<String> {
+        index::sample(rng, self.words.len(), n.min(self.words.len()))
+            .into_iter()
+            .map(|i| self.words[i].clone())
+            .collect()
+    }
+
+    /// Get a copy of up to `limit` words starting at the index `offset`,
+    /// for showing a page of them at a time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("one two three four five");
+    ///
+    /// assert_eq!(settings.words_page(0, 2), ["one", "two"]);
+    /// assert_eq!(settings.words_page(3, 10), ["four", "five"]);
+    /// assert_eq!(settings.words_page(4, 0), Vec::<String>::new());
+    /// assert_eq!(settings.words_page(5, 10), Vec::<String>::new());
+    /// assert_eq!(settings.words_page(usize::MAX, usize::MAX), Vec::<String>::new());
+    /// ```
+    pub fn words_page(&self, offset: usize, limit: usize) -> Vec<String> {
+        words_page(&self.words, offset, limit)
+    }
+
+    /// Search for up to `limit` words that start with `prefix`, getting their index
+    /// (to use with [`remove_word_at()`](PasswordSettings::remove_word_at) for example)
+    /// along with a copy of the word.
+    ///
+    /// The search is case-sensitive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.transliterate = false;
+    /// settings.get_words_from_str("straße strom über übel street Übung");
+    ///
+    /// assert_eq!(
+    ///     settings.find_words("str", 10),
+    ///     [(0, "straße".to_string()), (1, "strom".to_string()), (4, "street".to_string())]
+    /// );
+    /// assert_eq!(settings.find_words("str", 1), [(0, "straße".to_string())]);
+    /// assert_eq!(
+    ///     settings.find_words("üb", 10),
+    ///     [(2, "über".to_string()), (3, "übel".to_string())]
+    /// );
+    /// assert_eq!(settings.find_words("straß", 10), [(0, "straße".to_string())]);
+    /// assert!(settings.find_words("x", 10).is_empty());
+    /// assert_eq!(settings.find_words("", 10).len(), 6);
+    /// ```
+    pub fn find_words(&self, prefix: &str, limit: usize) -> Vec<(usize, String)> {
+        find_words(&self.words, prefix, limit)
+    }
+
+    /// Get a copy of the words, for storing them and restoring them
+    /// later with [`set_words()`](PasswordSettings::set_words).
+    pub fn clone_words(&self) -> Vec<String> {
+        self.words.clone()
+    }
+
+    /// Replace all the words with these ones, as they are, under a single new [`Source`].
+    ///
+    /// Unlike [`get_words_from_str()`](PasswordSettings::get_words_from_str),
+    /// nothing is extracted, transliterated or shuffled, so `set_words(clone_words())`
+    /// always gives back the same words in the same order. Only empty strings are
+    /// left out, which extracted words never are. Returns `None` when no words are left.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let words: Vec<String> = ["don't", "route66", "l'été", "Straße", "42"]
+    ///     .map(String::from)
+    ///     .into();
+    /// let mut settings = PasswordSettings::new();
+    /// settings.randomise = true;
+    /// settings.set_words(words.clone());
+    /// assert_eq!(settings.clone_words(), words);
+    ///
+    /// let stored = settings.clone_words();
+    /// settings = PasswordSettings::new();
+    /// settings.set_words(stored);
+    /// assert_eq!(settings.words(), words);
+    /// ```
+    pub fn set_words(&mut self, mut words: Vec<String>) -> Option<SourceId> {
+        self.clear_words();
+        words.retain(|word| !word.is_empty());
+        if words.is_empty() {
+            return None;
+        }
+
+        self.words = words;
+        let id = SourceId(0);
+        self.word_sources = vec![id; self.words.len()];
+        self.sources.push(Source {
+            label: String::new(),
+            weight: 1,
+            content_hash: None,
+        });
+        self.words_changed();
+        Some(id)
+    }
+
+    /// Give the words of settings saved before the sources existed a source of their own.
+    #[cfg(feature = "serde")]
+    pub(crate) fn source_saved_words(&mut self) {
+        if self.words.is_empty() || !self.word_sources.is_empty() {
+            return;
+        }
+
+        let id = SourceId(self.sources.len());
+        self.word_sources = vec![id; self.words.len()];
+        self.sources.push(Source {
+            label: String::new(),
+            weight: 1,
+            content_hash: None,
+        });
+    }
+
+    /// Clear the vector of words.
+    pub fn clear_words(&mut self) {
+        self.shuffle_pending = false;
+        self.words_seen = 0;
+        self.words_dropped = 0;
+        self.words.clear();
+        self.word_sources.clear();
+        self.sources.clear();
+        self.words_changed();
+    }
+
+    /// Remove a word at index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_word_at(&mut self, index: usize) {
+        self.words.remove(index);
+        self.word_sources.remove(index);
+        self.words_changed();
+    }
+
+    /// Remove the words that are the same as an earlier one when ignoring case,
+    /// and also when [transliterated](deunicode()) into ASCII if `transliterate` is set,
+    /// keeping the first one as it is. Returns how many were removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.transliterate = false;
+    /// settings.get_words_from_str("Café cafe the The CAFÉ Straße strasse naïve");
+    ///
+    /// assert_eq!(settings.dedupe_words_normalised(false), 2);
+    /// assert_eq!(settings.words(), ["Café", "cafe", "the", "Straße", "strasse", "naïve"]);
+    ///
+    /// assert_eq!(settings.dedupe_words_normalised(true), 2);
+    /// assert_eq!(settings.words(), ["Café", "the", "Straße", "naïve"]);
+    /// ```
+    pub fn dedupe_words_normalised(&mut self, transliterate: bool) -> usize {
+        let keep = first_occurrences(&self.words, transliterate);
+        let removed = keep.iter().filter(|&&keep| !keep).count();
+        if removed > 0 {
+            let mut keep_word = keep.iter();
+            self.words.retain(|_| *keep_word.next().unwrap());
+            let mut keep_source = keep.iter();
+            self.word_sources.retain(|_| *keep_source.next().unwrap());
+            self.words_changed();
+        }
+
+        removed
+    }
+
+    /// Remove the stored words that the [`word_validator`](PasswordSettings#structfield.word_validator)
+    /// rejects, like the ones stored before it was set or restored with
+    /// [`set_words()`](PasswordSettings::set_words). Returns how many were removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("keep drop keep DROP");
+    ///
+    /// settings.set_word_validator(|word| word.to_lowercase() != "drop");
+    /// assert_eq!(settings.words().len(), 4);
+    /// assert_eq!(settings.retain_valid_words(), 2);
+    /// assert_eq!(settings.words(), ["keep", "keep"]);
+    /// ```
+    pub fn retain_valid_words(&mut self) -> usize {
+        let Some(validator) = &self.word_validator else {
+            return 0;
+        };
+        let keep: Vec<bool> = self
+            .words
+            .iter()
+            .map(|word| validator.accepts(word))
+            .collect();
+        let removed = keep.iter().filter(|&&keep| !keep).count();
+        if removed > 0 {
+            let mut keep_word = keep.iter();
+            self.words.retain(|_| *keep_word.next().unwrap());
+            let mut keep_source = keep.iter();
+            self.word_sources.retain(|_| *keep_source.next().unwrap());
+            self.words_changed();
+        }
+
+        removed
+    }
+
+    /// Check for combinations of settings that work but are probably mistakes.
+    ///
+    /// None of these prevent generation, so they're meant to be shown as advisory messages.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CaseRule, PasswordSettings, SettingsWarning, WordCase};
+    /// let mut settings = PasswordSettings::new();
+    /// assert!(settings.warnings().is_empty());
+    ///
+    /// settings.word_case = WordCase::Capitalised;
+    /// settings.upper_handling = CaseRule::Never;
+    /// assert_eq!(settings.warnings(), [SettingsWarning::CapitaliseWithDontUpper]);
+    ///
+    /// let mut settings = PasswordSettings::new();
+    /// settings.length = 4..=6;
+    /// settings.upper_amount = 2..=8;
+    /// assert_eq!(
+    ///     settings.warnings(),
+    ///     [SettingsWarning::UpperAmountExceedsLength { upper: 8, length: 6 }]
+    /// );
+    ///
+    /// let mut settings = PasswordSettings::new();
+    /// settings.set_special_chars("!?!.?").unwrap();
+    /// assert_eq!(
+    ///     settings.warnings(),
+    ///     [SettingsWarning::DuplicateSpecialChars { duplicates: String::from("!?") }]
+    /// );
+    ///
+    /// let mut settings = PasswordSettings::new();
+    /// settings.retry_budget.assembly = 0;
+    /// assert_eq!(settings.warnings(), [SettingsWarning::ZeroResetAmount]);
+    /// ```
+    pub fn warnings(&self) -> Vec<SettingsWarning> {
+        let mut warnings = Vec::new();
+
+        let case = self.word_case_with(&GenerateOptions::default());
+        if matches!(case, WordCase::Capitalised | WordCase::Camel)
+            && self.upper_handling == CaseRule::Never
+        {
+            warnings.push(SettingsWarning::CapitaliseWithDontUpper);
+        }
+
+        if self.length_mode == LengthMode::Units && self.upper_amount.end() > self.length.end() {
+            warnings.push(SettingsWarning::UpperAmountExceedsLength {
+                upper: *self.upper_amount.end(),
+                length: *self.length.end(),
+            });
+        }
+
+        let mut duplicates = String::new();
+        for (i, c) in self.special_chars.char_indices() {
+            if self.special_chars[..i].contains(c) && !duplicates.contains(c) {
+                duplicates.push(c);
+            }
+        }
+        if !duplicates.is_empty() {
+            warnings.push(SettingsWarning::DuplicateSpecialChars { duplicates });
+        }
+
+        if self.retry_budget.assembly == 0 {
+            warnings.push(SettingsWarning::ZeroResetAmount);
+        }
+
+        let current = self.words_fingerprint();
+        if let Some(saved) = self.words_fingerprint.filter(|&saved| saved != current) {
+            warnings.push(SettingsWarning::WordsFingerprintMismatch { saved, current });
+        }
+
+        if self.insert_placement == InsertPlacement::Prefix && self.must_start_with_letter {
+            warnings.push(SettingsWarning::PrefixAfterFirstLetter);
+        }
+
+        if self.replace && self.length_mode == LengthMode::Units {
+            let inserts = self.number_amount.end()
+                + self.special_chars_amount.end() * self.longest_special()
+                + self
+                    .special_groups
+                    .iter()
+                    .filter(|g| !g.chars.is_empty())
+                    .map(|g| g.amount.end())
+                    .sum::<usize>();
+            let min_len = *self.length.start();
+            if inserts as f64 > self.max_replaced_fraction * min_len as f64 {
+                warnings.push(SettingsWarning::ReplaceOverwhelmsWords { inserts, min_len });
+            }
+        }
+
+        warnings
+    }
+
+    /// The range of lengths, in [`length_unit`](PasswordSettings#structfield.length_unit),
+    /// that every generated password falls within.
+    ///
+    /// It's the same as [`length`](PasswordSettings#structfield.length) when the passwords
+    /// that run out of [assembly retries](RetryBudget::assembly) are truncated to fit it
+    /// or error, but with [`ExhaustedBehaviour::TruncateAtWord`] they can fall short of
+    /// the minimum. The exception are letters that change length with their case,
+    /// like `ß` becoming `SS`, which can only happen when
+    /// [`transliterate`](PasswordSettings#structfield.transliterate) is turned off.
+    ///
+    /// There's no limit with [`LengthMode::Words`].
+    pub fn effective_length(&self) -> RangeInclusive<usize> {
+        match self.length_mode {
+            LengthMode::Units if self.on_exhausted == ExhaustedBehaviour::TruncateAtWord => {
+                0..=*self.length.end()
+            }
+            LengthMode::Units => self.length.clone(),
+            LengthMode::Words(_) => 0..=usize::MAX,
+        }
+    }
+
+    /// Check that generation would succeed, without generating anything.
+    ///
+    /// It makes the same checks the generation methods make before generating, and
+    /// doesn't allocate, so it's cheap enough to call on every frame of a GUI.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let configs: [fn(&mut PasswordSettings); 8] = [
+    ///     |_| {},
+    ///     |s| s.clear_words(),
+    ///     |s| s.length = 20..=10,
+    ///     |s| s.upper_amount = 3..=2,
+    ///     |s| {
+    ///         s.distinct_inserts = true;
+    ///         s.number_amount = 11..=11;
+    ///     },
+    ///     |s| {
+    ///         s.distinct_inserts = true;
+    ///         s.set_special_chars("!!??").unwrap();
+    ///         s.special_chars_amount = 3..=3;
+    ///     },
+    ///     |s| s.add_special_group("brackets", "()[]", 20..=20).unwrap(),
+    ///     |s| {
+    ///         s.set_special_chars("").unwrap();
+    ///         s.special_chars_amount = 30..=30;
+    ///     },
+    /// ];
+    ///
+    /// for config in configs {
+    ///     let mut settings = PasswordSettings::new();
+    ///     settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    ///     config(&mut settings);
+    ///
+    ///     let checked = settings.can_generate();
+    ///     assert_eq!(checked.is_ok(), settings.generate().is_ok(), "{checked:?}");
+    ///     assert_eq!(checked.is_ok(), settings.iter().is_ok(), "{checked:?}");
+    /// }
+    /// ```
+    pub fn can_generate(&self) -> Result<(), GenerateError> {
+        self.validate(&GenerateOptions::default())
+    }
+
+    /// Check that generation would succeed and describe what it would produce,
+    /// without generating any passwords.
+    ///
+    /// Makes the same checks as [`can_generate()`](PasswordSettings::can_generate).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let configs: [fn(&mut PasswordSettings); 5] = [
+    ///     |_| {},
+    ///     |s| s.clear_words(),
+    ///     |s| s.length = 20..=10,
+    ///     |s| {
+    ///         s.distinct_inserts = true;
+    ///         s.number_amount = 11..=11;
+    ///     },
+    ///     |s| {
+    ///         s.length = 8..=8;
+    ///         s.number_amount = 4..=6;
+    ///     },
+    /// ];
+    ///
+    /// for config in configs {
+    ///     let mut settings = PasswordSettings::new();
+    ///     settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    ///     config(&mut settings);
+    ///
+    ///     let report = settings.dry_run();
+    ///     assert_eq!(report.is_ok(), settings.generate().is_ok(), "{report:?}");
+    ///
+    ///     if let Ok(report) = report {
+    ///         assert_eq!(report.words, 9);
+    ///         assert!(report.inserts.end() <= settings.length.start());
+    ///         assert!(report.entropy > 0.0);
+    ///     }
+    /// }
+    /// ```
+    pub fn dry_run(&self) -> Result<DryRunReport, GenerateError> {
+        self.can_generate()?;
+
+        let specials = self.special_amount();
+        // What doesn't fit is dropped when generating.
+        let limit = self.insert_limit();
+        let inserts = (self.number_amount.start() + specials.start()).min(limit)
+            ..=(self.number_amount.end() + specials.end()).min(limit);
+
+        Ok(DryRunReport {
+            words: self.words.len(),
+            effective_length: self.effective_length(),
+            inserts,
+            entropy: self.search_space_bits(),
+            warnings: self.warnings(),
+        })
+    }
+
+    /// Check a password against what these settings promise,
+    /// like after it was edited by hand.
+    ///
+    /// The length has to be within [`length`](PasswordSettings#structfield.length),
+    /// unless it's measured in [words](LengthMode::Words), and the amounts of digits and special characters within the ones to insert,
+    /// counting every character of a [sequence](PasswordSettings::set_special_sequences)
+    /// but none of the [`separator`](PasswordSettings#structfield.separator).
+    /// With [`keep_numbers`](PasswordSettings#structfield.keep_numbers) there's no upper limit
+    /// to the digits, and if more numbers and special characters can be rolled than fit the length
+    /// there's no lower limit to either, since some are dropped when generating.
+    ///
+    /// Only the lower end of the amounts of uppercase and lowercase letters is ensured,
+    /// and only when the case isn't left alone with [`CaseRule::Never`].
+    /// With [`CaseRule::Auto`] a single letter is enough,
+    /// since that's what [`PasswordBuilder::ensure_case()`] settles for.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CaseRule, Nonconformity, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 100;
+    /// settings.upper_handling = CaseRule::Force;
+    ///
+    /// for password in settings.generate().unwrap() {
+    ///     assert_eq!(settings.conforms(&password), Ok(()), "{password}");
+    /// }
+    ///
+    /// let password = "Thequickbrownfox3jumps!over";
+    /// assert_eq!(settings.conforms(password), Ok(()));
+    /// assert_eq!(
+    ///     settings.conforms("Thequick3!over"),
+    ///     Err(vec![Nonconformity::Length {
+    ///         observed: 14,
+    ///         expected: 24..=30,
+    ///     }])
+    /// );
+    /// assert_eq!(
+    ///     settings.conforms("Thequickbrownfox3jumps!o789"),
+    ///     Err(vec![Nonconformity::Numbers {
+    ///         observed: 4,
+    ///         expected: 1..=2,
+    ///     }])
+    /// );
+    /// assert_eq!(
+    ///     settings.conforms("Thequickbrownfox3jumpsover"),
+    ///     Err(vec![Nonconformity::SpecialChars {
+    ///         observed: 0,
+    ///         expected: 1..=2,
+    ///     }])
+    /// );
+    /// assert_eq!(
+    ///     settings.conforms(&password.to_lowercase()),
+    ///     Err(vec![Nonconformity::Upper {
+    ///         observed: 0,
+    ///         expected: 1..=usize::MAX,
+    ///     }])
+    /// );
+    /// assert_eq!(
+    ///     settings.conforms(&password.to_uppercase()),
+    ///     Err(vec![Nonconformity::Lower {
+    ///         observed: 0,
+    ///         expected: 1..=usize::MAX,
+    ///     }])
+    /// );
+    /// ```
+    pub fn conforms(&self, password: &str) -> Result<(), Vec<Nonconformity>> {
+        let mut found = Vec::new();
+        let mut check =
+            |observed: usize,
+             expected: RangeInclusive<usize>,
+             nonconformity: fn(usize, RangeInclusive<usize>) -> Nonconformity| {
+                if !expected.contains(&observed) {
+                    found.push(nonconformity(observed, expected));
+                }
+            };
+
+        if self.length_mode == LengthMode::Units {
+            let length = unit_len(password, self.length_unit);
+            check(length, self.length.clone(), |observed, expected| {
+                Nonconformity::Length { observed, expected }
+            });
+        }
+
+        // Only the inserted characters count towards the amounts.
+        let inserted = if self.separator.is_empty() {
+            password.to_string()
+        } else {
+            password.replace(&self.separator, "")
+        };
+        let specials = self.special_amount();
+        let crowded = self.number_amount.end() + specials.end() > self.insert_limit();
+        let lowest = |range: &RangeInclusive<usize>| if crowded { 0 } else { *range.start() };
+
+        let numbers_end = if self.keep_numbers {
+            usize::MAX
+        } else {
+            *self.number_amount.end()
+        };
+        check(
+            count_digits(&inserted),
+            lowest(&self.number_amount)..=numbers_end,
+            |observed, expected| Nonconformity::Numbers { observed, expected },
+        );
+
+        // Sequences count once towards the amount, but with every one of their characters here.
+        let specials_end = self.special_chars_amount.end() * self.longest_special()
+            + self
+                .special_groups
+                .iter()
+                .filter(|g| !g.chars.is_empty())
+                .map(|g| g.amount.end())
+                .sum::<usize>();
+        let is_special = |c: char| {
+            self.special_chars.contains(c)
+                || self.special_sequences.iter().any(|s| s.contains(c))
+                || self.special_groups.iter().any(|g| g.chars.contains(c))
+        };
+        check(
+            inserted.chars().filter(|&c| is_special(c)).count(),
+            lowest(&specials)..=specials_end,
+            |observed, expected| Nonconformity::SpecialChars { observed, expected },
+        );
+
+        let case_start = |range: &RangeInclusive<usize>, rule: CaseRule| match rule {
+            CaseRule::Force => *range.start(),
+            CaseRule::Auto | CaseRule::Never => (*range.start()).min(1),
+        };
+        if self.upper_handling != CaseRule::Never {
+            check(
+                count_upper(password),
+                case_start(&self.upper_amount, self.upper_handling)..=usize::MAX,
+                |observed, expected| Nonconformity::Upper { observed, expected },
+            );
+        }
+        if self.lower_handling != CaseRule::Never {
+            check(
+                count_lower(password),
+                case_start(&self.lower_amount, self.lower_handling)..=usize::MAX,
+                |observed, expected| Nonconformity::Lower { observed, expected },
+            );
+        }
+
+        if found.is_empty() {
+            Ok(())
+        } else {
+            Err(found)
+        }
+    }
+
+    /// The amount of special characters to insert, including the ones from every group.
+    fn special_amount(&self) -> RangeInclusive<usize> {
+        let mut specials = if self.distinct_special_count() == 0 {
+            0..=0
+        } else {
+            self.special_chars_amount.clone()
+        };
+        for group in self.special_groups.iter().filter(|g| !g.chars.is_empty()) {
+            specials =
+                specials.start() + group.amount.start()..=specials.end() + group.amount.end();
+        }
+        specials
+    }
+
+    /// The length of the longest special character or sequence.
+    fn longest_special(&self) -> usize {
+        let chars = usize::from(!self.special_chars.is_empty());
+        self.special_sequences
+            .iter()
+            .map(|s| unit_len(s, self.length_unit))
+            .fold(chars, usize::max)
+    }
+
+    /// How much of the length the numbers and special characters can take up.
+    fn insert_limit(&self) -> usize {
+        if matches!(self.length_mode, LengthMode::Words(_)) {
+            usize::MAX
+        } else if self.replace {
+            *self.length.end()
+        } else {
+            *self.length.start()
+        }
+    }
+
+    /// A rough estimate of how many distinct passwords these settings can produce,
+    /// as bits so that it never overflows.
+    ///
+    /// It's the sum of the factors in [`SearchSpaceBreakdown`]:
+    ///
+    /// - Words: with [`WordSelection::Consecutive`] only the starting word is random,
+    ///   giving `log2(words)`, while with [`WordSelection::Uniform`] every word is,
+    ///   giving `log2(words)` for each of the words that fit on average.
+    /// - Word amount: with the length measured in [words](LengthMode::Words) it's the choice
+    ///   of the amount, `log2(amounts)`, and otherwise it's the decisions to keep adding
+    ///   words once the password is long enough, made with
+    ///   [`continue_probability`](PasswordSettings#structfield.continue_probability)
+    ///   at each of the boundaries where another word of the average length still fits.
+    /// - Numbers and special characters: each one adds its value and its position,
+    ///   `log2(pool) + log2(length)`, or of the length within the
+    ///   [`insert_zone`](PasswordSettings#structfield.insert_zone),
+    ///   or only its value when they're grouped by the
+    ///   [`insert_placement`](PasswordSettings#structfield.insert_placement),
+    ///   and the possible amounts add up, so it's
+    ///   `log2(Σ 2^(amount × bits per character))` over the range of amounts.
+    ///   Every [special group](PasswordSettings::add_special_group) counts on its own.
+    /// - Case: the ways of picking the letters to uppercase, `log2(Σ C(letters, amount))`
+    ///   over [`upper_amount`](PasswordSettings#structfield.upper_amount), but only when
+    ///   they're chosen at random, so not with [`CaseRule::Never`]
+    ///   or when the [`word_case`](PasswordSettings#structfield.word_case) already decides them.
+    ///
+    /// The lengths are the means of the ranges, and the words are treated as unrelated,
+    /// so it's only an upper bound for text where words repeat.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{PasswordSettings, WordSelection};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// let bits = settings.search_space_bits();
+    /// assert_eq!(bits, settings.search_space().total());
+    ///
+    /// settings.word_selection = WordSelection::Uniform;
+    /// let uniform = settings.search_space_bits();
+    /// assert!(uniform > bits);
+    ///
+    /// settings.get_words_from_str("a wizard quietly packs boxes of liquor jugs");
+    /// let more_words = settings.search_space_bits();
+    /// assert!(more_words > uniform);
+    ///
+    /// let before = settings.search_space();
+    /// for widened in [0..=2, 1..=4, 0..=6] {
+    ///     settings.number_amount = widened.clone();
+    ///     let after = settings.search_space();
+    ///     assert!(after.total() >= before.total(), "{before:?} {after:?}");
+    ///     settings.number_amount = 1..=2;
+    ///
+    ///     settings.special_chars_amount = widened;
+    ///     let after = settings.search_space();
+    ///     assert!(after.total() >= before.total(), "{before:?} {after:?}");
+    ///     settings.special_chars_amount = 1..=2;
+    /// }
+    /// ```
+    pub fn search_space_bits(&self) -> f64 {
+        self.search_space().total()
+    }
+
+    /// Each of the factors of [`search_space_bits()`](PasswordSettings::search_space_bits).
+    pub fn search_space(&self) -> SearchSpaceBreakdown {
+        if self.words.is_empty() {
+            return SearchSpaceBreakdown::default();
+        }
+
+        let mean = |range: &RangeInclusive<usize>| (range.start() + range.end()) as f64 / 2.0;
+        let words = self.words.len() as f64;
+        let word_len = self
+            .words
+            .iter()
+            .map(|w| unit_len(w, self.length_unit))
+            .sum::<usize>() as f64
+            / words;
+        let numbers = mean(&self.number_amount);
+        let specials = mean(&self.special_chars_amount)
+            + self
+                .special_groups
+                .iter()
+                .map(|g| mean(&g.amount))
+                .sum::<f64>();
+
+        // The length of the whole password and of its words, and the amount of words.
+        let inserted = if self.replace {
+            0.0
+        } else {
+            numbers + specials
+        };
+        let (length, words_len, word_amount) = match &self.length_mode {
+            LengthMode::Units => {
+                let length = mean(&self.length);
+                let words_len = (length - inserted).max(0.0);
+                (length, words_len, (words_len / word_len).max(1.0))
+            }
+            LengthMode::Words(range) => {
+                let amount = mean(range);
+                let words_len = amount * word_len;
+                (words_len + inserted, words_len, amount.max(1.0))
+            }
+        };
+        let word_amount_bits = match &self.length_mode {
+            LengthMode::Units => {
+                let word_unit = word_len + unit_len(&self.separator, self.length_unit) as f64;
+                let min = (*self.length.start() as f64 - inserted).max(0.0);
+                let max = *self.length.end() as f64 - inserted;
+                let forced = (min / word_unit).ceil().max(1.0);
+                let boundaries = ((max - forced * word_unit) / word_unit).floor().max(0.0);
+                stop_bits(self.continue_probability, boundaries)
+            }
+            LengthMode::Words(range) => ((range.end() - range.start() + 1) as f64).log2(),
+        };
+        let word_bits = match self.word_selection {
+            WordSelection::Consecutive => words.log2(),
+            WordSelection::Uniform => word_amount * words.log2(),
+        };
+
+        let positions = self.insert_positions(length);
+        let insert_bits = |amount: &RangeInclusive<usize>, pool: usize| {
+            if pool == 0 {
+                0.0
+            } else {
+                let per_char = (pool as f64).log2() + positions;
+                log2_sum(amount.clone().map(|k| k as f64 * per_char))
+            }
+        };
+        let mut special_chars =
+            insert_bits(&self.special_chars_amount, self.distinct_special_count());
+        for group in &self.special_groups {
+            special_chars += insert_bits(&group.amount, distinct_char_count(&group.chars));
+        }
+
+        let case = if self.upper_handling == CaseRule::Never
+            || self.word_case_with(&GenerateOptions::default()) != WordCase::AsIs
+                && self.upper_handling != CaseRule::Force
+        {
+            0.0
+        } else {
+            let letters = words_len.round() as usize;
+            log2_sum(
+                self.upper_amount
+                    .clone()
+                    .filter(|&k| k <= letters)
+                    .map(|k| log2_binomial(letters, k)),
+            )
+        };
+
+        SearchSpaceBreakdown {
+            words: word_bits,
+            word_amount: word_amount_bits,
+            numbers: insert_bits(&self.number_amount, 10),
+            special_chars,
+            case,
+        }
+    }
+
+    /// A rough estimate of the bits of entropy of a password generated with these settings,
+    /// like to show it next to the password.
+    ///
+    /// Unlike [`search_space_bits()`](PasswordSettings::search_space_bits), which averages
+    /// over everything the settings allow, it counts what's in the password:
+    ///
+    /// - Words: the same as in the [`search_space()`](PasswordSettings::search_space),
+    ///   except that with [`WordSelection::Uniform`] it's `log2(words)` for each of
+    ///   the words the letters would make at the average word length.
+    /// - Word amount: the same as in the [`search_space()`](PasswordSettings::search_space).
+    /// - Numbers and special characters: `log2(pool) + log2(length)` for each one in it,
+    ///   or of the length within the [`insert_zone`](PasswordSettings#structfield.insert_zone),
+    ///   where the pool of the special characters is all of them together, groups included.
+    /// - Case: `log2(C(letters, uppercase))` when the uppercase letters are chosen at random.
+    ///
+    /// It assumes that the password was generated with these settings
+    /// and is 0 when there are no words.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CaseRule, PasswordSettings, WordSelection};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("abcd efgh ijkl mnop qrst uvwx yzab cdef");
+    /// settings.length = 8..=16;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_handling = CaseRule::Never;
+    ///
+    /// // Starting at one of 8 words, with 2 more words fitting after the first 2,
+    /// // continued past with 0.5 each: log2(8) + H(0.5, 0.25, 0.25).
+    /// settings.set_continue_probability(0.5).unwrap();
+    /// assert_eq!(settings.entropy_of("abcdefgh"), 3.0 + 1.5);
+    /// assert_eq!(settings.entropy_of("abcdefgh"), settings.search_space_bits());
+    ///
+    /// // Deciding nothing at the boundaries only leaves the starting word.
+    /// settings.set_continue_probability(1.0).unwrap();
+    /// assert_eq!(settings.entropy_of("abcdefghijklmnop"), 3.0);
+    ///
+    /// // The default of 0.8: H(0.2, 0.16, 0.64).
+    /// settings.set_continue_probability(0.8).unwrap();
+    /// let stops = -[0.2f64, 0.16, 0.64].iter().map(|p| p * p.log2()).sum::<f64>();
+    /// assert!((settings.entropy_of("abcdefgh") - (3.0 + stops)).abs() < 1e-9);
+    ///
+    /// // A number leaves room for 1 more word, and has 10 values in 9 positions.
+    /// settings.set_continue_probability(0.5).unwrap();
+    /// settings.number_amount = 1..=1;
+    /// let bits = settings.entropy_of("abcd7efgh");
+    /// assert!((bits - (3.0 + 1.0 + 90f64.log2())).abs() < 1e-9);
+    ///
+    /// // Each of the 2 words is picked from all 8.
+    /// settings.word_selection = WordSelection::Uniform;
+    /// let bits = settings.entropy_of("abcd7efgh");
+    /// assert!((bits - (6.0 + 1.0 + 90f64.log2())).abs() < 1e-9);
+    /// ```
+    pub fn entropy_of(&self, password: &str) -> f64 {
+        let space = self.search_space();
+        if self.words.is_empty() {
+            return 0.0;
+        }
+
+        let special_chars = self.all_special_chars();
+        let found = composition(password, &special_chars);
+        let positions = self.insert_positions(unit_len(password, self.length_unit) as f64);
+        let letters = found.upper + found.lower;
+
+        let words = match self.word_selection {
+            WordSelection::Consecutive => space.words,
+            WordSelection::Uniform => {
+                let word_len = self
+                    .words
+                    .iter()
+                    .map(|w| unit_len(w, self.length_unit))
+                    .sum::<usize>() as f64
+                    / self.words.len() as f64;
+                (letters as f64 / word_len).round().max(1.0) * (self.words.len() as f64).log2()
+            }
+        };
+        let numbers = found.digits as f64 * (10f64.log2() + positions);
+        let special_pool = distinct_char_count(&special_chars);
+        let special = if special_pool == 0 {
+            0.0
+        } else {
+            found.special as f64 * ((special_pool as f64).log2() + positions)
+        };
+        let case = if self.upper_handling == CaseRule::Never
+            || self.word_case_with(&GenerateOptions::default()) != WordCase::AsIs
+                && self.upper_handling != CaseRule::Force
+        {
+            0.0
+        } else {
+            log2_binomial(letters, found.upper)
+        };
+
+        words + space.word_amount + numbers + special + case
+    }
+
+    /// Generate a vector of passwords.
+    pub fn generate(&self) -> Result<Vec<String>, GenerateError> {
+        self.generate_with(&GenerateOptions::default())
+    }
+
+    /// Generate a vector of passwords that are overwritten in memory when they're dropped.
+    ///
+    /// While generating, anything that held part of a password is overwritten too,
+    /// like the characters inserted into it, the attempts that were rejected
+    /// and the buffers it outgrew.
+    ///
+    /// Anything copied out of the passwords, like with `to_string()`, the
+    /// [details](PasswordSettings::generate_detailed) of other methods and
+    /// the words the passwords are made from aren't covered, and neither is
+    /// memory that was swapped to disk.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 3;
+    ///
+    /// for password in settings.generate_secure().unwrap() {
+    ///     assert!(settings.conforms(&password).is_ok());
+    /// }
+    /// ```
+    #[cfg(feature = "zeroize")]
+    pub fn generate_secure(&self) -> Result<Vec<Zeroizing<String>>, GenerateError> {
+        Ok(self.generate()?.into_iter().map(Zeroizing::new).collect())
+    }
+
+    /// Generate a vector of passwords, overriding some of the settings just for this call.
+    ///
+    /// The settings aren't mutated, so it can be called concurrently with different options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{GenerateOptions, PasswordSettings};
+    /// # use std::thread;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    ///
+    /// thread::scope(|s| {
+    ///     for (amount, length) in [(3, 10..=12), (5, 20..=20), (8, 30..=40)] {
+    ///         let settings = &settings;
+    ///         s.spawn(move || {
+    ///             let options = GenerateOptions {
+    ///                 pass_amount: Some(amount),
+    ///                 length: Some(length.clone()),
+    ///                 ..Default::default()
+    ///             };
+    ///             let passwords = settings.generate_with(&options).unwrap();
+    ///             assert_eq!(passwords.len(), amount);
+    ///             assert!(passwords.iter().all(|p| p.len() <= *length.end()));
+    ///         });
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(settings.pass_amount, 1);
+    /// ```
+    pub fn generate_with(&self, options: &GenerateOptions) -> Result<Vec<String>, GenerateError> {
+        self.validate(options)?;
+
+        Ok(self
+            .details(options, &mut thread_rng(), self.seed, &mut |_, _| {
+                ControlFlow::Continue(())
+            })?
+            .into_iter()
+            .map(|d| d.password)
+            .collect())
+    }
+
+    /// Make an immutable copy of the settings and words for generating,
+    /// to share between threads while these settings are still being edited.
+    ///
+    /// Freeze again after changing the settings to generate with the changes.
+    pub fn freeze(&self) -> FrozenSettings {
+        FrozenSettings::new(self.clone())
+    }
+
+    /// Generate a vector of passwords with all of the randomness drawn from `rng`,
+    /// like an [`OsRng`](rand::rngs::OsRng) or a seeded generator in tests.
+    ///
+    /// [`seed`](PasswordSettings#structfield.seed) is ignored in favour of `rng`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// # use rand::{rngs::StdRng, SeedableRng};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 10;
+    ///
+    /// let passwords = settings.generate_with_rng(&mut StdRng::seed_from_u64(1)).unwrap();
+    /// assert_eq!(passwords.len(), 10);
+    /// assert_eq!(
+    ///     settings.generate_with_rng(&mut StdRng::seed_from_u64(1)).unwrap(),
+    ///     passwords,
+    /// );
+    ///
+    /// assert!(settings.generate_with_rng(&mut rand::rngs::OsRng).is_ok());
+    /// ```
+    pub fn generate_with_rng<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<Vec<String>, GenerateError> {
+        self.validate(&GenerateOptions::default())?;
+
+        Ok(self
+            .details(&GenerateOptions::default(), rng, None, &mut |_, _| {
+                ControlFlow::Continue(())
+            })?
+            .into_iter()
+            .map(|d| d.password)
+            .collect())
+    }
+
+    /// Generate exactly `n` passwords, ignoring
+    /// [`pass_amount`](PasswordSettings#structfield.pass_amount).
+    ///
+    /// Zero returns an empty vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    ///
+    /// assert!(settings.generate_n(0).unwrap().is_empty());
+    /// assert_eq!(settings.generate_n(1).unwrap().len(), 1);
+    /// assert_eq!(settings.generate_n(1000).unwrap().len(), 1000);
+    /// assert_eq!(settings.pass_amount, 1);
+    /// ```
+    pub fn generate_n(&self, n: usize) -> Result<Vec<String>, GenerateError> {
+        self.generate_with(&GenerateOptions {
+            pass_amount: Some(n),
+            ..Default::default()
+        })
+    }
+
+    /// Generate passwords one at a time as they're needed, ignoring
+    /// [`pass_amount`](PasswordSettings#structfield.pass_amount), to go through
+    /// a lot of them without keeping them all in memory.
+    ///
+    /// The iterator never ends on its own, so take as many as needed.
+    /// The settings are checked here instead of for each password, and the view of
+    /// the words is prepared once, so the passwords are the same as [`generate()`](PasswordSettings::generate)
+    /// would return with a [`seed`](PasswordSettings#structfield.seed).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    ///
+    /// let long = settings.iter().unwrap().filter(|p| p.len() > 20).take(3);
+    /// assert_eq!(long.count(), 3);
+    ///
+    /// settings.clear_words();
+    /// assert!(settings.iter().is_err());
+    /// ```
+    pub fn iter(&self) -> Result<PasswordIter<'_>, GenerateError> {
+        self.validate(&GenerateOptions::default())?;
+
+        Ok(PasswordIter::new(self))
+    }
+
+    /// Generate a vector of passwords like [`generate()`](PasswordSettings::generate),
+    /// but give up with [`GenerateError::TimedOut`] if it takes longer than `deadline`.
+    ///
+    /// The time is checked while selecting the words of each password, so settings
+    /// that keep failing to fit the length, with a large
+    /// [`retry_budget.assembly`](RetryBudget::assembly), can't hold up the caller.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{GenerateError, PasswordSettings, RetryBudget};
+    /// # use std::time::Duration;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 10;
+    /// assert_eq!(settings.try_generate(Duration::from_secs(10)).unwrap().len(), 10);
+    ///
+    /// // Every word is longer than the password, so it never fits.
+    /// settings.set_words(vec![String::from("incomprehensibilities")]);
+    /// settings.length = 5..=5;
+    /// settings.retry_budget = RetryBudget { assembly: usize::MAX, ..Default::default() };
+    /// match settings.try_generate(Duration::from_millis(10)) {
+    ///     Err(GenerateError::TimedOut { source }) => {
+    ///         assert_eq!(source.finished, 0);
+    ///         assert!(source.resets > 0);
+    ///     }
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn try_generate(&self, deadline: Duration) -> Result<Vec<String>, GenerateError> {
+        let deadline = Instant::now().checked_add(deadline);
+        let mut passwords = self.iter()?.with_deadline(deadline);
+        let batch = passwords.by_ref().take(self.pass_amount).collect();
+
+        match passwords.take_error() {
+            Some(error) => Err(error),
+            None => Ok(batch),
+        }
+    }
+
+    /// Generate a password for each of the keys, like the names of accounts,
+    /// paired with them in the same order and ignoring
+    /// [`pass_amount`](PasswordSettings#structfield.pass_amount).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 10;
+    /// settings.seed = Some(42);
+    ///
+    /// let accounts = ["alice", "bob", "carol"];
+    /// let pairs = settings.generate_for_keys(accounts).unwrap();
+    /// let keys: Vec<&str> = pairs.iter().map(|(key, _)| key.as_str()).collect();
+    /// assert_eq!(keys, accounts);
+    ///
+    /// // The same passwords, in the same order, as generating that many.
+    /// let passwords: Vec<String> = pairs.into_iter().map(|(_, password)| password).collect();
+    /// assert_eq!(passwords, settings.generate_n(3).unwrap());
+    ///
+    /// assert!(settings.generate_for_keys(Vec::<String>::new()).unwrap().is_empty());
+    /// ```
+    pub fn generate_for_keys<K: Into<String>>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+    ) -> Result<Vec<(String, String)>, GenerateError> {
+        let keys: Vec<String> = keys.into_iter().map(Into::into).collect();
+        let passwords = self.generate_n(keys.len())?;
+        Ok(keys.into_iter().zip(passwords).collect())
+    }
+
+    /// Generate passwords while calling `on_each` with the index of every password
+    /// right after it's generated, which can stop the generation early by returning
+    /// [`ControlFlow::Break`]. Returns the passwords generated until then,
+    /// including the one the generation was stopped at.
+    ///
+    /// Useful for showing the progress when generating a lot of passwords.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// # use std::ops::ControlFlow;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 100;
+    ///
+    /// let mut seen = Vec::new();
+    /// let passwords = settings
+    ///     .generate_with_callback(|i, password| {
+    ///         seen.push((i, password.to_string()));
+    ///         if i + 1 == 10 {
+    ///             ControlFlow::Break(())
+    ///         } else {
+    ///             ControlFlow::Continue(())
+    ///         }
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(passwords.len(), 10);
+    /// assert!(seen.iter().enumerate().all(|(i, (index, password))| {
+    ///     i == *index && passwords[i] == *password
+    /// }));
+    ///
+    /// let passwords = settings.generate_with_callback(|_, _| ControlFlow::Continue(()));
+    /// assert_eq!(passwords.unwrap().len(), 100);
+    /// ```
+    pub fn generate_with_callback(
+        &self,
+        mut on_each: impl FnMut(usize, &str) -> ControlFlow<()>,
+    ) -> Result<Vec<String>, GenerateError> {
+        self.validate(&GenerateOptions::default())?;
+
+        Ok(self
+            .details(
+                &GenerateOptions::default(),
+                &mut thread_rng(),
+                self.seed,
+                &mut on_each,
+            )?
+            .into_iter()
+            .map(|d| d.password)
+            .collect())
+    }
+
+    /// Generate passwords along with details about each of them
+    /// and the [warnings](PasswordSettings::warnings) for the current settings.
+    pub fn generate_detailed(&self) -> Result<GenerationReport, GenerateError> {
+        self.validate(&GenerateOptions::default())?;
+
+        Ok(GenerationReport {
+            passwords: self.details(
+                &GenerateOptions::default(),
+                &mut thread_rng(),
+                self.seed,
+                &mut |_, _| ControlFlow::Continue(()),
+            )?,
+            warnings: self.warnings(),
+            words_fingerprint: self.words_fingerprint(),
+        })
+    }
+
+    /// Generate a vector of passwords from the words of a [`Lexicon`] instead of
+    /// the ones stored in the settings, which are left untouched.
+    ///
+    /// Since a [`Lexicon`] doesn't keep track of sources,
+    /// [`source_mixing`](PasswordSettings#structfield.source_mixing) and the weights are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{Lexicon, PasswordSettings, Split};
+    /// let mut first = Lexicon::new("first", Split::UnicodeWords);
+    /// first.extract_words("abc cab bca acb", |_| true);
+    /// let mut second = Lexicon::new("second", Split::UnicodeWords);
+    /// second.extract_words("xyz zyx yzx xzy", |_| true);
+    ///
+    /// let mut settings = PasswordSettings::new();
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.pass_amount = 20;
+    ///
+    /// for password in settings.generate_with_lexicon(&first).unwrap() {
+    ///     assert!(password.to_lowercase().chars().all(|c| "abc".contains(c)));
+    /// }
+    /// for password in settings.generate_with_lexicon(&second).unwrap() {
+    ///     assert!(password.to_lowercase().chars().all(|c| "xyz".contains(c)));
+    /// }
+    /// assert!(settings.words().is_empty());
+    /// ```
+    pub fn generate_with_lexicon(&self, lexicon: &Lexicon) -> Result<Vec<String>, GenerateError> {
+        self.validate_settings(&GenerateOptions::default())?;
+        ensure!(!lexicon.words().is_empty(), NotEnoughWordsSnafu);
+
+        let picker = WordPicker::unweighted(self);
+
+        Ok(self
+            .details_from(
+                Words::Strings(lexicon.words()),
+                &picker,
+                &GenerateOptions::default(),
+                &mut thread_rng(),
+                self.seed,
+                &mut |_, _| ControlFlow::Continue(()),
+            )?
+            .into_iter()
+            .map(|d| d.password)
+            .collect())
+    }
+
+    /// Make every check before generating with `options`, which every way of generating
+    /// goes through, so they all fail with the same error for the same settings.
+    pub(crate) fn validate(&self, options: &GenerateOptions) -> Result<(), GenerateError> {
+        self.validate_settings(options)?;
+        ensure!(!self.words.is_empty(), NotEnoughWordsSnafu);
+
+        Ok(())
+    }
+
+    /// The checks of [`validate()`](PasswordSettings::validate) that don't depend on
+    /// the words, for generating from a [`Lexicon`].
+    fn validate_settings(&self, options: &GenerateOptions) -> Result<(), GenerateError> {
+        for (field, range) in [
+            ("length", options.length.as_ref().unwrap_or(&self.length)),
+            (
+                "number_amount",
+                options
+                    .number_amount
+                    .as_ref()
+                    .unwrap_or(&self.number_amount),
+            ),
+            (
+                "special_chars_amount",
+                options
+                    .special_chars_amount
+                    .as_ref()
+                    .unwrap_or(&self.special_chars_amount),
+            ),
+            ("upper_amount", &self.upper_amount),
+            ("lower_amount", &self.lower_amount),
+        ] {
+            ensure!(!range.is_empty(), EmptyRangeSnafu { field });
+        }
+        if let LengthMode::Words(range) = &self.length_mode {
+            ensure!(
+                !range.is_empty(),
+                EmptyRangeSnafu {
+                    field: "length_mode"
+                }
+            );
+        }
+        for group in &self.special_groups {
+            ensure!(
+                !group.amount.is_empty(),
+                EmptyRangeSnafu {
+                    field: "special_groups"
+                }
+            );
+        }
+        self.check_distinct_inserts(options)?;
+        self.check_special_groups(options)?;
+        self.check_insert_zone(options)
+    }
+
+    /// Check that there are enough distinct characters to insert,
+    /// in case [`distinct_inserts`](PasswordSettings#structfield.distinct_inserts) is set.
+    fn check_distinct_inserts(&self, options: &GenerateOptions) -> Result<(), GenerateError> {
+        if !self.distinct_inserts {
+            return Ok(());
+        }
+
+        let number_amount = options
+            .number_amount
+            .as_ref()
+            .unwrap_or(&self.number_amount);
+        ensure!(
+            *number_amount.end() <= 10,
+            NotEnoughDistinctInsertsSnafu {
+                requested: *number_amount.end(),
+                available: 10usize,
+            }
+        );
+
+        let special_amount = options
+            .special_chars_amount
+            .as_ref()
+            .unwrap_or(&self.special_chars_amount);
+        let available = self.distinct_special_count();
+        ensure!(
+            *special_amount.end() <= available,
+            NotEnoughDistinctInsertsSnafu {
+                requested: *special_amount.end(),
+                available,
+            }
+        );
+
+        for group in &self.special_groups {
+            let available = distinct_char_count(&group.chars);
+            ensure!(
+                *group.amount.end() <= available,
+                NotEnoughDistinctInsertsSnafu {
+                    requested: *group.amount.end(),
+                    available,
+                }
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Make sure the [special groups](PasswordSettings::add_special_group) never have
+    /// their characters dropped for not fitting the length.
+    fn check_special_groups(&self, options: &GenerateOptions) -> Result<(), GenerateError> {
+        if self.special_groups.is_empty() || matches!(self.length_mode, LengthMode::Words(_)) {
+            return Ok(());
+        }
+
+        let length = options.length.as_ref().unwrap_or(&self.length);
+        let number_amount = options
+            .number_amount
+            .as_ref()
+            .unwrap_or(&self.number_amount);
+        let special_amount = options
+            .special_chars_amount
+            .as_ref()
+            .unwrap_or(&self.special_chars_amount);
+        let longest_special = self.longest_special();
+
+        let needed = number_amount.end()
+            + special_amount.end() * longest_special
+            + self
+                .special_groups
+                .iter()
+                .filter(|g| !g.chars.is_empty())
+                .map(|g| g.amount.end())
+                .sum::<usize>();
+        ensure!(
+            needed <= *length.start(),
+            GroupsExceedLengthSnafu {
+                needed,
+                length: *length.start(),
+            }
+        );
+
+        Ok(())
+    }
+
+    /// The bits of the position of each number and special character
+    /// in a password `length` units long, none when they're grouped.
+    fn insert_positions(&self, length: f64) -> f64 {
+        if self.insert_placement != InsertPlacement::Random {
+            return 0.0;
+        }
+        let length = match &self.insert_zone {
+            Some(zone) => length.min(*zone.end() as f64 + 1.0) - *zone.start() as f64,
+            None => length,
+        };
+        length.max(1.0).log2()
+    }
+
+    /// Check that the [`insert_zone`](PasswordSettings#structfield.insert_zone) isn't empty,
+    /// and has room for the fewest characters to replace.
+    fn check_insert_zone(&self, options: &GenerateOptions) -> Result<(), GenerateError> {
+        let Some(zone) = &self.insert_zone else {
+            return Ok(());
+        };
+        ensure!(
+            !zone.is_empty(),
+            EmptyRangeSnafu {
+                field: "insert_zone"
+            }
+        );
+        if !self.replace
+            || self.leet
+            || self.insert_placement != InsertPlacement::Random
+            || matches!(self.length_mode, LengthMode::Words(_))
+        {
+            return Ok(());
+        }
+
+        let length = options.length.as_ref().unwrap_or(&self.length);
+        let number_amount = options
+            .number_amount
+            .as_ref()
+            .unwrap_or(&self.number_amount);
+        let special_amount = options
+            .special_chars_amount
+            .as_ref()
+            .unwrap_or(&self.special_chars_amount);
+        let specials = if self.distinct_special_count() == 0 {
+            0
+        } else {
+            *special_amount.start()
+        };
+        let needed = number_amount.start()
+            + specials
+            + self
+                .special_groups
+                .iter()
+                .filter(|g| !g.chars.is_empty())
+                .map(|g| g.amount.start())
+                .sum::<usize>();
+        let zone_len = (*zone.end()).min(length.end().saturating_sub(1)) + 1;
+        let zone_len = zone_len.saturating_sub(*zone.start());
+        ensure!(
+            needed <= zone_len,
+            InsertZoneTooSmallSnafu {
+                needed,
+                zone: zone_len,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Generate the passwords drawing from `rng`, unless there's a `seed` to derive
+    /// the randomness of each password from.
+    pub(crate) fn details<R: Rng + ?Sized>(
+        &self,
+        options: &GenerateOptions,
+        rng: &mut R,
+        seed: Option<u64>,
+        on_each: &mut impl FnMut(usize, &str) -> ControlFlow<()>,
+    ) -> Result<Vec<PasswordDetails>, GenerateError> {
+        let (words, picker) = IterWords::with_picker(self, rng, seed);
+        self.details_from(words.get(), &picker, options, rng, seed, on_each)
+    }
+
+    /// The mixed words along with their sources.
+    pub(crate) fn mixed_view<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        seed: Option<u64>,
+    ) -> (Vec<&str>, Vec<SourceId>) {
+        self.mixed_indices(rng, seed)
+            .into_iter()
+            .map(|i| (self.words[i].as_str(), self.word_sources[i]))
+            .unzip()
+    }
+
+    /// The values shared by every password of a call, with [`consistent_batch`](PasswordSettings#structfield.consistent_batch).
+    pub(crate) fn batch_roll<R: Rng + ?Sized>(
+        &self,
+        options: &GenerateOptions,
+        rng: &mut R,
+        seed: Option<u64>,
+    ) -> Option<RolledParameters> {
+        self.consistent_batch.then(|| match seed {
+            Some(seed) => PasswordBuilder::roll(self, options, &mut StdRng::seed_from_u64(seed)),
+            None => PasswordBuilder::roll(self, options, rng),
+        })
+    }
+
+    /// Build and generate a single password with the values of the batch, if any.
+    pub(crate) fn generate_one<R: Rng + ?Sized>(
+        &self,
+        words: Words,
+        picker: &WordPicker,
+        options: &GenerateOptions,
+        batch: &Option<RolledParameters>,
+        pool: Option<usize>,
+        rng: &mut R,
+    ) -> Result<PasswordDetails, GenerateError> {
+        self.password_builder(words, picker, options, batch, rng)
+            .with_pool(pool)
+            .generate_checked(rng)
+    }
+
+    /// The builder of a single password with the values of the batch, if any.
+    pub(crate) fn password_builder<'w, R: Rng + ?Sized>(
+        &self,
+        words: Words<'w>,
+        picker: &'w WordPicker,
+        options: &GenerateOptions,
+        batch: &Option<RolledParameters>,
+        rng: &mut R,
+    ) -> PasswordBuilder<'w> {
+        let rolled = match batch {
+            Some(rolled) => rolled.clone(),
+            None => PasswordBuilder::roll(self, options, rng),
+        };
+        PasswordBuilder::with_rolled(self, options, rolled, rng).with_words(words, picker)
+    }
+
+    /// Generate the password at index `i` with `generate`, and again for as long as
+    /// it's one of the `seen` passwords with [`unique`](PasswordSettings#structfield.unique).
+    ///
+    /// With a seed, every attempt gets its own stream, so the password only depends
+    /// on the seed, the index and the passwords before it.
+    pub(crate) fn generate_unique<R: Rng + ?Sized>(
+        &self,
+        i: usize,
+        seen: &mut HashSet<String>,
+        seed: Option<u64>,
+        mut rng: &mut R,
+        mut generate: impl FnMut(&mut dyn RngCore) -> Result<PasswordDetails, GenerateError>,
+    ) -> Result<PasswordDetails, GenerateError> {
+        let mut retries = 0;
+        loop {
+            let mut details = match seed {
+                Some(seed) => generate(&mut attempt_rng(seed, i, retries)),
+                None => generate(&mut rng),
+            }?;
+            if !self.unique || seen.insert(details.password.clone()) {
+                details.retries.uniqueness = retries;
+                return Ok(details);
+            }
+            if retries == self.retry_budget.uniqueness {
+                return Err(CannotSatisfyUniquenessError {
+                    distinct: seen.len(),
+                    retries,
+                }
+                .into());
+            }
+            retries += 1;
+        }
+    }
+
+    fn details_from<R: Rng + ?Sized>(
+        &self,
+        words: Words,
+        picker: &WordPicker,
+        options: &GenerateOptions,
+        rng: &mut R,
+        seed: Option<u64>,
+        on_each: &mut impl FnMut(usize, &str) -> ControlFlow<()>,
+    ) -> Result<Vec<PasswordDetails>, GenerateError> {
+        let mut passwords = Vec::new();
+        let batch = self.batch_roll(options, rng, seed);
+        let mut pool = self.batch_retry.pool();
+        let mut seen = HashSet::new();
+
+        for i in 0..options.pass_amount.unwrap_or(self.pass_amount) {
+            let details = self.generate_unique(i, &mut seen, seed, rng, |rng| {
+                self.generate_one(words, picker, options, &batch, pool, rng)
+            })?;
+            spend_pool(&mut pool, details.retries.assembly, i);
+            let flow = on_each(i, &details.password);
+            passwords.push(details);
+            if flow.is_break() {
+                break;
+            }
+        }
+
+        Ok(passwords)
+    }
+
+    /// Generate a vector of passwords with [`rayon`].
+    #[cfg(feature = "rayon")]
+    pub fn generate_parallel(&self) -> Result<Vec<String>, GenerateError> {
+        self.generate_n_parallel(self.pass_amount)
+    }
+
+    /// Generate exactly `n` passwords with [`rayon`], ignoring
+    /// [`pass_amount`](PasswordSettings#structfield.pass_amount).
+    ///
+    /// Zero returns an empty vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    ///
+    /// assert!(settings.generate_n_parallel(0).unwrap().is_empty());
+    /// assert_eq!(settings.generate_n_parallel(1).unwrap().len(), 1);
+    /// assert_eq!(settings.generate_n_parallel(1000).unwrap().len(), 1000);
+    /// assert_eq!(settings.pass_amount, 1);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn generate_n_parallel(&self, n: usize) -> Result<Vec<String>, GenerateError> {
+        self.validate(&GenerateOptions::default())?;
+
+        let (words, picker) = IterWords::with_picker(self, &mut thread_rng(), self.seed);
+        self.parallel_from(words.get(), &picker, n)
+    }
+
+    /// Generate a password for each of the keys with [`rayon`], like
+    /// [`generate_for_keys()`](PasswordSettings::generate_for_keys).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    /// settings.seed = Some(42);
+    ///
+    /// let accounts: Vec<String> = (0..100).map(|i| format!("user{i}")).collect();
+    /// let pairs = settings.generate_for_keys_parallel(accounts.clone()).unwrap();
+    /// assert_eq!(pairs, settings.generate_for_keys(accounts).unwrap());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn generate_for_keys_parallel<K: Into<String>>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+    ) -> Result<Vec<(String, String)>, GenerateError> {
+        let keys: Vec<String> = keys.into_iter().map(Into::into).collect();
+        let passwords = self.generate_n_parallel(keys.len())?;
+        Ok(keys.into_iter().zip(passwords).collect())
+    }
+
+    /// Generate passwords with [`rayon`] while calling `on_each` with the index of every
+    /// password, like [`generate_with_callback()`](PasswordSettings::generate_with_callback).
+    ///
+    /// The callback is only called from the calling thread and in order,
+    /// so a password that's generated early might have to wait for the ones before it.
+    /// Stopping the generation lets the passwords that are already being generated finish,
+    /// but they're left out of the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::PasswordSettings;
+    /// # use std::ops::ControlFlow;
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("The quick brown fox jumps over the lazy dog");
+    /// settings.pass_amount = 100;
+    ///
+    /// let mut next = 0;
+    /// let passwords = settings
+    ///     .generate_parallel_with_callback(|i, _| {
+    ///         assert_eq!(i, next);
+    ///         next += 1;
+    ///         if next == 10 {
+    ///             ControlFlow::Break(())
+    ///         } else {
+    ///             ControlFlow::Continue(())
+    ///         }
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(passwords.len(), 10);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn generate_parallel_with_callback(
+        &self,
+        on_each: impl FnMut(usize, &str) -> ControlFlow<()>,
+    ) -> Result<Vec<String>, GenerateError> {
+        self.validate(&GenerateOptions::default())?;
+
+        let (words, picker) = IterWords::with_picker(self, &mut thread_rng(), self.seed);
+        self.parallel_in_order(words.get(), &picker, on_each)
+    }
+
+    /// Build and generate the password at `i` on the current thread,
+    /// so that nothing has to be prepared for every password beforehand.
+    #[cfg(feature = "rayon")]
+    fn generate_parallel_one(
+        &self,
+        words: Words,
+        picker: &WordPicker,
+        batch: &Option<RolledParameters>,
+        i: usize,
+    ) -> Result<PasswordDetails, GenerateError> {
+        let options = GenerateOptions::default();
+        match self.seed {
+            Some(seed) => self.generate_one(
+                words,
+                picker,
+                &options,
+                batch,
+                None,
+                &mut password_rng(seed, i),
+            ),
+            None => self.generate_one(words, picker, &options, batch, None, &mut thread_rng()),
+        }
+    }
+
+    /// Keep the password generated in parallel at `i`, or generate it again on this thread
+    /// if it's one of the `seen` passwords with [`unique`](PasswordSettings#structfield.unique),
+    /// which ends up the same as generating it in order.
+    #[cfg(feature = "rayon")]
+    fn parallel_unique(
+        &self,
+        words: Words,
+        picker: &WordPicker,
+        batch: &Option<RolledParameters>,
+        i: usize,
+        password: String,
+        seen: &mut HashSet<String>,
+    ) -> Result<String, GenerateError> {
+        if !self.unique || seen.insert(password.clone()) {
+            return Ok(password);
+        }
+        let options = GenerateOptions::default();
+        self.generate_unique(i, seen, self.seed, &mut thread_rng(), |rng| {
+            self.generate_one(words, picker, &options, batch, None, rng)
+        })
+        .map(|details| details.password)
+    }
+
+    /// Generate in parallel, but hand the passwords to `on_each` in order on this thread.
+    #[cfg(feature = "rayon")]
+    fn parallel_in_order(
+        &self,
+        words: Words,
+        picker: &WordPicker,
+        mut on_each: impl FnMut(usize, &str) -> ControlFlow<()>,
+    ) -> Result<Vec<String>, GenerateError> {
+        use rayon::prelude::*;
+        use std::{
+            collections::BTreeMap,
+            sync::{
+                atomic::{AtomicBool, Ordering},
+                mpsc::channel,
+            },
+            thread,
+        };
+
+        let batch = self.batch_roll(&GenerateOptions::default(), &mut thread_rng(), self.seed);
+        let stopped = AtomicBool::new(false);
+        let (sender, receiver) = channel();
+        let mut passwords = Vec::with_capacity(self.pass_amount);
+        let mut seen = HashSet::new();
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                (0..self.pass_amount)
+                    .into_par_iter()
+                    .for_each_with(sender, |sender, i| {
+                        if !stopped.load(Ordering::Relaxed) {
+                            sender
+                                .send((i, self.generate_parallel_one(words, picker, &batch, i)))
+                                .expect("receiver should outlive the generation");
+                        }
+                    });
+            });
+
+            // The passwords that arrived before the ones preceding them.
+            let mut early = BTreeMap::new();
+            for (i, details) in &receiver {
+                match details {
+                    Ok(details) => early.insert(i, details.password),
+                    Err(e) => {
+                        stopped.store(true, Ordering::Relaxed);
+                        return Err(e);
+                    }
+                };
+                while let Some(password) = early.remove(&passwords.len()) {
+                    let password = match self.parallel_unique(
+                        words,
+                        picker,
+                        &batch,
+                        passwords.len(),
+                        password,
+                        &mut seen,
+                    ) {
+                        Ok(password) => password,
+                        Err(e) => {
+                            stopped.store(true, Ordering::Relaxed);
+                            return Err(e);
+                        }
+                    };
+                    let flow = on_each(passwords.len(), &password);
+                    passwords.push(password);
+                    if flow.is_break() {
+                        stopped.store(true, Ordering::Relaxed);
+                        return Ok(());
+                    }
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok(passwords)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn parallel_from(
+        &self,
+        words: Words,
+        picker: &WordPicker,
+        amount: usize,
+    ) -> Result<Vec<String>, GenerateError> {
+        use rayon::prelude::*;
+
+        let batch = self.batch_roll(&GenerateOptions::default(), &mut thread_rng(), self.seed);
+
+        // Indexed, so the passwords are collected in order.
+        let passwords: Vec<String> = (0..amount)
+            .into_par_iter()
+            .map(|i| {
+                self.generate_parallel_one(words, picker, &batch, i)
+                    .map(|details| details.password)
+            })
+            .collect::<Result<_, _>>()?;
+        if !self.unique {
+            return Ok(passwords);
+        }
+
+        let mut seen = HashSet::with_capacity(amount);
+        passwords
+            .into_iter()
+            .enumerate()
+            .map(|(i, password)| {
+                self.parallel_unique(words, picker, &batch, i, password, &mut seen)
+            })
+            .collect()
+    }
+}
+
+/// Take the assembly retries spent by the password at index `i` out of the shared pool, if any.
+pub(crate) fn spend_pool(pool: &mut Option<usize>, spent: usize, i: usize) {
+    if let Some(pool) = pool {
+        let left = pool.saturating_sub(spent);
+        #[cfg(feature = "tracing")]
+        if left == 0 && *pool > 0 {
+            tracing::warn!(
+                password = i,
+                "ran out of shared assembly retries, the rest of the passwords won't retry"
+            );
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = i;
+        *pool = left;
+    }
+}
+
+/// The classic substitutions that [`leet`](PasswordSettings#structfield.leet) starts with.
+fn default_leet_substitutions() -> BTreeMap<char, String> {
+    [
+        ('a', "@"),
+        ('e', "3"),
+        ('i', "1!"),
+        ('o', "0"),
+        ('s', "$"),
+        ('t', "7"),
+    ]
+    .into_iter()
+    .map(|(letter, with)| (letter, with.to_owned()))
+    .collect()
+}
+
+/// The generator of the attempt at the password at index `i` with [`seed`](PasswordSettings#structfield.seed),
+/// which is the same as [`password_rng()`] for the first attempt.
+fn attempt_rng(seed: u64, i: usize, attempt: usize) -> StdRng {
+    let seed = seed ^ (attempt as u64).wrapping_mul(0xD6E8_FEB8_6659_FD93);
+    password_rng(seed, i)
+}
+
+/// The generator of the password at index `i` with [`seed`](PasswordSettings#structfield.seed).
+///
+/// Every index gets its own stream, so the password doesn't depend on which thread
+/// generates it or on the passwords before it.
+pub(crate) fn password_rng(seed: u64, i: usize) -> StdRng {
+    StdRng::seed_from_u64(seed ^ (i as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+/// Extract words from text exactly like [`PasswordSettings::get_words_from_str()`] does
+/// with the default settings, without needing an instance of them.
+///
+/// Words are transliterated into ASCII first, and numbers are only kept
+/// as part of the words with `keep_numbers`.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{extract_words, PasswordSettings};
+/// let text = "Der Fuß trat 3 Mal auf den Wagen42, 日本!";
+/// let words = extract_words(text, false);
+/// assert_eq!(words, ["Der", "Fuss", "trat", "Mal", "auf", "den", "Wagen", "Ri", "Ben"]);
+/// assert!(extract_words(text, true).contains(&String::from("Wagen42")));
+///
+/// let mut settings = PasswordSettings::new();
+/// settings.get_words_from_str(text);
+/// assert_eq!(settings.words(), words);
+/// ```
+pub fn extract_words(text: &str, keep_numbers: bool) -> Vec<String> {
+    let mut words = Vec::new();
+    push_legacy_words(text, &word_regex(keep_numbers), true, &mut words);
+    words
+}
+
+fn word_regex(keep_numbers: bool) -> Regex {
+    if keep_numbers {
+        Regex::new(r"\w+").unwrap()
+    } else {
+        Regex::new(r"[^\d\W]+").unwrap()
+    }
+}
+
+/// The size in bytes from which a text is transliterated in another chunk.
+const TRANSLITERATION_CHUNK: usize = 64 * 1024;
+
+/// Push the words matching `re` with [`ExtractionBackend::Legacy`].
+///
+/// The text is transliterated a chunk at a time, so that a big text isn't held
+/// twice in memory, with the same words as if it was transliterated at once.
+fn push_legacy_words(text: &str, re: &Regex, transliterate: bool, words: &mut Vec<String>) {
+    if !transliterate {
+        return push_matches(text, re, words);
+    }
+
+    for chunk in whitespace_chunks(text, TRANSLITERATION_CHUNK) {
+        if chunk.is_ascii() {
+            push_matches(chunk, re, words);
         } else {
-            unreachable!("Unexpected metadata error");
+            push_matches(&deunicode(chunk), re, words);
         }
+    }
+}
 
-        if text.is_empty() {
-            return Ok(());
+fn push_matches(text: &str, re: &Regex, words: &mut Vec<String>) {
+    for caps in re.captures_iter(text) {
+        if let Some(cap) = caps.get(0) {
+            words.push(cap.as_str().to_owned());
         }
+    }
+}
 
-        if !text.is_ascii() {
-            text = deunicode(&text);
+/// Split the text in chunks of at least `size` bytes, each ending right after
+/// an ASCII whitespace character, or at the end of the text.
+///
+/// A word never spans two chunks, and since `deunicode()` only looks at the next
+/// character to drop the space after a transliteration, nor does its output.
+fn whitespace_chunks(text: &str, size: usize) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
         }
+        // A longer run without whitespace is carried over to the chunk it ends in.
+        let end = rest.as_bytes()[size.min(rest.len())..]
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .map_or(rest.len(), |i| size + i + 1);
+        let (chunk, tail) = rest.split_at(end);
+        rest = tail;
+        Some(chunk)
+    })
+}
 
-        let re = if self.keep_numbers {
-            Regex::new(r"\w+").unwrap()
-        } else {
-            Regex::new(r"[^\d\W]+").unwrap()
-        };
+/// How many times each password can be retried, for each of the reasons to retry.
+///
+/// The same struct is used in [`PasswordDetails`] to report how much of each was used.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::PasswordSettings;
+/// let mut settings = PasswordSettings::new();
+/// settings.get_words_from_str("abcdefghij klmnopqrst");
+/// settings.length = 15..=15;
+/// settings.number_amount = 0..=0;
+/// settings.special_chars_amount = 0..=0;
+///
+/// for assembly in [0, 3, 10] {
+///     settings.retry_budget.assembly = assembly;
+///     let report = settings.generate_detailed().unwrap();
+///     let details = &report.passwords[0];
+///     assert_eq!(details.retries.assembly, assembly);
+///     assert!(details.truncated);
+///     // Dropped back to the first word.
+///     assert_eq!(details.password.len(), 10);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RetryBudget {
+    /// Times to start the word selection over when the words don't fit the length,
+    /// before giving up and doing what [`on_exhausted`](PasswordSettings#structfield.on_exhausted) says.
+    ///
+    /// If the range of the length is too small or an exact number, it'll be harder
+    /// to get a fitting set of words.
+    pub assembly: usize,
 
-        for caps in re.captures_iter(&text) {
-            if let Some(cap) = caps.get(0) {
-                self.words.push(cap.as_str().to_owned());
-            }
-        }
+    /// Times to generate the password again when it's rejected by a policy.
+    pub policy: usize,
 
-        if self.randomise {
-            self.words.shuffle(&mut thread_rng());
-        }
+    /// Times to generate the password again when it's the same as one already generated,
+    /// with [`unique`](PasswordSettings#structfield.unique).
+    pub uniqueness: usize,
+}
 
-        Ok(())
+impl Default for RetryBudget {
+    fn default() -> Self {
+        Self {
+            assembly: 10,
+            policy: 10,
+            uniqueness: 10,
+        }
     }
+}
 
-    /// Extract words from a string.
+/// What to do with a password that doesn't fit the length after running out of
+/// [assembly retries](RetryBudget::assembly), set with
+/// [`on_exhausted`](PasswordSettings#structfield.on_exhausted).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ExhaustedBehaviour {
+    /// Drop the words back to the last one that fits within the maximum length,
+    /// which leaves the password shorter than the minimum. Only when even the first
+    /// word is too long is it truncated to the maximum like with [`Truncate`](ExhaustedBehaviour::Truncate).
     ///
-    /// In case no words were extracted nothing is added and no error is given.
+    /// The inserted numbers and special characters aren't affected,
+    /// so there are no more of them to make up for the missing length.
     ///
-    /// Accepts UTF-8 characters, but translates them to ASCII for use in the password.
-    /// So if a word in another language is encountered, it will be transformed into a
-    /// kind of phonetic spelling in ASCII, and if an emoji is encountered, it will be
-    /// translated into its meaning, for example, :D would become 'grinning'.
-    pub fn get_words_from_str(&mut self, text: &str) {
-        if text.is_empty() {
-            return;
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CaseRule, PasswordSettings};
+    /// let words = ["abcdefg", "hijklmnop", "qrstuvwxyz", "ab"];
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str(&words.join(" "));
+    /// settings.length = 12..=13;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_handling = CaseRule::Never;
+    /// settings.lower_handling = CaseRule::Never;
+    /// settings.retry_budget.assembly = 0;
+    /// settings.pass_amount = 50;
+    /// settings.seed = Some(7);
+    ///
+    /// let report = settings.generate_detailed().unwrap();
+    /// assert!(report.passwords.iter().any(|details| details.truncated));
+    /// for details in report.passwords {
+    ///     assert!(details.password.len() <= 13);
+    ///     for span in &details.word_spans {
+    ///         assert!(words.contains(&&details.password[span.clone()]), "{}", details.password);
+    ///     }
+    /// }
+    ///
+    /// // Too long to fit at all, so it's cut.
+    /// settings.set_words(vec![String::from("abcdefghijklmnopqrstuvwxyz")]);
+    /// assert_eq!(settings.generate().unwrap()[0], "abcdefghijklm");
+    /// ```
+    #[default]
+    TruncateAtWord,
+
+    /// Truncate the password to the maximum length, even in the middle of a word.
+    ///
+    /// When the words fall short of the minimum length, another one is added
+    /// only to be cut, so the password is never shorter than the minimum.
+    Truncate,
+
+    /// Return [`GenerateError::LengthNotSatisfied`] instead of the password.
+    Error,
+}
+
+/// How the [assembly retries](RetryBudget::assembly) are handed out to the passwords
+/// of a call, set with [`batch_retry`](PasswordSettings#structfield.batch_retry).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum BatchRetryMode {
+    /// Each password gets [`retry_budget.assembly`](RetryBudget::assembly) retries.
+    #[default]
+    PerPassword,
+
+    /// The passwords of a call draw from this many retries in total, ignoring
+    /// [`retry_budget.assembly`](RetryBudget::assembly). Once they run out,
+    /// the rest of the passwords give up on the first set of words that doesn't fit,
+    /// as set by [`on_exhausted`](PasswordSettings#structfield.on_exhausted),
+    /// which [`PasswordDetails::truncated`] reports.
+    ///
+    /// Only the retries of the password that's kept are counted when it's
+    /// [rejected by a policy](RetryBudget::policy) and generated again.
+    /// Generating in parallel, where there's no order to share them in,
+    /// falls back to [`PerPassword`](BatchRetryMode::PerPassword).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{BatchRetryMode, PasswordSettings};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("abc defg hijkl mnopqrs tuvwxyzab");
+    /// settings.length = 13..=13;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.pass_amount = 50;
+    /// settings.batch_retry = BatchRetryMode::SharedPool(100);
+    ///
+    /// let report = settings.generate_detailed().unwrap();
+    /// let used: usize = report.passwords.iter().map(|d| d.retries.assembly).sum();
+    /// assert!(used <= 100);
+    /// ```
+    SharedPool(usize),
+}
+
+impl BatchRetryMode {
+    /// The retries to share, if any.
+    pub(crate) fn pool(self) -> Option<usize> {
+        match self {
+            BatchRetryMode::PerPassword => None,
+            BatchRetryMode::SharedPool(pool) => Some(pool),
+        }
+    }
+}
+
+/// Where a word is going in the password, passed to a
+/// [custom word transform](WordTransform::Custom).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WordContext {
+    /// The position of the word within the password, starting at 0.
+    pub index: usize,
+}
+
+/// The signature of a custom [`WordTransform`].
+pub type WordTransformFn = dyn Fn(&str, WordContext) -> String + Send + Sync;
+
+/// How each word is changed as it's selected, set with
+/// [`word_transform`](PasswordSettings#structfield.word_transform).
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{PasswordSettings, WordTransform};
+/// let mut settings = PasswordSettings::new();
+/// settings.get_words_from_str("password aeiou a rhythm Orange");
+/// settings.word_transform = Some(WordTransform::StripInnerVowels);
+/// settings.number_amount = 0..=0;
+/// settings.special_chars_amount = 0..=0;
+/// settings.length = 10..=40;
+/// settings.pass_amount = 50;
+///
+/// let stripped = ["psswrd", "a", "rhythm", "orng"];
+/// for details in settings.generate_detailed().unwrap().passwords {
+///     for span in &details.word_spans {
+///         let word = details.password[span.clone()].to_lowercase();
+///         assert!(stripped.contains(&word.as_str()), "{}", details.password);
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum WordTransform {
+    /// Drop the vowels after the first character, so `password` becomes `psswrd`
+    /// and `orange` becomes `orng`. Only the ASCII vowels are dropped and a word is
+    /// never left empty, so `aeiou` becomes `a` and `a` stays as it is.
+    StripInnerVowels,
+    /// A closure, usually set with [`PasswordSettings::set_word_transform()`].
+    Custom(Arc<WordTransformFn>),
+}
+
+// The closure is only ever called, so the settings are left as they were if it panics.
+impl UnwindSafe for WordTransform {}
+impl RefUnwindSafe for WordTransform {}
+
+impl PartialEq for WordTransform {
+    /// Closures are only equal to their clones.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::StripInnerVowels, Self::StripInnerVowels) => true,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
         }
+    }
+}
 
-        let converted;
-        let ascii = match text {
-            ascii if ascii.is_ascii() => ascii,
-            utf8 => {
-                converted = deunicode(utf8);
-                &converted
+impl WordTransform {
+    pub(crate) fn apply(&self, word: &str, index: usize) -> String {
+        match self {
+            WordTransform::StripInnerVowels => {
+                let mut chars = word.chars();
+                let mut stripped: String = chars.next().into_iter().collect();
+                stripped.extend(chars.filter(|c| !"aeiouAEIOU".contains(*c)));
+                if stripped.is_empty() {
+                    word.to_string()
+                } else {
+                    stripped
+                }
             }
-        };
+            WordTransform::Custom(transform) => transform(word, WordContext { index }),
+        }
+    }
+}
 
-        let re = if self.keep_numbers {
-            Regex::new(r"\w+").unwrap()
-        } else {
-            Regex::new(r"[^\d\W]+").unwrap()
-        };
+impl fmt::Debug for WordTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordTransform::StripInnerVowels => f.write_str("StripInnerVowels"),
+            WordTransform::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// The signature of a custom [`WordWeighting`].
+pub type WordWeightingFn = dyn Fn(&str) -> f64 + Send + Sync;
+
+/// The probability of keeping each word that's selected, set with
+/// [`word_weighting`](PasswordSettings#structfield.word_weighting).
+#[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[non_exhaustive]
+pub enum WordWeighting {
+    /// Keep every word. The default.
+    #[default]
+    Uniform,
+    /// Keep words of at least 6 characters, and shorter ones in proportion to their length,
+    /// so `a` is only kept a sixth of the time and `to` a third.
+    ByLength,
+    /// A closure giving the probability between 0 and 1 of keeping a word,
+    /// with values outside of it clamped and NaN counting as 1.
+    ///
+    /// Isn't serialised, so serialising the settings with it errors.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Arc<WordWeightingFn>),
+}
+
+// The closure is only ever called, so the settings are left as they were if it panics.
+impl UnwindSafe for WordWeighting {}
+impl RefUnwindSafe for WordWeighting {}
+
+impl PartialEq for WordWeighting {
+    /// Closures are only equal to their clones.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Uniform, Self::Uniform) | (Self::ByLength, Self::ByLength) => true,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
 
-        for caps in re.captures_iter(ascii) {
-            if let Some(cap) = caps.get(0) {
-                self.words.push(cap.as_str().to_owned());
+impl WordWeighting {
+    /// The probability of keeping the word, or `None` if every word is kept.
+    pub(crate) fn weight(&self, word: &str) -> Option<f64> {
+        match self {
+            WordWeighting::Uniform => None,
+            WordWeighting::ByLength => Some((word.chars().count() as f64 / 6.0).min(1.0)),
+            WordWeighting::Custom(weight) => {
+                let weight = weight(word);
+                Some(if weight.is_nan() {
+                    1.0
+                } else {
+                    weight.clamp(0.0, 1.0)
+                })
             }
         }
+    }
+}
 
-        if self.randomise {
-            self.words.shuffle(&mut thread_rng());
+impl fmt::Debug for WordWeighting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordWeighting::Uniform => f.write_str("Uniform"),
+            WordWeighting::ByLength => f.write_str("ByLength"),
+            WordWeighting::Custom(_) => f.write_str("Custom(..)"),
         }
     }
+}
 
-    /// Get a reference to the vector of words.
-    pub fn words(&self) -> &[String] {
-        &self.words
+/// The signature of a custom [`WordValidator`].
+pub type WordValidatorFn = dyn Fn(&str) -> bool + Send + Sync;
+
+/// Which extracted words are stored, set with
+/// [`word_validator`](PasswordSettings#structfield.word_validator).
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{PasswordSettings, WordValidator};
+/// # use std::sync::Arc;
+/// let mut settings = PasswordSettings::new();
+/// settings.word_validator = Some(WordValidator::Custom(Arc::new(|word| word.len() > 2)));
+/// settings.get_words_from_str("an old oak is on the hill");
+/// assert_eq!(settings.words(), ["old", "oak", "the", "hill"]);
+/// ```
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum WordValidator {
+    /// Reject common English profanity and slurs, ignoring case.
+    /// Only a basic blocklist, so combine it with a custom one for anything more.
+    #[cfg(feature = "profanity")]
+    Profanity,
+    /// A closure returning whether to keep a word,
+    /// usually set with [`PasswordSettings::set_word_validator()`].
+    Custom(Arc<WordValidatorFn>),
+}
+
+// The closure is only ever called, so the settings are left as they were if it panics.
+impl UnwindSafe for WordValidator {}
+impl RefUnwindSafe for WordValidator {}
+
+impl PartialEq for WordValidator {
+    /// Closures are only equal to their clones.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            #[cfg(feature = "profanity")]
+            (Self::Profanity, Self::Profanity) => true,
+            (Self::Custom(a), Self::Custom(b)) => Arc::ptr_eq(a, b),
+            #[cfg(feature = "profanity")]
+            _ => false,
+        }
     }
+}
 
-    /// Clear the vector of words.
-    pub fn clear_words(&mut self) {
-        self.words.clear();
+impl WordValidator {
+    pub(crate) fn accepts(&self, word: &str) -> bool {
+        match self {
+            #[cfg(feature = "profanity")]
+            WordValidator::Profanity => !crate::profanity::is_profane(word),
+            WordValidator::Custom(validator) => validator(word),
+        }
     }
+}
 
-    /// Remove a word at index.
+impl fmt::Debug for WordValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "profanity")]
+            WordValidator::Profanity => f.write_str("Profanity"),
+            WordValidator::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// The way [`PasswordSettings`] extracts words from text.
+///
+/// Both backends read the files of a directory the same way, they only differ in how
+/// the text of each file is split into words.
+///
+/// # Example
+///
+/// The same text gives different words, so switch over deliberately.
+///
+/// ```
+/// # use genrepass::{CharFilter, Deunicode, ExtractionBackend, PasswordSettings, Split};
+/// let corpus = "Don't panic! Café au lait costs 3.50, or 2 for 6.";
+///
+/// let mut legacy = PasswordSettings::new();
+/// legacy.get_words_from_str(corpus);
+/// assert_eq!(
+///     legacy.words(),
+///     ["Don", "t", "panic", "Cafe", "au", "lait", "costs", "or", "for"]
+/// );
+///
+/// legacy.clear_words();
+/// legacy.keep_numbers = true;
+/// legacy.get_words_from_str(corpus);
+/// assert_eq!(
+///     legacy.words(),
+///     ["Don", "t", "panic", "Cafe", "au", "lait", "costs", "3", "50", "or", "2", "for", "6"]
+/// );
+///
+/// let mut lexicon = PasswordSettings::new();
+/// lexicon.extraction = ExtractionBackend::Lexicon {
+///     split: Split::UnicodeWords,
+///     deunicode: Deunicode::Deactivated,
+///     filter: CharFilter::Unicode,
+/// };
+/// lexicon.get_words_from_str(corpus);
+/// assert_eq!(
+///     lexicon.words(),
+///     ["Don't", "panic", "Café", "au", "lait", "costs", "3.50", "or", "2", "for", "6"]
+/// );
+///
+/// // The closest to the legacy backend.
+/// lexicon.clear_words();
+/// lexicon.extraction = ExtractionBackend::Lexicon {
+///     split: Split::UnicodeWords,
+///     deunicode: Deunicode::BeforeSplitting,
+///     filter: CharFilter::AsciiWithoutDigitsOrPunctuation,
+/// };
+/// lexicon.get_words_from_str(corpus);
+/// assert_eq!(
+///     lexicon.words(),
+///     ["Dont", "panic", "Cafe", "au", "lait", "costs", "or", "for"]
+/// );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ExtractionBackend {
+    /// Every run of letters, or letters and digits with
+    /// [`keep_numbers`](PasswordSettings#structfield.keep_numbers), is a word,
+    /// so something like `don't` becomes two words.
+    /// The text is transliterated to ASCII beforehand unless
+    /// [`transliterate`](PasswordSettings#structfield.transliterate) is turned off.
+    #[default]
+    Legacy,
+
+    /// The same pipeline as [`Lexicon::extract_words()`],
+    /// ignoring [`keep_numbers`](PasswordSettings#structfield.keep_numbers)
+    /// and [`transliterate`](PasswordSettings#structfield.transliterate).
+    Lexicon {
+        /// See [`Lexicon::split`].
+        split: Split,
+        /// See [`Lexicon::deunicode`].
+        deunicode: Deunicode,
+        /// The characters to keep in each word.
+        filter: CharFilter,
+    },
+}
+
+/// How the size of the password is decided.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum LengthMode {
+    /// Words are added until the password is within
+    /// [`length`](PasswordSettings#structfield.length),
+    /// measured in [`length_unit`](PasswordSettings#structfield.length_unit).
+    #[default]
+    Units,
+
+    /// An amount of words picked from the range, like for a diceware passphrase,
+    /// whatever their length.
+    ///
+    /// The numbers and special characters are inserted on top of the words,
+    /// and none of them are dropped for not fitting.
+    /// Since the password is never reset or truncated,
+    /// [`length`](PasswordSettings#structfield.length),
+    /// [`continue_probability`](PasswordSettings::set_continue_probability),
+    /// [`skip_oversized_words`](PasswordSettings#structfield.skip_oversized_words) and
+    /// [`no_wrap`](PasswordSettings#structfield.no_wrap) are ignored.
     ///
     /// # Panics
     ///
-    /// Panics if `index` is out of bounds.
-    pub fn remove_word_at(&mut self, index: usize) {
-        self.words.remove(index);
-    }
+    /// Panics upon generation if the inclusive range is empty (i.e. end < start).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{CaseRule, LengthMode, PasswordSettings};
+    /// let words = ["correct", "horse", "battery", "staple", "wizard", "quietly"];
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str(&words.join(" "));
+    /// settings.length_mode = LengthMode::Words(4..=4);
+    /// settings.separator = " ".to_string();
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_handling = CaseRule::Never;
+    /// settings.pass_amount = 50;
+    ///
+    /// for password in settings.generate().unwrap() {
+    ///     let chosen: Vec<&str> = password.split(' ').collect();
+    ///     assert_eq!(chosen.len(), 4, "{password}");
+    ///     assert!(chosen.iter().all(|word| words.contains(word)), "{password}");
+    /// }
+    ///
+    /// settings.length_mode = LengthMode::Words(3..=5);
+    /// settings.number_amount = 2..=2;
+    /// settings.special_chars_amount = 2..=2;
+    /// for details in settings.generate_detailed().unwrap().passwords {
+    ///     let amount = details.rolled.words.unwrap();
+    ///     assert!((3..=5).contains(&amount));
+    ///     assert_eq!(details.password.split(' ').count(), amount);
+    ///     assert_eq!(details.composition.digits, 2);
+    ///     assert_eq!(details.composition.special, 2);
+    /// }
+    /// ```
+    Words(RangeInclusive<usize>),
+}
 
-    /// Generate a vector of passwords.
+/// The unit in which the length of the password is measured.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum LengthUnit {
+    /// Every Unicode scalar value counts as one, so `é` written as `e` followed by
+    /// a combining accent counts as two.
+    #[default]
+    Chars,
+
+    /// Every [extended grapheme cluster](http://www.unicode.org/reports/tr29/#Grapheme_Cluster_Boundaries)
+    /// counts as one, which is closer to what's perceived as a single character.
     ///
-    /// # Panics
+    /// # Example
     ///
-    /// Panics if any of the inclusive ranges are empty (i.e. end < start).
-    pub fn generate(&self) -> Result<Vec<String>, NotEnoughWordsError> {
-        ensure!(
-            !self.words.is_empty() && self.words.len() > 1,
-            NotEnoughWordsSnafu
-        );
+    /// ```
+    /// # use genrepass::{CaseRule, LengthUnit, PasswordSettings};
+    /// use unicode_segmentation::UnicodeSegmentation;
+    ///
+    /// // "é" written with a combining accent and "각" written with conjoining jamo.
+    /// let clusters = ["e\u{301}", "\u{1100}\u{1161}\u{11A8}"];
+    ///
+    /// let mut settings = PasswordSettings::new();
+    /// settings.transliterate = false;
+    /// settings.get_words_from_str("e\u{301}e\u{301}e\u{301} \u{1100}\u{1161}\u{11A8}e\u{301}");
+    /// settings.length_unit = LengthUnit::Graphemes;
+    /// settings.length = 5..=5;
+    /// settings.retry_budget.assembly = 0;
+    /// settings.number_amount = 0..=0;
+    /// settings.special_chars_amount = 0..=0;
+    /// settings.upper_handling = CaseRule::Never;
+    /// settings.lower_handling = CaseRule::Never;
+    /// settings.pass_amount = 100;
+    ///
+    /// for password in settings.generate().unwrap() {
+    ///     assert!(password.graphemes(true).count() <= 5);
+    ///     assert!(password.graphemes(true).all(|g| clusters.contains(&g)));
+    /// }
+    ///
+    /// // Even when counting chars, truncation doesn't split the clusters.
+    /// settings.length_unit = LengthUnit::Chars;
+    /// for password in settings.generate().unwrap() {
+    ///     assert!(password.chars().count() <= 5);
+    ///     assert!(password.graphemes(true).all(|g| clusters.contains(&g)));
+    /// }
+    /// ```
+    Graphemes,
+}
 
-        let mut passwords = Vec::new();
+/// When letters are uppercased or lowercased to reach the amount rolled from
+/// [`upper_amount`](PasswordSettings#structfield.upper_amount) or
+/// [`lower_amount`](PasswordSettings#structfield.lower_amount), set with
+/// [`upper_handling`](PasswordSettings#structfield.upper_handling) and
+/// [`lower_handling`](PasswordSettings#structfield.lower_handling).
+///
+/// The letters already in that case count towards the amount.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CaseRule {
+    /// Only when there are no letters in that case at all,
+    /// so the original case is kept for the most part.
+    #[default]
+    Auto,
+
+    /// Whenever there are fewer letters in that case than the amount.
+    Force,
+
+    /// Never, keeping the case of the words as it is.
+    Never,
+}
 
-        for _ in 0..self.pass_amount {
-            passwords.push(Password::new(self).generate(self));
+impl CaseRule {
+    /// The rule after setting the deprecated `force_*` flag, which `Never` wins over.
+    fn forced(self, force: bool) -> Self {
+        match (self, force) {
+            (CaseRule::Auto, true) => CaseRule::Force,
+            (CaseRule::Force, false) => CaseRule::Auto,
+            (rule, _) => rule,
         }
+    }
 
-        Ok(passwords)
+    /// The rule after setting the deprecated `dont_*` flag.
+    fn never(self, never: bool) -> Self {
+        match (self, never) {
+            (_, true) => CaseRule::Never,
+            (CaseRule::Never, false) => CaseRule::Auto,
+            (rule, false) => rule,
+        }
     }
+}
 
-    /// Generate a vector of passwords with [`rayon`].
-    ///
-    /// # Panics
-    ///
-    /// Panics if any of the inclusive ranges are empty (i.e. end < start).
-    #[cfg(feature = "rayon")]
-    pub fn generate_parallel(&self) -> Result<Vec<String>, NotEnoughWordsError> {
-        use rayon::prelude::*;
-        use std::sync::mpsc::channel;
+/// Where the numbers and special characters go, set with
+/// [`insert_placement`](PasswordSettings#structfield.insert_placement).
+///
+/// When grouped, the numbers come before the special characters, each in the order
+/// they were picked, and they're [inserted](PasswordSettings#structfield.insert_at_boundaries)
+/// or [replace](PasswordSettings#structfield.replace) the characters right there.
+/// It doesn't change where [`leet`](PasswordSettings#structfield.leet) substitutes letters.
+///
+/// # Example
+///
+/// ```
+/// # use genrepass::{InsertPlacement, PasswordSettings};
+/// let mut settings = PasswordSettings::new();
+/// settings.get_words_from_str("correct horse battery staple");
+/// settings.insert_placement = InsertPlacement::Suffix;
+/// settings.number_amount = 2..=2;
+/// settings.special_chars_amount = 1..=1;
+/// settings.pass_amount = 50;
+///
+/// for password in settings.generate().unwrap() {
+///     let (words, inserted) = password.split_at(password.len() - 3);
+///     assert!(words.chars().all(char::is_alphabetic), "{password}");
+///     assert!(inserted[..2].chars().all(|c| c.is_ascii_digit()), "{password}");
+///     assert!(!inserted[2..].starts_with(char::is_alphanumeric), "{password}");
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum InsertPlacement {
+    /// At random positions, or at the boundaries of the words with
+    /// [`insert_at_boundaries`](PasswordSettings#structfield.insert_at_boundaries).
+    #[default]
+    Random,
 
-        ensure!(
-            !self.words.is_empty() && self.words.len() > 1,
-            NotEnoughWordsSnafu
-        );
+    /// All together at the end.
+    Suffix,
 
-        let mut password_settings = Vec::new();
+    /// All together at the start, or right after the first letter with
+    /// [`must_start_with_letter`](PasswordSettings#structfield.must_start_with_letter).
+    Prefix,
+}
 
-        for _ in 0..self.pass_amount {
-            password_settings.push(Password::new(self));
-        }
+/// How the case of the words is changed, set with
+/// [`word_case`](PasswordSettings#structfield.word_case).
+///
+/// [Capitalise exceptions](PasswordSettings::add_capitalise_exception) are written
+/// as they are with [`WordCase::Capitalised`] and [`WordCase::Camel`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum WordCase {
+    /// The words are kept as they were extracted.
+    #[default]
+    AsIs,
 
-        let (sender, receiver) = channel();
+    /// The first letter of every word is uppercased, like `CorrectHorseBattery`.
+    Capitalised,
 
-        password_settings
-            .into_par_iter()
-            .for_each_with(sender, |sender, mut password| {
-                sender
-                    .send(password.generate(self))
-                    .expect("receiver should still be alive until all passwords are generated");
-            });
+    /// The first word is lowercased and the first letter of every other word
+    /// is uppercased, like `correctHorseBattery`.
+    Camel,
 
-        let mut passwords = Vec::new();
+    /// Every word is lowercased, like `correct-horse-battery`.
+    Lower,
+
+    /// Every word is uppercased, like `CORRECT_HORSE_BATTERY`.
+    Upper,
+}
+
+/// The way the words of a password are selected.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum WordSelection {
+    /// Consecutive words are taken starting from a random one,
+    /// which keeps the password readable since it's part of the source text.
+    #[default]
+    Consecutive,
+
+    /// Every word is picked at random.
+    Uniform,
+}
 
-        while let Ok(value) = receiver.try_recv() {
-            passwords.push(value);
+/// Settings that don't prevent generation but are probably a mistake.
+///
+/// See [`PasswordSettings::warnings()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsWarning {
+    /// [`WordCase::Capitalised`] or [`WordCase::Camel`] is set along with
+    /// [`CaseRule::Never`] for [`upper_handling`](PasswordSettings#structfield.upper_handling), which only prevents
+    /// the case adjustments, so every word still gets capitalised.
+    CapitaliseWithDontUpper,
+    /// The maximum of [`upper_amount`](PasswordSettings#structfield.upper_amount)
+    /// is larger than the maximum of [`length`](PasswordSettings#structfield.length),
+    /// so it can never be reached.
+    UpperAmountExceedsLength {
+        /// The maximum amount of uppercase characters.
+        upper: usize,
+        /// The maximum length of the password.
+        length: usize,
+    },
+    /// The special characters contain duplicates, which makes those more likely to be picked.
+    DuplicateSpecialChars {
+        /// Each of the characters that appear more than once.
+        duplicates: String,
+    },
+    /// The [assembly retries](RetryBudget::assembly) are zero,
+    /// so the first password that doesn't fit the length gets truncated.
+    ZeroResetAmount,
+    /// With [`replace`](PasswordSettings#structfield.replace), the most numbers and special
+    /// characters there can be are more than the
+    /// [`max_replaced_fraction`](PasswordSettings#structfield.max_replaced_fraction)
+    /// of the minimum length, so the shortest passwords are mostly noise.
+    ReplaceOverwhelmsWords {
+        /// The most characters that can be replaced.
+        inserts: usize,
+        /// The minimum length of the password.
+        min_len: usize,
+    },
+    /// The [fingerprint of the words](PasswordSettings::words_fingerprint) that was
+    /// serialised along with the settings doesn't match the words they have,
+    /// so they were changed outside of the settings and passwords generated with a
+    /// [`seed`](PasswordSettings#structfield.seed) won't be the same.
+    WordsFingerprintMismatch {
+        /// The fingerprint that was serialised.
+        saved: u64,
+        /// The fingerprint of the words.
+        current: u64,
+    },
+    /// [`InsertPlacement::Prefix`] is set along with
+    /// [`must_start_with_letter`](PasswordSettings#structfield.must_start_with_letter),
+    /// so the numbers and special characters go after the first letter instead.
+    PrefixAfterFirstLetter,
+}
+
+impl fmt::Display for SettingsWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsWarning::CapitaliseWithDontUpper => write!(
+                f,
+                "words are still capitalised when uppercasing is turned off"
+            ),
+            SettingsWarning::UpperAmountExceedsLength { upper, length } => write!(
+                f,
+                "amount of uppercase characters ({upper}) is larger than the length ({length})"
+            ),
+            SettingsWarning::DuplicateSpecialChars { duplicates } => {
+                write!(f, "special characters contain duplicates: {duplicates}")
+            }
+            SettingsWarning::ZeroResetAmount => write!(
+                f,
+                "assembly retries are zero, so passwords that don't fit get truncated right away"
+            ),
+            SettingsWarning::ReplaceOverwhelmsWords { inserts, min_len } => write!(
+                f,
+                "up to {inserts} of the {min_len} characters of the shortest passwords get replaced"
+            ),
+            SettingsWarning::WordsFingerprintMismatch { saved, current } => write!(
+                f,
+                "the words have the fingerprint {current:016x} instead of the saved {saved:016x}"
+            ),
+            SettingsWarning::PrefixAfterFirstLetter => write!(
+                f,
+                "numbers and special characters go after the first letter when it must be a letter"
+            ),
         }
+    }
+}
 
-        Ok(passwords)
+/// A named group of special characters with its own amount to insert,
+/// added with [`PasswordSettings::add_special_group()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SpecialGroup {
+    pub(crate) name: String,
+    pub(crate) chars: String,
+    pub(crate) amount: RangeInclusive<usize>,
+}
+
+impl SpecialGroup {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn chars(&self) -> &str {
+        &self.chars
+    }
+
+    pub fn amount(&self) -> &RangeInclusive<usize> {
+        &self.amount
+    }
+
+    /// The characters without duplicates.
+    pub(crate) fn distinct_chars(&self) -> Vec<char> {
+        let mut chars: Vec<char> = Vec::new();
+        for c in self.chars.chars() {
+            if !chars.contains(&c) {
+                chars.push(c);
+            }
+        }
+        chars
     }
 }
 
-/// When non-ASCII characters are found during [`PasswordSettings::set_special_chars()`].
+/// The errors that generating passwords can return.
+#[derive(Debug, Snafu)]
+pub enum GenerateError {
+    /// See [`NotEnoughWordsError`].
+    #[snafu(context(false), display("{source}"))]
+    NotEnoughWords {
+        /// The underlying error.
+        source: NotEnoughWordsError,
+    },
+
+    /// See [`NotEnoughDistinctInsertsError`].
+    #[snafu(context(false), display("{source}"))]
+    NotEnoughDistinctInserts {
+        /// The underlying error.
+        source: NotEnoughDistinctInsertsError,
+    },
+
+    /// See [`EmptyRangeError`].
+    #[snafu(context(false), display("{source}"))]
+    EmptyRange {
+        /// The underlying error.
+        source: EmptyRangeError,
+    },
+
+    /// See [`GroupsExceedLengthError`].
+    #[snafu(context(false), display("{source}"))]
+    GroupsExceedLength {
+        /// The underlying error.
+        source: GroupsExceedLengthError,
+    },
+
+    /// See [`InsertZoneTooSmallError`].
+    #[snafu(context(false), display("{source}"))]
+    InsertZoneTooSmall {
+        /// The underlying error.
+        source: InsertZoneTooSmallError,
+    },
+
+    /// See [`PolicyRejectedError`].
+    #[snafu(context(false), display("{source}"))]
+    PolicyRejected {
+        /// The underlying error.
+        source: PolicyRejectedError,
+    },
+
+    /// See [`LengthNotSatisfiedError`].
+    #[snafu(context(false), display("{source}"))]
+    LengthNotSatisfied {
+        /// The underlying error.
+        source: LengthNotSatisfiedError,
+    },
+
+    /// See [`TimedOutError`].
+    #[snafu(context(false), display("{source}"))]
+    TimedOut {
+        /// The underlying error.
+        source: TimedOutError,
+    },
+
+    /// See [`CannotSatisfyUniquenessError`].
+    #[snafu(context(false), display("{source}"))]
+    CannotSatisfyUniqueness {
+        /// The underlying error.
+        source: CannotSatisfyUniquenessError,
+    },
+}
+
+/// When a password kept being rejected, such as for containing one of the
+/// [context exclusions](PasswordSettings::add_context_exclusion),
+/// after running out of [policy retries](RetryBudget::policy).
+#[derive(Debug, Snafu)]
+#[snafu(display("the password was still rejected after {retries} retries"))]
+pub struct PolicyRejectedError {
+    pub(crate) retries: usize,
+}
+
+/// When a password still didn't fit the length after running out of
+/// [assembly retries](RetryBudget::assembly), with [`ExhaustedBehaviour::Error`].
+#[derive(Debug, Snafu)]
+#[snafu(display(
+    "the words didn't fit a length of {} to {} after {resets} retries",
+    length.start(),
+    length.end()
+))]
+pub struct LengthNotSatisfiedError {
+    pub(crate) length: RangeInclusive<usize>,
+    pub(crate) resets: usize,
+}
+
+/// When [`PasswordSettings::try_generate()`] was still assembling a password at its deadline.
+#[derive(Debug, Snafu)]
+#[snafu(display("ran out of time with {finished} passwords finished after {resets} retries"))]
+pub struct TimedOutError {
+    /// The amount of passwords finished before the deadline.
+    pub finished: usize,
+    /// The [assembly retries](RetryBudget::assembly) of every password, including the unfinished one.
+    pub resets: usize,
+}
+
+/// When a password kept being one that was already generated in the same call,
+/// with [`unique`](PasswordSettings#structfield.unique), after running out of
+/// [uniqueness retries](RetryBudget::uniqueness).
+#[derive(Debug, Snafu)]
+#[snafu(display(
+    "only {distinct} distinct passwords could be generated, even after {retries} retries"
+))]
+pub struct CannotSatisfyUniquenessError {
+    pub(crate) distinct: usize,
+    pub(crate) retries: usize,
+}
+
+/// When there are [special groups](PasswordSettings::add_special_group), but the most
+/// numbers and special characters that could be inserted don't fit the minimum length.
+#[derive(Debug, Snafu)]
+#[snafu(display(
+    "up to {needed} numbers and special characters can't fit in a length of {length}"
+))]
+pub struct GroupsExceedLengthError {
+    needed: usize,
+    length: usize,
+}
+
+/// When the [`insert_zone`](PasswordSettings#structfield.insert_zone) is smaller than
+/// the fewest numbers and special characters to replace in it.
+#[derive(Debug, Snafu)]
+#[snafu(display(
+    "at least {needed} numbers and special characters can't be replaced within {zone} characters"
+))]
+pub struct InsertZoneTooSmallError {
+    needed: usize,
+    zone: usize,
+}
+
+/// When non-ASCII characters are found during [`PasswordSettings::set_special_chars()`]
+/// or [`PasswordSettings::set_special_sequences()`].
 #[derive(Debug, Snafu)]
 #[snafu(display("non-ASCII special characters aren't allowed for insertables"))]
 pub struct NonAsciiSpecialCharsError;
 
-/// When [`PasswordSettings`] holds either one or zero words.
+/// When the probability given to [`PasswordSettings::set_continue_probability()`]
+/// isn't between 0.0 and 1.0.
+#[derive(Debug, Snafu)]
+#[snafu(display("{probability} isn't a probability between 0 and 1"))]
+pub struct InvalidProbabilityError {
+    probability: f64,
+}
+
+/// When there are no words to generate passwords from.
+///
+/// A single word is enough, since it's repeated for as long as it fits.
+///
+/// # Example
 ///
-/// The reason one word isn't allowed is due to the use of [`std::iter::Peekable`].
+/// ```
+/// # use genrepass::{GenerateError, PasswordSettings, WordSelection};
+/// let mut settings = PasswordSettings::new();
+/// assert!(matches!(
+///     settings.generate(),
+///     Err(GenerateError::NotEnoughWords { .. })
+/// ));
+///
+/// settings.get_words_from_str("word");
+/// settings.length = 16..=16;
+/// settings.number_amount = 0..=0;
+/// settings.special_chars_amount = 0..=0;
+/// settings.upper_amount = 0..=0;
+/// settings.pass_amount = 10;
+/// for selection in [WordSelection::Consecutive, WordSelection::Uniform] {
+///     settings.word_selection = selection;
+///     for no_wrap in [false, true] {
+///         settings.no_wrap = no_wrap;
+///         for password in settings.generate().unwrap() {
+///             assert_eq!(password, "wordwordwordword");
+///         }
+///     }
+/// }
+///
+/// // Doesn't fit evenly, so it stops at the last whole one.
+/// settings.length = 10..=10;
+/// let details = &settings.generate_detailed().unwrap().passwords[0];
+/// assert_eq!(details.password, "wordword");
+/// assert!(details.truncated);
+/// ```
 #[derive(Debug, Snafu)]
 #[snafu(display("not enough words for password generation"))]
 pub struct NotEnoughWordsError;
+
+/// When [`distinct_inserts`](PasswordSettings#structfield.distinct_inserts) is set,
+/// but more numbers or special characters are requested than there are distinct ones.
+#[derive(Debug, Snafu)]
+#[snafu(display(
+    "can't insert {requested} distinct characters when only {available} are available"
+))]
+pub struct NotEnoughDistinctInsertsError {
+    /// The upper end of the requested amount.
+    pub requested: usize,
+    /// The amount of distinct characters available.
+    pub available: usize,
+}
+
+/// When one of the ranges of [`PasswordSettings`] is empty (i.e. end < start),
+/// as found before generating or by [`PasswordSettings::can_generate()`].
+#[derive(Debug, Snafu)]
+#[snafu(display("{field} is an empty range"))]
+pub struct EmptyRangeError {
+    /// The name of the field with the empty range.
+    pub field: &'static str,
+}
+
+/// `log2(Σ 2^bits)`, without leaving the logarithms to keep from overflowing.
+fn log2_sum(bits: impl Iterator<Item = f64>) -> f64 {
+    let bits: Vec<f64> = bits.collect();
+    let max = bits.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if max == f64::NEG_INFINITY {
+        return 0.0;
+    }
+    max + bits.iter().map(|b| (b - max).exp2()).sum::<f64>().log2()
+}
+
+/// The bits of stopping at one of `boundaries + 1` places,
+/// going past each of them with the probability `p`.
+fn stop_bits(p: f64, boundaries: f64) -> f64 {
+    if p <= 0.0 || p >= 1.0 {
+        return 0.0;
+    }
+    let mut bits = 0.0;
+    let mut reached = 1.0;
+    let mut boundary = 0.0;
+    // Past a point the probabilities are too small to add anything.
+    while boundary < boundaries && reached > 1e-12 {
+        let stop = reached * (1.0 - p);
+        bits -= stop * stop.log2();
+        reached *= p;
+        boundary += 1.0;
+    }
+    if boundary >= boundaries {
+        bits -= reached * reached.log2();
+    }
+    bits
+}
+
+/// `log2(C(n, k))`, the ways of picking `k` out of `n`.
+fn log2_binomial(n: usize, k: usize) -> f64 {
+    (0..k)
+        .map(|i| ((n - i) as f64 / (i + 1) as f64).log2())
+        .sum()
+}
+
+/// The amount of different characters in the string, counted without allocating.
+fn distinct_char_count(s: &str) -> usize {
+    s.char_indices()
+        .filter(|&(i, c)| !s[..i].contains(c))
+        .count()
+}
@@ -0,0 +1,96 @@
+/// Identifies one of the [`Source`]s of the words in
+/// [`PasswordSettings`](crate::PasswordSettings).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SourceId(pub(crate) usize);
+
+impl SourceId {
+    /// The index of the source in [`PasswordSettings::sources()`](crate::PasswordSettings::sources).
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// A text from which words were extracted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Source {
+    /// A name to tell the source apart, which is the path when loading from one.
+    pub label: String,
+
+    /// How likely the words from this source are to be selected compared to other sources.
+    ///
+    /// See [`PasswordSettings::set_source_weight()`](crate::PasswordSettings::set_source_weight).
+    pub weight: u32,
+
+    /// A hash of the text the words were extracted from, to tell when the same text
+    /// is loaded again with [`dedupe_sources`](crate::PasswordSettings#structfield.dedupe_sources).
+    ///
+    /// It's `None` for the words given to
+    /// [`set_words()`](crate::PasswordSettings::set_words).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub content_hash: Option<u64>,
+}
+
+/// What happened to a text that words were extracted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceLoad {
+    /// The words were added as a new source.
+    Added(SourceId),
+
+    /// The same text was already loaded as this source, so it was skipped with
+    /// [`dedupe_sources`](crate::PasswordSettings#structfield.dedupe_sources).
+    AlreadyLoaded(SourceId),
+}
+
+impl SourceLoad {
+    /// The id of the source with the words of the text, whether it was just added or not.
+    pub fn id(&self) -> SourceId {
+        match *self {
+            SourceLoad::Added(id) | SourceLoad::AlreadyLoaded(id) => id,
+        }
+    }
+}
+
+/// How the words from different sources are mixed together for generation.
+///
+/// The mixing is only applied to a view of the words at generation time,
+/// so the stored order is never changed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum SourceMixing {
+    /// Words are used in the order they were loaded, one source after the other.
+    ///
+    /// Since consecutive words are selected, each password will
+    /// most likely be made from the words of only one source.
+    #[default]
+    Concatenate,
+
+    /// Words are taken from each source in turn, one at a time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{PasswordSettings, SourceMixing};
+    /// let mut settings = PasswordSettings::new();
+    /// let journal = settings.get_words_from_str("dear diary today was long").unwrap().id();
+    /// let lyrics = settings.get_words_from_str("never gonna give you up").unwrap().id();
+    /// settings.set_source_label(journal, "journal");
+    /// settings.set_source_label(lyrics, "lyrics");
+    /// settings.source_mixing = SourceMixing::RoundRobin;
+    ///
+    /// assert_eq!(
+    ///     settings.mixed_words(),
+    ///     ["dear", "never", "diary", "gonna", "today", "give", "was", "you", "long", "up"]
+    /// );
+    /// // The stored order stays the same.
+    /// assert_eq!(settings.words()[..3], ["dear", "diary", "today"]);
+    /// ```
+    RoundRobin,
+
+    /// Words from all the sources are shuffled together on every generation.
+    Shuffled,
+}
@@ -0,0 +1,246 @@
+use crate::settings::{BatchRetryMode, LengthMode, PasswordSettings};
+use snafu::{ensure, ResultExt, Snafu};
+
+/// Most passwords that can be generated with a single call.
+const MAX_PASS_AMOUNT: usize = 10_000;
+/// Longest a password can be, which also limits the amounts of characters to insert.
+const MAX_LENGTH: usize = 1024;
+/// Most words that can be stored.
+const MAX_WORDS: usize = 1_000_000;
+/// Most bytes the words can add up to.
+const MAX_WORD_BYTES: usize = 64 * 1024 * 1024;
+/// Most special characters, and separately, the most special sequences.
+const MAX_SPECIAL_CHARS: usize = 256;
+/// Most times a password can be retried for each reason.
+const MAX_RETRIES: usize = 1000;
+/// Most context exclusions, and separately, the most capitalise exceptions.
+const MAX_EXCLUSIONS: usize = 256;
+
+impl PasswordSettings {
+    /// Deserialise settings from JSON that can't be trusted, like a request to a web server.
+    ///
+    /// On top of what the deserialisation checks, it enforces these limits
+    /// so that generating from the settings stays cheap, and rejects anything
+    /// that would otherwise panic or never finish during generation:
+    ///
+    /// - [`pass_amount`](PasswordSettings#structfield.pass_amount) of at most 10000.
+    /// - [`length`](PasswordSettings#structfield.length) of at most 1024, which is also
+    ///   the limit for the amounts of numbers, special characters, uppercase and lowercase
+    ///   letters, the amount of [words](crate::LengthMode::Words), the end of the
+    ///   [`insert_zone`](PasswordSettings#structfield.insert_zone) and the length of the
+    ///   [`separator`](PasswordSettings#structfield.separator).
+    /// - At most 1000000 words, adding up to at most 64 MiB, none of them empty,
+    ///   and every one of them coming from a known source.
+    /// - At most 256 special characters and 256 special sequences, all ASCII,
+    ///   and at most 256 special groups of at most 256 ASCII characters each.
+    /// - At most 256 [leet substitutions](PasswordSettings::set_leet_substitution)
+    ///   of at most 256 characters each.
+    /// - At most 256 [context exclusions](PasswordSettings::add_context_exclusion) and 256
+    ///   [capitalise exceptions](PasswordSettings::add_capitalise_exception),
+    ///   none of them empty and each of at most 1024 characters.
+    /// - At most 1000 retries for each reason, and 10000000 in a
+    ///   [shared pool](crate::BatchRetryMode::SharedPool).
+    /// - No empty ranges (i.e. end < start).
+    /// - A [continue probability](PasswordSettings::set_continue_probability) and a
+    ///   [most replaced fraction](PasswordSettings::set_max_replaced_fraction) between 0.0 and 1.0.
+    ///
+    /// The input itself can't be larger than 128 MiB. It never panics, whatever the input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use genrepass::{PasswordSettings, UntrustedSettingsError};
+    /// let mut settings = PasswordSettings::new();
+    /// settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    /// let json = serde_json::to_string(&settings).unwrap();
+    ///
+    /// let settings = PasswordSettings::from_untrusted_json(&json).unwrap();
+    /// assert_eq!(settings.generate().unwrap().len(), 1);
+    ///
+    /// let json = json.replace(r#""pass_amount":1"#, r#""pass_amount":1000000"#);
+    /// assert!(matches!(
+    ///     PasswordSettings::from_untrusted_json(&json),
+    ///     Err(UntrustedSettingsError::OverLimit { field: "pass_amount", .. })
+    /// ));
+    /// ```
+    pub fn from_untrusted_json(json: &str) -> Result<Self, UntrustedSettingsError> {
+        ensure!(
+            json.len() <= 2 * MAX_WORD_BYTES,
+            OverLimitSnafu {
+                field: "input",
+                limit: 2 * MAX_WORD_BYTES,
+            }
+        );
+        let settings: PasswordSettings = serde_json::from_str(json).context(JsonSnafu)?;
+
+        let over = |field: &'static str, value: usize, limit: usize| {
+            ensure!(value <= limit, OverLimitSnafu { field, limit });
+            Ok(())
+        };
+        over("pass_amount", settings.pass_amount, MAX_PASS_AMOUNT)?;
+
+        for (field, range) in [
+            ("length", &settings.length),
+            ("number_amount", &settings.number_amount),
+            ("special_chars_amount", &settings.special_chars_amount),
+            ("upper_amount", &settings.upper_amount),
+            ("lower_amount", &settings.lower_amount),
+        ] {
+            ensure!(!range.is_empty(), EmptyRangeSnafu { field });
+        }
+        if let Some(zone) = &settings.insert_zone {
+            ensure!(
+                !zone.is_empty(),
+                EmptyRangeSnafu {
+                    field: "insert_zone"
+                }
+            );
+            over("insert_zone", *zone.end(), MAX_LENGTH)?;
+        }
+        if let LengthMode::Words(range) = &settings.length_mode {
+            ensure!(
+                !range.is_empty(),
+                EmptyRangeSnafu {
+                    field: "length_mode"
+                }
+            );
+            over("length_mode", *range.end(), MAX_LENGTH)?;
+        }
+        over("separator", settings.separator.chars().count(), MAX_LENGTH)?;
+        over("length", *settings.length.end(), MAX_LENGTH)?;
+        over("number_amount", *settings.number_amount.end(), MAX_LENGTH)?;
+        let special_amount = *settings.special_chars_amount.end();
+        over("special_chars_amount", special_amount, MAX_LENGTH)?;
+        over("upper_amount", *settings.upper_amount.end(), MAX_LENGTH)?;
+        over("lower_amount", *settings.lower_amount.end(), MAX_LENGTH)?;
+
+        for (field, probability) in [
+            ("continue_probability", settings.continue_probability),
+            ("max_replaced_fraction", settings.max_replaced_fraction),
+        ] {
+            ensure!(
+                (0.0..=1.0).contains(&probability),
+                InvalidProbabilitySnafu { field }
+            );
+        }
+
+        let retries = &settings.retry_budget;
+        over("retry_budget.assembly", retries.assembly, MAX_RETRIES)?;
+        over("retry_budget.policy", retries.policy, MAX_RETRIES)?;
+        over("retry_budget.uniqueness", retries.uniqueness, MAX_RETRIES)?;
+        if let BatchRetryMode::SharedPool(pool) = settings.batch_retry {
+            over("batch_retry", pool, MAX_RETRIES * MAX_PASS_AMOUNT)?;
+        }
+
+        let special_chars = settings.special_chars.chars().count();
+        over("special_chars", special_chars, MAX_SPECIAL_CHARS)?;
+        let sequences = settings.special_sequences.len();
+        over("special_sequences", sequences, MAX_SPECIAL_CHARS)?;
+        for sequence in &settings.special_sequences {
+            over("special_sequences", sequence.len(), MAX_LENGTH)?;
+            ensure!(
+                !sequence.is_empty(),
+                EmptySnafu {
+                    field: "special_sequences"
+                }
+            );
+        }
+        let leet = &settings.leet_substitutions;
+        over("leet_substitutions", leet.len(), MAX_SPECIAL_CHARS)?;
+        for with in leet.values() {
+            over(
+                "leet_substitutions",
+                with.chars().count(),
+                MAX_SPECIAL_CHARS,
+            )?;
+        }
+        let groups = &settings.special_groups;
+        over("special_groups", groups.len(), MAX_SPECIAL_CHARS)?;
+        for group in groups {
+            over("special_groups", group.chars.len(), MAX_SPECIAL_CHARS)?;
+            ensure!(
+                !group.amount.is_empty(),
+                EmptyRangeSnafu {
+                    field: "special_groups"
+                }
+            );
+            over("special_groups", *group.amount.end(), MAX_LENGTH)?;
+        }
+        ensure!(
+            settings.special_chars.is_ascii()
+                && settings.special_sequences.iter().all(|s| s.is_ascii())
+                && groups.iter().all(|g| g.chars.is_ascii()),
+            NonAsciiSpecialCharsSnafu
+        );
+
+        for (field, entries) in [
+            ("context_exclusions", &settings.context_exclusions),
+            ("capitalise_exceptions", &settings.capitalise_exceptions),
+        ] {
+            over(field, entries.len(), MAX_EXCLUSIONS)?;
+            for entry in entries {
+                over(field, entry.chars().count(), MAX_LENGTH)?;
+                ensure!(!entry.is_empty(), EmptySnafu { field });
+            }
+        }
+
+        over("words", settings.words.len(), MAX_WORDS)?;
+        let bytes = settings
+            .words
+            .iter()
+            .try_fold(0usize, |total, word| total.checked_add(word.len()));
+        over("words", bytes.unwrap_or(usize::MAX), MAX_WORD_BYTES)?;
+        ensure!(
+            settings.words.iter().all(|word| !word.is_empty()),
+            EmptySnafu { field: "words" }
+        );
+
+        ensure!(
+            settings.word_sources.len() == settings.words.len()
+                && settings
+                    .word_sources
+                    .iter()
+                    .all(|id| id.0 < settings.sources.len()),
+            MismatchedSourcesSnafu
+        );
+        let weights = settings
+            .sources
+            .iter()
+            .try_fold(0u32, |total, source| total.checked_add(source.weight));
+        ensure!(
+            weights.is_some(),
+            OverLimitSnafu {
+                field: "sources.weight",
+                limit: u32::MAX as usize,
+            }
+        );
+
+        Ok(settings)
+    }
+}
+
+/// The errors that [`PasswordSettings::from_untrusted_json()`] can return.
+#[derive(Debug, Snafu)]
+pub enum UntrustedSettingsError {
+    /// When the input isn't JSON for [`PasswordSettings`].
+    #[snafu(display("invalid settings: {source}"))]
+    Json { source: serde_json::Error },
+    /// When a value is over one of the limits.
+    #[snafu(display("{field} is over the limit of {limit}"))]
+    OverLimit { field: &'static str, limit: usize },
+    /// When an inclusive range is empty (i.e. end < start).
+    #[snafu(display("{field} is an empty range"))]
+    EmptyRange { field: &'static str },
+    /// When a probability isn't between 0.0 and 1.0.
+    #[snafu(display("{field} isn't a probability between 0 and 1"))]
+    InvalidProbability { field: &'static str },
+    /// When a word, special sequence, context exclusion or capitalise exception is empty.
+    #[snafu(display("{field} can't contain empty strings"))]
+    Empty { field: &'static str },
+    /// When a special character or sequence isn't ASCII.
+    #[snafu(display("special characters must be ASCII"))]
+    NonAsciiSpecialChars,
+    /// When the words and the sources they come from don't match up.
+    #[snafu(display("every word must come from a known source"))]
+    MismatchedSources,
+}
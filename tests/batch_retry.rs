@@ -0,0 +1,58 @@
+//! Sharing the assembly retries between the passwords of a call.
+
+use genrepass::{BatchRetryMode, PasswordSettings};
+
+/// Where the three starting words that would run into the one longer word don't fit.
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("abcd efgh ijkl mnop qrst uvwx yzab cdef ghij klmno");
+    settings.length = 12..=12;
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+    settings.pass_amount = 200;
+    settings.retry_budget.assembly = 1;
+    settings
+}
+
+fn truncations(settings: &PasswordSettings) -> usize {
+    (0..20)
+        .map(|_| {
+            let report = settings.generate_detailed().unwrap();
+            report.passwords.iter().filter(|d| d.truncated).count()
+        })
+        .sum()
+}
+
+#[test]
+fn fewer_truncations_from_a_shared_pool() {
+    let mut settings = settings();
+    let per_password = truncations(&settings);
+
+    settings.batch_retry = BatchRetryMode::SharedPool(settings.pass_amount);
+    let shared = truncations(&settings);
+
+    assert!(shared < per_password, "{shared} >= {per_password}");
+}
+
+#[test]
+fn pool_runs_out() {
+    let mut settings = settings();
+    settings.batch_retry = BatchRetryMode::SharedPool(10);
+
+    for _ in 0..20 {
+        let passwords = settings.generate_detailed().unwrap().passwords;
+        let used: Vec<usize> = passwords.iter().map(|d| d.retries.assembly).collect();
+        assert!(used.iter().sum::<usize>() <= 10, "{used:?}");
+
+        // Once it's empty, nothing retries and the words that don't fit get truncated.
+        let mut left = 10;
+        for details in &passwords {
+            if left == 0 {
+                assert_eq!(details.retries.assembly, 0);
+            }
+            left -= details.retries.assembly;
+        }
+        assert_eq!(left, 0);
+        assert!(passwords.iter().any(|d| d.truncated));
+    }
+}
@@ -0,0 +1,167 @@
+//! The rules for uppercasing and lowercasing letters, and the deprecated flags they replace.
+
+use genrepass::{CaseRule, PasswordSettings};
+
+/// Words with a few capitals, so that there's something to keep and something to change.
+const MOSTLY_LOWER: &str = "Alpha bravo Charlie delta Echo foxtrot golf hotel";
+const MOSTLY_UPPER: &str = "ALPHA bravo CHARLIE delta ECHO FOXTROT GOLF HOTEL";
+
+fn base(text: &str) -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str(text);
+    settings.length = 12..=16;
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+    settings.upper_amount = 3..=3;
+    settings.lower_amount = 3..=3;
+    settings.pass_amount = 50;
+    settings
+}
+
+/// What the old flags meant: `dont_*` wins over `force_*`.
+fn expected(force: bool, dont: bool) -> CaseRule {
+    match (force, dont) {
+        (_, true) => CaseRule::Never,
+        (true, false) => CaseRule::Force,
+        (false, false) => CaseRule::Auto,
+    }
+}
+
+#[allow(deprecated)]
+fn with_flags(
+    mut settings: PasswordSettings,
+    [force_upper, force_lower, dont_upper, dont_lower]: [bool; 4],
+    dont_first: bool,
+) -> PasswordSettings {
+    if dont_first {
+        settings.set_dont_upper(dont_upper);
+        settings.set_dont_lower(dont_lower);
+    }
+    settings.set_force_upper(force_upper);
+    settings.set_force_lower(force_lower);
+    if !dont_first {
+        settings.set_dont_upper(dont_upper);
+        settings.set_dont_lower(dont_lower);
+    }
+    settings
+}
+
+/// Check that the passwords follow the rule for one case, where `is_case` tells
+/// the letters of that case and `kept` the ones that are in it in the words.
+fn check(settings: &PasswordSettings, rule: CaseRule, is_case: fn(char) -> bool, kept: &str) {
+    for details in settings.generate_detailed().unwrap().passwords {
+        let password = &details.password;
+        let cased: Vec<char> = password.chars().filter(|&c| is_case(c)).collect();
+        let untouched = cased.iter().all(|c| kept.contains(*c));
+        match rule {
+            CaseRule::Never => assert!(untouched, "{password}"),
+            CaseRule::Force => assert!(cased.len() >= 3, "{password}"),
+            // Only changed when none were there, so either untouched or just enough.
+            CaseRule::Auto => assert!(untouched || cased.len() == 3, "{password}"),
+        }
+    }
+}
+
+#[test]
+fn every_old_flag_combination() {
+    for flags in 0..16 {
+        let flags = [0, 1, 2, 3].map(|bit| flags & (1 << bit) != 0);
+        let [force_upper, force_lower, dont_upper, dont_lower] = flags;
+        let (upper, lower) = (
+            expected(force_upper, dont_upper),
+            expected(force_lower, dont_lower),
+        );
+
+        for dont_first in [false, true] {
+            let settings = with_flags(base(MOSTLY_LOWER), flags, dont_first);
+            assert_eq!(
+                (settings.upper_handling, settings.lower_handling),
+                (upper, lower),
+                "{flags:?}"
+            );
+            #[allow(deprecated)]
+            let read_back = [
+                settings.force_upper(),
+                settings.force_lower(),
+                settings.dont_upper(),
+                settings.dont_lower(),
+            ];
+            assert_eq!(
+                read_back,
+                [
+                    upper == CaseRule::Force,
+                    lower == CaseRule::Force,
+                    dont_upper,
+                    dont_lower
+                ],
+                "{flags:?}"
+            );
+        }
+
+        // Lowercasing can undo uppercasing and the other way around,
+        // so each case is checked with the other one left alone.
+        let mut settings = with_flags(base(MOSTLY_LOWER), flags, false);
+        settings.lower_handling = CaseRule::Never;
+        check(&settings, upper, char::is_uppercase, "ACE");
+
+        let mut settings = with_flags(base(MOSTLY_UPPER), flags, false);
+        settings.upper_handling = CaseRule::Never;
+        check(&settings, lower, char::is_lowercase, "bravodelta");
+    }
+}
+
+#[test]
+fn same_passwords_as_the_flags() {
+    for seed in 0..16 {
+        let flags = [0, 1, 2, 3].map(|bit| seed & (1 << bit) != 0);
+        let [force_upper, force_lower, dont_upper, dont_lower] = flags;
+        let mut old = with_flags(base(MOSTLY_LOWER), flags, false);
+        old.seed = Some(seed);
+        let mut new = base(MOSTLY_LOWER);
+        new.upper_handling = expected(force_upper, dont_upper);
+        new.lower_handling = expected(force_lower, dont_lower);
+        new.seed = old.seed;
+
+        assert_eq!(
+            old.generate().unwrap(),
+            new.generate().unwrap(),
+            "{flags:?}"
+        );
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn turning_a_flag_off() {
+    let mut settings = PasswordSettings::new();
+    settings.set_force_upper(true);
+    settings.set_force_upper(false);
+    assert_eq!(settings.upper_handling, CaseRule::Auto);
+
+    settings.set_dont_lower(true);
+    settings.set_force_lower(true);
+    assert_eq!(settings.lower_handling, CaseRule::Never);
+    settings.set_dont_lower(false);
+    assert_eq!(settings.lower_handling, CaseRule::Auto);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn round_trips() {
+    let mut settings = base(MOSTLY_LOWER);
+    settings.upper_handling = CaseRule::Force;
+    settings.lower_handling = CaseRule::Never;
+
+    let json = serde_json::to_string(&settings).unwrap();
+    assert!(json.contains(r#""upper_handling":"Force""#), "{json}");
+    let saved: PasswordSettings = serde_json::from_str(&json).unwrap();
+    assert_eq!(saved, settings);
+
+    // Settings saved before the rules existed get the defaults.
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value.as_object_mut().unwrap().remove("upper_handling");
+    value.as_object_mut().unwrap().remove("lower_handling");
+    let old: PasswordSettings = serde_json::from_value(value).unwrap();
+    assert_eq!(old.upper_handling, CaseRule::Auto);
+    assert_eq!(old.lower_handling, CaseRule::Auto);
+}
@@ -0,0 +1,70 @@
+//! How the files that vanish or change while words are extracted from them are reported.
+//!
+//! A dangling link stands for a file removed after being found, and a link to a file
+//! in `/proc`, which has no size until it's read, for a file written to while being read.
+
+#![cfg(target_os = "linux")]
+
+use genrepass::{PasswordSettings, SkipReason};
+use std::{fs, os::unix::fs::symlink, path::PathBuf};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("genrepass-changing-{}", std::process::id()))
+        .join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("text.txt"), "hello world").unwrap();
+    dir
+}
+
+#[test]
+fn vanished() {
+    let dir = temp_dir("vanished");
+    symlink(dir.join("removed.txt"), dir.join("gone.txt")).unwrap();
+
+    let mut settings = PasswordSettings::new();
+    settings.sort_entries = true;
+    let (_, report) = settings.get_words_from_path_with_report(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(report.read, 1);
+    assert_eq!(
+        report.skipped,
+        [(dir.join("gone.txt"), SkipReason::Vanished)]
+    );
+    assert_eq!(settings.words(), ["hello", "world"]);
+}
+
+#[test]
+fn changed_during_read() {
+    let dir = temp_dir("changed");
+    symlink("/proc/self/status", dir.join("status")).unwrap();
+
+    let mut settings = PasswordSettings::new();
+    let (_, report) = settings.get_words_from_path_with_report(&dir).unwrap();
+
+    assert_eq!(report.read, 1);
+    assert_eq!(
+        report.skipped,
+        [(dir.join("status"), SkipReason::ChangedDuringRead)]
+    );
+    assert_eq!(settings.words(), ["hello", "world"]);
+
+    #[cfg(feature = "from_path")]
+    {
+        use genrepass::{Lexicon, Split, WalkOptions};
+
+        let mut lexicon = Lexicon::new("changing", Split::UnicodeWords);
+        let options = WalkOptions::new().follow_links(true);
+        let report = lexicon.extract_words_from_path_with(&[&dir], &options, |_| true);
+
+        assert_eq!(report.read, 1);
+        assert_eq!(
+            report.skipped,
+            [(dir.join("status"), SkipReason::ChangedDuringRead)]
+        );
+        assert_eq!(lexicon.words(), ["hello", "world"]);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,66 @@
+//! Big texts are transliterated in chunks, with the same words as all at once.
+
+use deunicode::deunicode;
+use genrepass::PasswordSettings;
+use regex::Regex;
+
+const LINES: [&str; 6] = [
+    "Die Straße ist nass, aber die Füße bleiben trocken.\n",
+    "日本の首都は東京です 北亰 げんまい茶\n",
+    "Le cœur a ses raisons que la raison ne connaît point. ",
+    "Съешь же ещё этих мягких французских булок 😃 🥫\t",
+    "Ἐν ἀρχῇ ἦν ὁ λόγος, 2024 and the quick brown fox … ",
+    "ᔕᓇᓇ Æneid étude\r\n",
+];
+
+/// A text of a few hundred KB, so it's transliterated in more than one chunk.
+fn fixture() -> String {
+    let mut text = String::new();
+    for i in 0..4000 {
+        text.push_str(LINES[i % LINES.len()]);
+        if i % 7 == 0 {
+            // Without whitespace, so words and transliterations land on the chunk ends.
+            text.push_str("東京");
+        }
+    }
+    // A run without whitespace longer than a whole chunk.
+    text.push_str(&"北亰ß".repeat(40_000));
+    text.push_str(" the end");
+    text
+}
+
+fn monolithic(text: &str, keep_numbers: bool) -> Vec<String> {
+    let re = match keep_numbers {
+        true => Regex::new(r"\w+").unwrap(),
+        false => Regex::new(r"[^\d\W]+").unwrap(),
+    };
+    re.find_iter(&deunicode(text))
+        .map(|word| word.as_str().to_owned())
+        .collect()
+}
+
+#[test]
+fn chunked_words_are_the_same_as_monolithic() {
+    let text = fixture();
+    for keep_numbers in [false, true] {
+        let mut settings = PasswordSettings::new();
+        settings.keep_numbers = keep_numbers;
+        settings.get_words_from_str(&text);
+        assert_eq!(settings.words(), monolithic(&text, keep_numbers));
+    }
+}
+
+#[test]
+fn every_split_point_gives_the_same_words() {
+    // Short texts are a single chunk, so each is checked by starting a new source
+    // at every whitespace, which is where the chunks can end.
+    let text = LINES.concat();
+    let expected = monolithic(&text, false);
+    for (i, _) in text.match_indices(|c: char| c.is_ascii_whitespace()) {
+        let (head, tail) = text.split_at(i + 1);
+        let mut settings = PasswordSettings::new();
+        settings.get_words_from_str(head);
+        settings.get_words_from_str(tail);
+        assert_eq!(settings.words(), expected, "split after {head:?}");
+    }
+}
@@ -0,0 +1,109 @@
+//! The context exclusions taken from the names of the files words are extracted from.
+
+use genrepass::PasswordSettings;
+use std::{fs, path::PathBuf};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("genrepass-context-{}", std::process::id()))
+        .join(name);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn text() -> String {
+    "secretword the quick brown fox jumps over the lazy dog ".repeat(20)
+}
+
+fn assert_never_contains(settings: &PasswordSettings, word: &str) {
+    for details in settings.generate_detailed().unwrap().passwords {
+        let letters: String = details
+            .password
+            .chars()
+            .filter(char::is_ascii_alphabetic)
+            .collect();
+        assert!(
+            !letters.to_lowercase().contains(word),
+            "{}",
+            details.password
+        );
+    }
+}
+
+#[test]
+fn file_name() {
+    let dir = temp_dir("file");
+    let file = dir.join("secretword.txt");
+    fs::write(&file, text()).unwrap();
+
+    let mut settings = PasswordSettings::new();
+    settings.auto_context_exclusions = true;
+    settings.get_words_from_path(&file).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(settings.get_context_exclusions(), ["secretword"]);
+    settings.pass_amount = 200;
+    settings.retry_budget.policy = 1000;
+    assert_never_contains(&settings, "secretword");
+}
+
+#[test]
+fn files_in_directory() {
+    let dir = temp_dir("directory").join("my_notes");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("secretword.txt"), text()).unwrap();
+    fs::write(dir.join("of.md"), "of").unwrap();
+
+    let mut settings = PasswordSettings::new();
+    settings.auto_context_exclusions = true;
+    settings.sort_entries = true;
+    settings.get_words_from_path(&dir).unwrap();
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+
+    // Too short to exclude.
+    assert!(!settings
+        .get_context_exclusions()
+        .contains(&"of".to_string()));
+    assert!(settings
+        .get_context_exclusions()
+        .contains(&"notes".to_string()));
+    assert!(settings
+        .get_context_exclusions()
+        .contains(&"secretword".to_string()));
+    settings.pass_amount = 200;
+    settings.retry_budget.policy = 1000;
+    assert_never_contains(&settings, "secretword");
+}
+
+#[test]
+fn turned_off() {
+    let dir = temp_dir("off");
+    let file = dir.join("secretword.txt");
+    fs::write(&file, text()).unwrap();
+
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_path(&file).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(settings.get_context_exclusions().is_empty());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str(&text());
+    settings.add_context_exclusion("SecretWord");
+    settings.pass_amount = 200;
+    settings.retry_budget.policy = 1000;
+
+    for password in settings.generate_parallel().unwrap() {
+        assert!(
+            !password.to_lowercase().contains("secretword"),
+            "{password}"
+        );
+    }
+
+    settings.retry_budget.policy = 0;
+    assert!(settings.generate_parallel().is_err());
+}
@@ -0,0 +1,89 @@
+//! Skipping a text that was already loaded with `dedupe_sources`.
+
+use genrepass::{PasswordSettings, SourceLoad};
+use std::{fs, path::PathBuf};
+
+/// A directory with two files, one of them also outside of it.
+fn texts(name: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir()
+        .join(format!("genrepass-dedupe-{}", std::process::id()))
+        .join(name);
+    fs::create_dir_all(dir.join("notes")).unwrap();
+    fs::write(dir.join("notes").join("a.txt"), "the quick brown fox").unwrap();
+    fs::write(dir.join("notes").join("b.txt"), "jumps over the lazy dog").unwrap();
+    fs::write(dir.join("fox.txt"), "the quick brown fox").unwrap();
+    (dir.join("notes"), dir.join("fox.txt"))
+}
+
+#[test]
+fn same_file_twice() {
+    let (dir, file) = texts("file");
+    let mut settings = PasswordSettings::new();
+
+    let first = settings.get_words_from_path(&file).unwrap().unwrap();
+    let second = settings.get_words_from_path(&file).unwrap().unwrap();
+    assert!(matches!(second, SourceLoad::Added(_)));
+    assert_ne!(first.id(), second.id());
+    assert_eq!(settings.words().len(), 8);
+
+    settings.clear_words();
+    settings.dedupe_sources = true;
+    let first = settings.get_words_from_path(&file).unwrap().unwrap();
+    let (second, report) = settings.get_words_from_path_with_report(&file).unwrap();
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+
+    assert!(matches!(first, SourceLoad::Added(_)));
+    assert_eq!(second, Some(SourceLoad::AlreadyLoaded(first.id())));
+    assert_eq!(report.read, 1);
+    assert_eq!(settings.words().len(), 4);
+    assert_eq!(settings.sources().len(), 1);
+}
+
+#[test]
+fn same_directory_twice() {
+    let (dir, _) = texts("dir");
+    let mut settings = PasswordSettings::new();
+    settings.dedupe_sources = true;
+
+    let first = settings.get_words_from_path(&dir).unwrap().unwrap();
+    let (second, report) = settings.get_words_from_path_with_report(&dir).unwrap();
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+
+    assert_eq!(second, Some(SourceLoad::AlreadyLoaded(first.id())));
+    assert_eq!(report.read, 2);
+    assert_eq!(settings.words().len(), 9);
+}
+
+#[test]
+fn same_text_from_a_file_and_a_string() {
+    let (dir, file) = texts("string");
+    let mut settings = PasswordSettings::new();
+    settings.dedupe_sources = true;
+
+    let from_str = settings.get_words_from_str("the quick brown fox").unwrap();
+    let from_file = settings.get_words_from_path(&file).unwrap();
+    // Only part of the directory is the same, so it's loaded.
+    let from_dir = settings.get_words_from_path(&dir).unwrap().unwrap();
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+
+    assert_eq!(from_file, Some(SourceLoad::AlreadyLoaded(from_str.id())));
+    assert!(matches!(from_dir, SourceLoad::Added(_)));
+    assert_eq!(settings.sources().len(), 2);
+}
+
+#[test]
+fn loaded_again_after_clearing() {
+    let mut settings = PasswordSettings::new();
+    settings.dedupe_sources = true;
+
+    settings.get_words_from_str("the quick brown fox");
+    settings.clear_words();
+    let again = settings.get_words_from_str("the quick brown fox").unwrap();
+    assert!(matches!(again, SourceLoad::Added(_)));
+
+    // The words given as they are have no text to compare with.
+    settings.set_words(vec![String::from("fox")]);
+    let text = settings.get_words_from_str("fox").unwrap();
+    assert!(matches!(text, SourceLoad::Added(_)));
+    assert_eq!(settings.sources()[0].content_hash, None);
+}
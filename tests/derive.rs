@@ -0,0 +1,88 @@
+//! The passwords derived for each site from a master secret.
+
+#![cfg(feature = "derive")]
+
+use genrepass::{GenerateError, PasswordSettings, SourceMixing, WordCase};
+use secrecy::SecretString;
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings.get_words_from_str("sphinx of black quartz judge my vow");
+    settings.word_case = WordCase::Capitalised;
+    settings.randomise = true;
+    settings.source_mixing = SourceMixing::Shuffled;
+    settings
+}
+
+fn master(secret: &str) -> SecretString {
+    SecretString::from(secret)
+}
+
+#[test]
+fn same_inputs_same_password() {
+    let settings = settings();
+    let master = master("correct horse battery staple");
+
+    for site in ["github", "example.com", ""] {
+        let password = settings.derive(site, &master).unwrap();
+        assert_eq!(settings.derive(site, &master).unwrap(), password);
+        assert_eq!(settings.clone().derive(site, &master).unwrap(), password);
+        assert!(settings.conforms(&password).is_ok(), "{password}");
+    }
+}
+
+#[test]
+fn ignores_the_seed_of_the_settings() {
+    let mut settings = settings();
+    let master = master("correct horse battery staple");
+    let password = settings.derive("github", &master).unwrap();
+
+    settings.seed = Some(42);
+    assert_eq!(settings.derive("github", &master).unwrap(), password);
+}
+
+#[test]
+fn different_sites_and_masters_diverge() {
+    let mut settings = settings();
+    settings.length = 40..=50;
+    let first = master("correct horse battery staple");
+    let second = master("correct horse battery stapler");
+
+    let sites = ["github", "GitHub", "gitlab", "example.com", "example.org"];
+    let mut passwords: Vec<String> = sites
+        .iter()
+        .flat_map(|site| {
+            [
+                settings.derive(site, &first).unwrap(),
+                settings.derive(site, &second).unwrap(),
+            ]
+        })
+        .collect();
+    passwords.sort();
+    passwords.dedup();
+    assert_eq!(passwords.len(), sites.len() * 2);
+
+    // Moving characters between the master and the site isn't the same.
+    assert_ne!(
+        settings.derive("hub", &master("git")).unwrap(),
+        settings.derive("ithub", &master("g")).unwrap()
+    );
+}
+
+#[test]
+fn changes_with_the_words() {
+    let mut settings = settings();
+    settings.length = 40..=50;
+    let master = master("correct horse battery staple");
+    let password = settings.derive("github", &master).unwrap();
+
+    settings.get_words_from_str("pack my box with five dozen liquor jugs");
+    assert_ne!(settings.derive("github", &master).unwrap(), password);
+
+    settings.clear_words();
+    assert!(matches!(
+        settings.derive("github", &master),
+        Err(GenerateError::NotEnoughWords { .. })
+    ));
+}
@@ -0,0 +1,104 @@
+//! Refining a password step by step with a `PasswordDraft`.
+
+use genrepass::{PasswordDraft, PasswordSettings};
+
+const TEXT: &str = "the quick brown fox jumps over the lazy dog";
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str(TEXT);
+    settings.length = 20..=24;
+    settings.number_amount = 2..=3;
+    settings.special_chars_amount = 2..=3;
+    settings.upper_amount = 3..=5;
+    settings.lower_amount = 1..=2;
+    settings
+}
+
+/// The characters of the words, in order and with their case.
+fn letters(draft: &PasswordDraft) -> String {
+    draft
+        .password()
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .collect()
+}
+
+/// The positions and characters of everything that isn't a letter.
+fn inserts(draft: &PasswordDraft) -> Vec<(usize, char)> {
+    draft
+        .password()
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| !c.is_alphabetic())
+        .collect()
+}
+
+#[test]
+fn reroll_inserts_keeps_the_words() {
+    let settings = settings();
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..20 {
+        let mut draft = settings.draft(&mut rng).unwrap();
+        let words = letters(&draft);
+        let before = inserts(&draft);
+
+        let mut changed = false;
+        for _ in 0..20 {
+            draft.reroll_inserts(&mut rng);
+            assert_eq!(letters(&draft), words, "{}", draft.password());
+            assert_eq!(inserts(&draft).len(), before.len(), "{}", draft.password());
+            changed |= inserts(&draft) != before;
+        }
+        assert!(changed, "{}", draft.password());
+    }
+}
+
+#[test]
+fn reroll_case_keeps_the_words_and_inserts() {
+    let settings = settings();
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..20 {
+        let mut draft = settings.draft(&mut rng).unwrap();
+        let words = letters(&draft).to_lowercase();
+        let before = inserts(&draft);
+
+        for _ in 0..20 {
+            draft.reroll_case(&mut rng);
+            assert_eq!(letters(&draft).to_lowercase(), words);
+            assert_eq!(inserts(&draft), before);
+            let details = draft.details();
+            assert!(details.achieved_upper >= details.requested_upper);
+        }
+    }
+}
+
+#[test]
+fn reroll_words_keeps_the_rolled_amounts() {
+    let settings = settings();
+    let mut rng = rand::thread_rng();
+    let mut draft = settings.draft(&mut rng).unwrap();
+    let rolled = draft.details().rolled.clone();
+
+    for _ in 0..20 {
+        draft.reroll_words(&mut rng);
+        assert_eq!(draft.details().rolled, rolled);
+        assert_eq!(
+            inserts(&draft).len(),
+            rolled.numbers + rolled.special_chars,
+            "{}",
+            draft.password()
+        );
+    }
+
+    let details = draft.details().clone();
+    assert_eq!(draft.into_details(), details);
+}
+
+#[test]
+fn no_words() {
+    let settings = PasswordSettings::new();
+    assert!(settings.draft(&mut rand::thread_rng()).is_err());
+}
@@ -0,0 +1,59 @@
+//! Truncating or erroring when the words can never fit the length.
+
+use genrepass::{ExhaustedBehaviour, GenerateError, PasswordSettings};
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("incomprehensibilities counterrevolutionaries");
+    settings.length = 8..=8;
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+    settings.pass_amount = 20;
+    settings
+}
+
+#[test]
+fn truncates_by_default() {
+    let settings = settings();
+    for details in settings.generate_detailed().unwrap().passwords {
+        assert!(details.truncated);
+        assert_eq!(details.password.len(), 8, "{}", details.password);
+    }
+}
+
+#[test]
+fn errors_in_strict_mode() {
+    let mut settings = settings();
+    settings.on_exhausted = ExhaustedBehaviour::Error;
+    settings.retry_budget.assembly = 3;
+
+    let error = settings.generate().unwrap_err();
+    assert!(matches!(error, GenerateError::LengthNotSatisfied { .. }));
+    assert_eq!(
+        error.to_string(),
+        "the words didn't fit a length of 8 to 8 after 3 retries"
+    );
+    assert!(settings.generate_detailed().is_err());
+
+    // Words that fit are unaffected.
+    settings.get_words_from_str("abcd efgh");
+    settings.length = 8..=12;
+    assert_eq!(settings.generate().unwrap().len(), 20);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn errors_in_parallel() {
+    let mut settings = settings();
+    assert_eq!(settings.generate_parallel().unwrap().len(), 20);
+
+    settings.on_exhausted = ExhaustedBehaviour::Error;
+    assert!(matches!(
+        settings.generate_parallel(),
+        Err(GenerateError::LengthNotSatisfied { .. })
+    ));
+    assert!(matches!(
+        settings.generate_parallel_with_callback(|_, _| std::ops::ControlFlow::Continue(())),
+        Err(GenerateError::LengthNotSatisfied { .. })
+    ));
+}
@@ -0,0 +1,81 @@
+//! Pushing text and files into an [`ExtractionSession`](genrepass::ExtractionSession)
+//! gives the same words as extracting everything at once.
+
+use genrepass::{Lexicon, Split};
+use std::{fs, path::PathBuf};
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("genrepass-session-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    fs::write(&path, contents).unwrap();
+    path
+}
+
+fn at_once(split: Split, text: &str) -> Vec<String> {
+    let mut lexicon = Lexicon::new("at once", split);
+    lexicon.extract_words(text, |_| true);
+    lexicon.words().to_vec()
+}
+
+#[test]
+fn every_split_point() {
+    let text = "Ünïcödé  wörds,\tsplit  ăcross\n\npieces; of-text";
+
+    for split in [
+        Split::UnicodeWords,
+        Split::WordBounds,
+        Split::UnicodeWhitespace,
+        Split::AsciiWhitespace,
+        Split::Chars(vec![' ', ',', ';']),
+    ] {
+        let expected = at_once(split.clone(), text);
+
+        for (cut, _) in text.char_indices() {
+            let mut lexicon = Lexicon::new("pieces", split.clone());
+            let mut session = lexicon.begin_extraction(|_| true);
+            session.push_text(&text[..cut]);
+            session.push_text(&text[cut..]);
+            session.finish();
+
+            assert_eq!(lexicon.words(), expected, "{split:?} cut at {cut}");
+        }
+    }
+}
+
+#[test]
+fn files_larger_than_a_chunk() {
+    // A multibyte character and a word both straddle the end of the first chunk.
+    let mut text = "word ".repeat(64 * 1024 / 5);
+    text.truncate(64 * 1024 - 1);
+    text.push_str("ĳssel stroomt");
+    let path = temp_file("large.txt", text.as_bytes());
+
+    let mut lexicon = Lexicon::new("file", Split::UnicodeWords);
+    let mut session = lexicon.begin_extraction(|_| true);
+    session.push_text("before");
+    let added = session.push_file(&path).unwrap();
+    session.push_text("after");
+    session.finish();
+    fs::remove_file(&path).unwrap();
+
+    let expected = at_once(Split::UnicodeWords, &text);
+    assert_eq!(added, expected.len());
+    assert_eq!(lexicon.words().first().unwrap(), "before");
+    assert_eq!(&lexicon.words()[1..=added], expected);
+    assert_eq!(lexicon.words().last().unwrap(), "after");
+}
+
+#[test]
+fn invalid_utf8_keeps_the_words_before_it() {
+    let path = temp_file("invalid.txt", b"valid words \xFF\xFE more");
+
+    let mut lexicon = Lexicon::new("file", Split::UnicodeWords);
+    let mut session = lexicon.begin_extraction(|_| true);
+    let error = session.push_file(&path).unwrap_err();
+    session.finish();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(lexicon.words(), ["valid", "words"]);
+}
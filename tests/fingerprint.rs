@@ -0,0 +1,80 @@
+//! The fingerprint of the words.
+
+use genrepass::{Lexicon, PasswordSettings, Split};
+
+fn fingerprint(words: &[&str]) -> u64 {
+    let mut settings = PasswordSettings::new();
+    settings.set_words(words.iter().map(|word| word.to_string()).collect());
+    settings.words_fingerprint()
+}
+
+#[test]
+fn changes_with_any_edit() {
+    let words = ["the", "quick", "brown", "fox"];
+    let original = fingerprint(&words);
+
+    for edited in [
+        &["the", "quick", "brown"][..],
+        &["the", "quick", "brown", "fox", "jumps"],
+        &["quick", "the", "brown", "fox"],
+        &["the", "quick", "brown", "fix"],
+        &["the", "quick", "brown", "Fox"],
+        &["thequick", "brown", "fox"],
+        &["th", "equick", "brown", "fox"],
+        &[],
+    ] {
+        assert_ne!(fingerprint(edited), original, "{edited:?}");
+    }
+    assert_eq!(fingerprint(&words), original);
+}
+
+#[test]
+fn same_on_every_platform() {
+    assert_eq!(fingerprint(&[]), 0xCBF2_9CE4_8422_2325);
+    assert_eq!(
+        fingerprint(&["the", "quick", "brown", "fox"]),
+        0x1162_33FB_F3D8_4B8E
+    );
+}
+
+#[test]
+fn same_for_a_lexicon() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("sphinx of black quartz judge my vow");
+    let mut lexicon = Lexicon::new("sphinx", Split::UnicodeWords);
+    lexicon.extract_words("sphinx of black quartz judge my vow", |_| true);
+
+    assert_eq!(lexicon.words_fingerprint(), settings.words_fingerprint());
+    assert_eq!(
+        settings.generate_detailed().unwrap().words_fingerprint,
+        settings.words_fingerprint()
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn warns_when_the_words_changed_outside() {
+    use genrepass::SettingsWarning;
+
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox");
+    let json = serde_json::to_string(&settings).unwrap();
+
+    let saved: PasswordSettings = serde_json::from_str(&json).unwrap();
+    assert!(saved.warnings().is_empty());
+
+    let edited: PasswordSettings = serde_json::from_str(&json.replace("fox", "dog")).unwrap();
+    assert_eq!(
+        edited.warnings(),
+        [SettingsWarning::WordsFingerprintMismatch {
+            saved: settings.words_fingerprint(),
+            current: fingerprint(&["the", "quick", "brown", "dog"]),
+        }]
+    );
+
+    // Settings saved before the fingerprint existed have nothing to compare with.
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value.as_object_mut().unwrap().remove("words_fingerprint");
+    let old: PasswordSettings = serde_json::from_value(value).unwrap();
+    assert!(old.warnings().is_empty());
+}
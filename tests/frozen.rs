@@ -0,0 +1,85 @@
+//! Generating from frozen settings while the original ones keep changing.
+
+use genrepass::PasswordSettings;
+use std::thread;
+
+#[test]
+fn unaffected_by_edits() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("abc cab bca acb");
+    settings.length = 10..=12;
+    settings.pass_amount = 20;
+    let frozen = settings.freeze();
+
+    thread::scope(|s| {
+        for _ in 0..8 {
+            let frozen = frozen.clone();
+            s.spawn(move || {
+                for _ in 0..200 {
+                    for password in frozen.generate().unwrap() {
+                        assert!((10..=12).contains(&password.len()), "{password}");
+                        assert!(
+                            password
+                                .to_lowercase()
+                                .chars()
+                                .filter(char::is_ascii_alphabetic)
+                                .all(|c| "abc".contains(c)),
+                            "{password}"
+                        );
+                    }
+                }
+            });
+        }
+
+        for i in 0..200 {
+            settings.get_words_from_str("xyz zyx yzx xzy");
+            settings.length = 20 + i % 10..=40;
+            settings.randomise = i % 2 == 0;
+            if i % 50 == 0 {
+                settings.shuffle_words();
+            }
+        }
+    });
+
+    assert_eq!(frozen.settings().words().len(), 4);
+    assert_eq!(frozen.settings().length, 10..=12);
+}
+
+#[test]
+fn refreeze() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("abc cab bca acb");
+    let frozen = settings.freeze();
+
+    settings.clear_words();
+    settings.get_words_from_str("xyz zyx yzx xzy");
+    let refrozen = settings.freeze();
+
+    let letters = |password: &str| {
+        password
+            .to_lowercase()
+            .replace(|c: char| !c.is_ascii_alphabetic(), "")
+    };
+    assert!(letters(&frozen.generate().unwrap()[0])
+        .chars()
+        .all(|c| "abc".contains(c)));
+    assert!(letters(&refrozen.generate().unwrap()[0])
+        .chars()
+        .all(|c| "xyz".contains(c)));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("abc cab bca acb");
+    settings.seed = Some(3);
+    settings.pass_amount = 100;
+    let frozen = settings.freeze();
+    settings.get_words_from_str("xyz zyx yzx xzy");
+
+    assert_eq!(
+        frozen.generate_parallel().unwrap(),
+        frozen.generate().unwrap()
+    );
+}
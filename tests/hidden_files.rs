@@ -0,0 +1,57 @@
+//! How [`Lexicon::extract_words_from_path()`] treats hidden directories and files.
+
+#![cfg(feature = "from_path")]
+
+use genrepass::{CharFilter, Lexicon, Split};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A directory with a visible file, a hidden file and a hidden directory with a file.
+fn notes(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("genrepass-hidden-{}", std::process::id()))
+        .join(name);
+    fs::create_dir_all(dir.join(".notes")).unwrap();
+    fs::write(dir.join("visible.md"), "visible").unwrap();
+    fs::write(dir.join(".hidden.md"), "hidden").unwrap();
+    fs::write(dir.join(".notes").join("note.md"), "note").unwrap();
+    dir
+}
+
+fn extract(paths: &[&Path], include_hidden: bool) -> Vec<String> {
+    let mut lexicon = Lexicon::new("notes", Split::UnicodeWords);
+    lexicon.include_hidden = include_hidden;
+    lexicon.extract_words_from_path(paths, 3, None, CharFilter::Unicode.closure());
+    lexicon.words().to_vec()
+}
+
+#[test]
+fn skipped_by_default() {
+    let dir = notes("default");
+    let words = extract(&[&dir], false);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(words, ["visible"]);
+}
+
+#[test]
+fn included() {
+    let dir = notes("included");
+    let words = extract(&[&dir], true);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(words, ["hidden", "note", "visible"]);
+}
+
+#[test]
+fn passed_directly() {
+    let dir = notes("direct");
+    let hidden_dir = extract(&[&dir.join(".notes")], false);
+    let hidden_file = extract(&[&dir.join(".hidden.md")], false);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(hidden_dir, ["note"]);
+    assert_eq!(hidden_file, ["hidden"]);
+}
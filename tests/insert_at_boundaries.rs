@@ -0,0 +1,103 @@
+//! The numbers and special characters inserted only between the words with `insert_at_boundaries`.
+
+use genrepass::PasswordSettings;
+
+const WORDS: [&str; 5] = ["quick", "brown", "jumps", "over", "lazy"];
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str(&WORDS.join(" "));
+    settings.insert_at_boundaries = true;
+    settings.length = 20..=28;
+    // Never more than the places between at least two words.
+    settings.number_amount = 1..=2;
+    settings.special_chars_amount = 0..=1;
+    settings.upper_amount = 0..=0;
+    settings.pass_amount = 100;
+    settings
+}
+
+#[test]
+fn inserts_keep_the_words_whole() {
+    for details in settings().generate_detailed().unwrap().passwords {
+        let password = &details.password;
+        for (i, span) in details.word_spans.iter().enumerate() {
+            let word = &password[span.clone()];
+            let last = i + 1 == details.word_spans.len();
+            assert!(
+                WORDS.contains(&word) || last && details.truncated,
+                "{word} in {password}"
+            );
+        }
+
+        // Every inserted character is next to the start or the end of a word.
+        let edges: Vec<usize> = details
+            .word_spans
+            .iter()
+            .flat_map(|span| [span.start, span.end])
+            .chain([0, password.len()])
+            .collect();
+        let mut run = None;
+        for (i, c) in password.char_indices().chain([(password.len(), 'a')]) {
+            match (c.is_alphabetic(), run) {
+                (false, None) => run = Some(i),
+                (true, Some(start)) => {
+                    assert!(
+                        edges.contains(&start) || edges.contains(&i),
+                        "{start}..{i} in {password}"
+                    );
+                    run = None;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[test]
+fn replacing_starts_at_the_word_edges() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("abcd efgh ijkl mnop");
+    settings.insert_at_boundaries = true;
+    settings.replace = true;
+    settings.length = 16..=16;
+    settings.number_amount = 2..=2;
+    settings.special_chars_amount = 1..=1;
+    settings.upper_amount = 0..=0;
+    settings.pass_amount = 100;
+
+    for password in settings.generate().unwrap() {
+        assert_eq!(password.len(), 16, "{password}");
+        for (i, c) in password.char_indices() {
+            if !c.is_alphabetic() {
+                assert!(i % 4 == 0 || i % 4 == 3, "{i} in {password}");
+            }
+        }
+    }
+}
+
+#[test]
+fn extra_inserts_fall_back_to_random_positions() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("abcdefgh");
+    settings.insert_at_boundaries = true;
+    settings.length = 12..=12;
+    settings.number_amount = 4..=4;
+    settings.special_chars_amount = 0..=0;
+    settings.upper_amount = 0..=0;
+    settings.pass_amount = 50;
+
+    for password in settings.generate().unwrap() {
+        assert_eq!(password.len(), 12, "{password}");
+        assert_eq!(password.chars().filter(char::is_ascii_digit).count(), 4);
+        // Both edges of the only word are taken before any other position.
+        assert!(
+            password.starts_with(|c: char| c.is_ascii_digit()),
+            "{password}"
+        );
+        assert!(
+            password.ends_with(|c: char| c.is_ascii_digit()),
+            "{password}"
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! The numbers and special characters grouped at one end with `insert_placement`.
+
+use genrepass::{InsertPlacement, PasswordSettings, SettingsWarning};
+
+const NUMBERS: usize = 2;
+const SPECIALS: usize = 2;
+
+fn settings(placement: InsertPlacement) -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("correct horse battery staple");
+    settings.insert_placement = placement;
+    settings.number_amount = NUMBERS..=NUMBERS;
+    settings.special_chars_amount = SPECIALS..=SPECIALS;
+    settings.length = 16..=24;
+    settings.pass_amount = 100;
+    settings
+}
+
+/// Check that `group` is the numbers followed by the special characters.
+fn assert_group(group: &str, password: &str) {
+    let (numbers, specials) = group.split_at(NUMBERS);
+    assert!(numbers.chars().all(|c| c.is_ascii_digit()), "{password}");
+    assert!(specials.chars().all(|c| !c.is_alphanumeric()), "{password}");
+}
+
+#[test]
+fn suffix_ends_with_the_insertables() {
+    let settings = settings(InsertPlacement::Suffix);
+    for password in settings.generate().unwrap() {
+        assert!((16..=24).contains(&password.len()), "{password}");
+        let (words, group) = password.split_at(password.len() - NUMBERS - SPECIALS);
+        assert!(words.chars().all(char::is_alphabetic), "{password}");
+        assert_group(group, &password);
+    }
+}
+
+#[test]
+fn prefix_starts_with_the_insertables() {
+    let settings = settings(InsertPlacement::Prefix);
+    for password in settings.generate().unwrap() {
+        assert!((16..=24).contains(&password.len()), "{password}");
+        let (group, words) = password.split_at(NUMBERS + SPECIALS);
+        assert_group(group, &password);
+        assert!(words.chars().all(char::is_alphabetic), "{password}");
+    }
+}
+
+#[test]
+fn replacing_overwrites_the_end() {
+    let mut settings = settings(InsertPlacement::Suffix);
+    settings.replace = true;
+    settings.length = 20..=20;
+    for details in settings.generate_detailed().unwrap().passwords {
+        let password = &details.password;
+        assert_eq!(password.len(), 20, "{password}");
+        let (words, group) = password.split_at(20 - NUMBERS - SPECIALS);
+        assert!(words.chars().all(char::is_alphabetic), "{password}");
+        assert_group(group, password);
+        // The words are cut short rather than pushed along.
+        let letters: usize = details.word_spans.iter().map(|span| span.len()).sum();
+        assert_eq!(letters, 20 - NUMBERS - SPECIALS, "{password}");
+    }
+}
+
+#[test]
+fn prefix_goes_after_the_first_letter_when_it_must_be_one() {
+    let mut settings = settings(InsertPlacement::Prefix);
+    settings.must_start_with_letter = true;
+    assert!(settings
+        .warnings()
+        .contains(&SettingsWarning::PrefixAfterFirstLetter));
+
+    for password in settings.generate().unwrap() {
+        assert!(password.starts_with(char::is_alphabetic), "{password}");
+        assert_group(&password[1..1 + NUMBERS + SPECIALS], &password);
+    }
+}
+
+#[test]
+fn grouped_positions_add_no_entropy() {
+    let random = settings(InsertPlacement::Random);
+    let suffix = settings(InsertPlacement::Suffix);
+    assert!(suffix.search_space_bits() < random.search_space_bits());
+    assert!(suffix.entropy_of("correct42!?") < random.entropy_of("correct42!?"));
+}
@@ -0,0 +1,134 @@
+//! The numbers and special characters kept within `insert_zone`.
+
+use genrepass::{GenerateError, InsertPlacement, PasswordSettings};
+use rand::{rngs::StdRng, SeedableRng};
+use std::ops::ControlFlow;
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings.number_amount = 1..=3;
+    settings.special_chars_amount = 1..=3;
+    settings.length = 16..=24;
+    settings.insert_zone = Some(2..=9);
+    settings.pass_amount = 50;
+    settings
+}
+
+/// Check that every character that isn't a letter is within the zone.
+fn assert_in_zone(settings: &mut PasswordSettings) {
+    for seed in 0..20 {
+        settings.seed = Some(seed);
+        for password in settings.generate().unwrap() {
+            for (i, c) in password.chars().enumerate() {
+                if !c.is_alphabetic() {
+                    assert!((2..=9).contains(&i), "{password}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn inserted_within_zone() {
+    assert_in_zone(&mut settings());
+}
+
+#[test]
+fn replaced_within_zone() {
+    let mut settings = settings();
+    settings.replace = true;
+    assert_in_zone(&mut settings);
+}
+
+#[test]
+fn at_boundaries_within_zone() {
+    let mut settings = settings();
+    settings.insert_at_boundaries = true;
+    assert_in_zone(&mut settings);
+    settings.replace = true;
+    assert_in_zone(&mut settings);
+}
+
+#[test]
+fn zone_too_small_to_replace() {
+    let mut settings = settings();
+    settings.replace = true;
+    settings.insert_zone = Some(30..=40);
+    assert!(matches!(
+        settings.generate(),
+        Err(GenerateError::InsertZoneTooSmall { .. })
+    ));
+
+    settings.replace = false;
+    assert!(settings.generate().is_ok());
+}
+
+#[test]
+fn zone_checked_by_every_method() {
+    let mut settings = settings();
+    settings.replace = true;
+    for zone in [30..=40, 5..=5] {
+        settings.insert_zone = Some(zone);
+        let checked = settings.can_generate().unwrap_err();
+        assert!(matches!(checked, GenerateError::InsertZoneTooSmall { .. }));
+
+        assert!(settings.generate().is_err());
+        assert!(settings.iter().is_err());
+        assert!(settings.generate_detailed().is_err());
+        assert!(settings
+            .generate_with_rng(&mut StdRng::seed_from_u64(0))
+            .is_err());
+        assert!(settings
+            .generate_with_callback(|_, _| ControlFlow::Continue(()))
+            .is_err());
+        assert!(settings.draft(&mut StdRng::seed_from_u64(0)).is_err());
+        #[cfg(feature = "rayon")]
+        assert!(settings.generate_parallel().is_err());
+    }
+
+    settings.insert_zone = Some(2..=9);
+    assert!(settings.can_generate().is_ok());
+    assert!(settings.generate().is_ok());
+    assert!(settings.iter().is_ok());
+}
+
+#[test]
+fn empty_zone() {
+    let mut settings = settings();
+    #[allow(clippy::reversed_empty_ranges)]
+    {
+        settings.insert_zone = Some(5..=4);
+    }
+    assert!(matches!(
+        settings.generate(),
+        Err(GenerateError::EmptyRange { .. })
+    ));
+    assert!(matches!(
+        settings.can_generate(),
+        Err(GenerateError::EmptyRange { .. })
+    ));
+}
+
+#[test]
+fn fewer_positions_within_zone() {
+    let mut settings = settings();
+    let within = settings.search_space();
+    settings.insert_zone = None;
+    let anywhere = settings.search_space();
+    assert!(within.numbers < anywhere.numbers);
+    assert!(within.special_chars < anywhere.special_chars);
+    assert_eq!(within.words, anywhere.words);
+}
+
+#[test]
+fn grouped_inserts_ignore_zone() {
+    let mut settings = settings();
+    settings.replace = true;
+    settings.insert_zone = Some(0..=0);
+    settings.insert_placement = InsertPlacement::Suffix;
+
+    for password in settings.generate().unwrap() {
+        assert!(!password.ends_with(char::is_alphabetic), "{password}");
+    }
+}
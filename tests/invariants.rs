@@ -0,0 +1,177 @@
+//! The guarantees every generated password keeps, checked over a matrix of settings.
+//!
+//! The passwords aren't seeded, so every run checks a different sample.
+
+use genrepass::{
+    CaseRule, ExhaustedBehaviour, PasswordDetails, PasswordSettings, WordCase, WordSelection,
+};
+use std::ops::RangeInclusive;
+
+const TEXT: &str = "the quick brown fox jumps over the lazy dog while a wizard quietly \
+                    packs boxes of liquor jugs and five dozen jars of honey near the river";
+
+/// The settings a configuration differs in, kept for naming it when a check fails.
+#[derive(Debug, Clone)]
+struct Config {
+    capitalise: bool,
+    replace: bool,
+    upper_handling: CaseRule,
+    lower_handling: CaseRule,
+    word_selection: WordSelection,
+    length: RangeInclusive<usize>,
+}
+
+impl Config {
+    fn settings(&self) -> PasswordSettings {
+        let mut settings = PasswordSettings::new();
+        settings.get_words_from_str(TEXT);
+        settings.word_case = if self.capitalise {
+            WordCase::Capitalised
+        } else {
+            WordCase::AsIs
+        };
+        settings.replace = self.replace;
+        settings.upper_handling = self.upper_handling;
+        settings.lower_handling = self.lower_handling;
+        settings.word_selection = self.word_selection;
+        settings.length = self.length.clone();
+        // Dropping back to a whole word can leave too few letters for the amounts.
+        settings.on_exhausted = ExhaustedBehaviour::Truncate;
+        settings.pass_amount = 200;
+        settings
+    }
+}
+
+fn matrix() -> Vec<Config> {
+    const RULES: [CaseRule; 3] = [CaseRule::Auto, CaseRule::Force, CaseRule::Never];
+    let mut configs = Vec::new();
+    for flags in 0..4 {
+        for (upper_handling, lower_handling) in
+            RULES.into_iter().flat_map(|u| RULES.map(|l| (u, l)))
+        {
+            for word_selection in [WordSelection::Consecutive, WordSelection::Uniform] {
+                for length in [8..=8, 12..=16, 24..=30] {
+                    configs.push(Config {
+                        capitalise: flags & 1 != 0,
+                        replace: flags & 2 != 0,
+                        upper_handling,
+                        lower_handling,
+                        word_selection,
+                        length,
+                    });
+                }
+            }
+        }
+    }
+    configs
+}
+
+fn check(property: &str, holds: bool, details: &PasswordDetails, config: &Config) {
+    assert!(
+        holds,
+        "{property} doesn't hold for {:?} generated with {config:?}",
+        details.password,
+    );
+}
+
+#[test]
+fn hold_across_the_matrix() {
+    for config in matrix() {
+        let settings = config.settings();
+        let report = settings.generate_detailed().unwrap();
+
+        for details in &report.passwords {
+            let password = &details.password;
+            let count = |f: fn(&char) -> bool| password.chars().filter(f).count();
+            let (digits, upper, lower) = (
+                count(char::is_ascii_digit),
+                count(char::is_ascii_uppercase),
+                count(char::is_ascii_lowercase),
+            );
+
+            check(
+                "length within effective_length()",
+                settings
+                    .effective_length()
+                    .contains(&password.chars().count()),
+                details,
+                &config,
+            );
+            // Even the shortest length fits the most numbers and special characters.
+            check(
+                "digits within number_amount",
+                settings.number_amount.contains(&digits),
+                details,
+                &config,
+            );
+            check(
+                "special characters only from special_chars",
+                password
+                    .chars()
+                    .filter(|c| !c.is_ascii_alphanumeric())
+                    .all(|c| settings.get_special_chars().contains(c)),
+                details,
+                &config,
+            );
+            check(
+                "special characters within special_chars_amount",
+                settings
+                    .special_chars_amount
+                    .contains(&(password.chars().count() - digits - upper - lower)),
+                details,
+                &config,
+            );
+
+            if config.upper_handling == CaseRule::Never {
+                if !config.capitalise {
+                    check(
+                        "no uppercasing with CaseRule::Never",
+                        upper == 0,
+                        details,
+                        &config,
+                    );
+                }
+            } else if config.upper_handling == CaseRule::Force || !config.capitalise {
+                check(
+                    "upper_amount reached when uppercasing is forced",
+                    upper >= *settings.upper_amount.start(),
+                    details,
+                    &config,
+                );
+            } else {
+                check(
+                    "some uppercase with capitalise",
+                    upper >= 1,
+                    details,
+                    &config,
+                );
+            }
+            if config.lower_handling != CaseRule::Never {
+                check(
+                    "lower_amount reached",
+                    lower >= *settings.lower_amount.start(),
+                    details,
+                    &config,
+                );
+            }
+
+            check(
+                "composition matches a recount",
+                details.composition.upper == upper
+                    && details.composition.lower == lower
+                    && details.composition.digits == digits
+                    && details.composition.special
+                        == password.chars().count() - digits - upper - lower
+                    && details.composition.other == 0,
+                details,
+                &config,
+            );
+            check(
+                "truncated only after running out of assembly retries",
+                !details.truncated || details.retries.assembly == settings.retry_budget.assembly,
+                details,
+                &config,
+            );
+        }
+    }
+}
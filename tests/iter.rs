@@ -0,0 +1,42 @@
+//! The passwords of the lazy iterator.
+
+use genrepass::{GenerateError, PasswordSettings, WordCase};
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings.word_case = WordCase::Capitalised;
+    settings.length = 16..=20;
+    settings.number_amount = 2..=2;
+    settings.special_chars_amount = 1..=1;
+    settings
+}
+
+#[test]
+fn same_format_as_generate() {
+    let settings = settings();
+    let lazy: Vec<String> = settings.iter().unwrap().take(5).collect();
+    let eager = settings.generate_n(5).unwrap();
+    assert_eq!(lazy.len(), eager.len());
+
+    for password in lazy.iter().chain(&eager) {
+        assert!(settings.conforms(password).is_ok(), "{password}");
+    }
+}
+
+#[test]
+fn ignores_pass_amount() {
+    let mut settings = settings();
+    settings.pass_amount = 2;
+    assert_eq!(settings.iter().unwrap().take(1000).count(), 1000);
+}
+
+#[test]
+fn not_enough_words_up_front() {
+    let mut settings = settings();
+    settings.clear_words();
+    assert!(matches!(
+        settings.iter(),
+        Err(GenerateError::NotEnoughWords { .. })
+    ));
+}
@@ -0,0 +1,112 @@
+//! The letters substituted with `leet`.
+
+use genrepass::{PasswordSettings, WordCase};
+
+/// Settings that always make `word` repeated to fill `length`, without changing its case.
+fn settings(word: &str, length: usize) -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str(word);
+    settings.length = length..=length;
+    settings.upper_amount = 0..=0;
+    settings.leet = true;
+    settings.pass_amount = 50;
+    settings
+}
+
+/// The positions where the password differs from the original,
+/// checking that every one of them is a substitution.
+fn substituted(settings: &PasswordSettings, password: &str, original: &str) -> Vec<usize> {
+    assert_eq!(
+        password.chars().count(),
+        original.chars().count(),
+        "{password}"
+    );
+    let substitutions = settings.get_leet_substitutions();
+    password
+        .chars()
+        .zip(original.chars())
+        .enumerate()
+        .filter(|(_, (new, old))| new != old)
+        .map(|(i, (new, old))| {
+            assert!(substitutions[&old].contains(new), "{password}");
+            i
+        })
+        .collect()
+}
+
+#[test]
+fn only_substituted_positions_differ() {
+    let mut settings = settings("assimilate", 20);
+    settings.number_amount = 2..=2;
+    settings.special_chars_amount = 1..=1;
+
+    for password in settings.generate().unwrap() {
+        let positions = substituted(&settings, &password, "assimilateassimilate");
+        assert_eq!(positions.len(), 3, "{password}");
+    }
+}
+
+#[test]
+fn fewer_eligible_letters() {
+    let mut settings = settings("brrrt", 5);
+    settings.number_amount = 3..=3;
+    settings.special_chars_amount = 2..=2;
+
+    for password in settings.generate().unwrap() {
+        assert_eq!(password, "brrr7");
+    }
+
+    let mut settings = self::settings("lymph", 5);
+    settings.number_amount = 3..=3;
+    for password in settings.generate().unwrap() {
+        assert_eq!(password, "lymph");
+    }
+}
+
+#[test]
+fn custom_substitutions() {
+    let mut settings = settings("banana", 6);
+    settings.number_amount = 3..=3;
+    settings.special_chars_amount = 0..=0;
+    settings.set_leet_substitution('a', "4");
+    settings.set_leet_substitution('n', "^");
+
+    for password in settings.generate().unwrap() {
+        let positions = substituted(&settings, &password, "banana");
+        assert_eq!(positions.len(), 3, "{password}");
+    }
+
+    settings.set_leet_substitution('a', "");
+    settings.set_leet_substitution('n', "");
+    assert_eq!(settings.generate().unwrap()[0], "banana");
+}
+
+#[test]
+fn uppercase_letters_and_first_letter() {
+    let mut settings = settings("ease", 4);
+    settings.word_case = WordCase::Capitalised;
+    settings.upper_amount = 1..=1;
+    settings.lower_amount = 0..=0;
+    settings.must_start_with_letter = true;
+    settings.number_amount = 4..=4;
+    settings.special_chars_amount = 0..=0;
+
+    for password in settings.generate().unwrap() {
+        assert_eq!(password, "E@$3");
+    }
+}
+
+#[test]
+fn keeps_the_length() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings.leet = true;
+    settings.length = 20..=24;
+    settings.number_amount = 3..=3;
+    settings.special_chars_amount = 3..=3;
+    settings.pass_amount = 50;
+
+    for password in settings.generate().unwrap() {
+        assert!((20..=24).contains(&password.len()), "{password}");
+    }
+}
@@ -0,0 +1,98 @@
+//! The `lexicon_cli` example, built into the tests so that the way it bridges
+//! a [`Lexicon`](genrepass::Lexicon) to the settings can't break unnoticed.
+
+#![cfg(feature = "from_path")]
+
+#[allow(dead_code)]
+#[path = "../examples/lexicon_cli.rs"]
+mod lexicon_cli;
+
+use std::{fs, path::PathBuf};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("genrepass-lexicon-cli-{}", std::process::id()))
+        .join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("notes.txt"),
+        "Ein Bär und ein Kätzchen gingen 42 Meilen weit, ohne Pause",
+    )
+    .unwrap();
+    fs::write(dir.join("binary"), [0x89, 0x00, 0xC3, 0x28]).unwrap();
+    dir
+}
+
+fn run(args: &[&str]) -> Result<Vec<String>, String> {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    let mut out = Vec::new();
+    lexicon_cli::run(&args, &mut out).map_err(|e| e.to_string())?;
+    Ok(String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+#[test]
+fn extracts_and_generates() {
+    let dir = temp_dir("generates");
+    let path = dir.to_str().unwrap();
+    let lines = run(&[
+        path,
+        "--filter",
+        "unicode-letters",
+        "--min-len",
+        "4",
+        "--count",
+        "3",
+    ]);
+    let lines = lines.unwrap();
+
+    assert_eq!(lines[0], "Read 1 files, skipped 1");
+    assert_eq!(
+        lines[1],
+        "Extracted 10 words (10 distinct), 3 to 8 characters long"
+    );
+    let fingerprint = lines[2].strip_prefix("Kept 6 words with the fingerprint ");
+    assert!(fingerprint.is_some_and(|f| u64::from_str_radix(f, 16).is_ok()));
+    assert_eq!(lines.len(), 6);
+    for password in &lines[3..] {
+        let letters: String = password.chars().filter(char::is_ascii_alphabetic).collect();
+        assert!(!letters.is_empty(), "{password}");
+    }
+
+    let lines = run(&[path, "--deunicode", "off", "--split", "ascii-whitespace"]).unwrap();
+    assert!(lines[2].starts_with("Kept 11 words"));
+    assert_eq!(lines.len(), 4);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn invalid_arguments() {
+    assert!(run(&[]).unwrap_err().contains("missing the path"));
+    assert!(run(&["a", "b"])
+        .unwrap_err()
+        .contains("unexpected argument 'b'"));
+    assert!(run(&["a", "--count"])
+        .unwrap_err()
+        .contains("missing the value"));
+    assert!(run(&["a", "--split", "x"])
+        .unwrap_err()
+        .contains("invalid value 'x'"));
+    assert!(run(&["a", "--colour", "red"])
+        .unwrap_err()
+        .contains("unknown option"));
+}
+
+#[cfg(feature = "schema")]
+#[test]
+fn prints_the_schema() {
+    let printed = run(&["--print-schema"]).unwrap().join("\n");
+    let schema: serde_json::Value = serde_json::from_str(&printed).unwrap();
+    assert_eq!(
+        schema,
+        serde_json::to_value(genrepass::PasswordSettings::json_schema()).unwrap()
+    );
+}
@@ -0,0 +1,87 @@
+//! The numbers and special characters kept apart with `no_adjacent_inserts`.
+
+use genrepass::{GenerateError, PasswordDetails, PasswordSettings};
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings.no_adjacent_inserts = true;
+    settings.number_amount = 0..=4;
+    settings.special_chars_amount = 0..=4;
+    settings.length = 8..=20;
+    settings.pass_amount = 20;
+    settings
+}
+
+/// Check that no two inserted characters are next to each other,
+/// and that the ones that aren't there were counted as dropped.
+fn assert_apart(details: &PasswordDetails) {
+    let password = &details.password;
+    let chars: Vec<char> = password.chars().collect();
+    for pair in chars.windows(2) {
+        assert!(
+            pair.iter().any(|c| c.is_alphabetic()),
+            "{pair:?} in {password}"
+        );
+    }
+
+    let rolled = details.rolled.numbers + details.rolled.special_chars;
+    let inserted = details.composition.digits + details.composition.special;
+    assert_eq!(inserted + details.dropped_inserts, rolled, "{password}");
+}
+
+#[test]
+fn inserts_are_never_adjacent() {
+    let mut settings = settings();
+    for (replace, boundaries) in [(false, false), (false, true), (true, false), (true, true)] {
+        settings.replace = replace;
+        settings.insert_at_boundaries = boundaries;
+        for must_start_with_letter in [false, true] {
+            settings.must_start_with_letter = must_start_with_letter;
+            for seed in 0..100 {
+                settings.seed = Some(seed);
+                for details in settings.generate_detailed().unwrap().passwords {
+                    assert_apart(&details);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn too_many_inserts_are_dropped() {
+    let mut settings = settings();
+    settings.number_amount = 6..=6;
+    settings.special_chars_amount = 6..=6;
+    settings.length = 10..=10;
+    settings.replace = true;
+    settings.pass_amount = 50;
+
+    // 10 characters have room for 5 apart from each other.
+    for details in settings.generate_detailed().unwrap().passwords {
+        assert_apart(&details);
+        assert_eq!(details.dropped_inserts, 7, "{}", details.password);
+    }
+
+    settings.strict_inserts = true;
+    assert!(matches!(
+        settings.generate(),
+        Err(GenerateError::PolicyRejected { .. })
+    ));
+}
+
+#[test]
+fn nothing_is_dropped_with_room_to_spare() {
+    let mut settings = settings();
+    settings.strict_inserts = true;
+    settings.number_amount = 2..=2;
+    settings.special_chars_amount = 2..=2;
+    settings.length = 30..=40;
+    settings.pass_amount = 100;
+
+    for details in settings.generate_detailed().unwrap().passwords {
+        assert_apart(&details);
+        assert_eq!(details.dropped_inserts, 0);
+        assert_eq!(details.retries.policy, 0);
+    }
+}
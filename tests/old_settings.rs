@@ -0,0 +1,114 @@
+//! Settings saved by earlier versions still loading.
+
+#![cfg(feature = "json")]
+
+use genrepass::{CaseRule, PasswordSettings, SourceMixing};
+
+/// What 1.1.4 saved with the serde feature.
+const SAVED_1_1_4: &str = r#"{
+  "capitalise": true,
+  "replace": false,
+  "randomise": false,
+  "pass_amount": 3,
+  "reset_amount": 25,
+  "length": {
+    "start": 24,
+    "end": 30
+  },
+  "number_amount": {
+    "start": 1,
+    "end": 2
+  },
+  "special_chars_amount": {
+    "start": 1,
+    "end": 2
+  },
+  "special_chars": "^!(-_=)$<[@.#]>%{~,+}&*",
+  "upper_amount": {
+    "start": 1,
+    "end": 2
+  },
+  "lower_amount": {
+    "start": 1,
+    "end": 2
+  },
+  "keep_numbers": false,
+  "force_upper": true,
+  "force_lower": false,
+  "dont_upper": false,
+  "dont_lower": true,
+  "words": [
+    "The",
+    "quick",
+    "brown",
+    "fox",
+    "jumps",
+    "over",
+    "the",
+    "lazy",
+    "dog"
+  ]
+}"#;
+
+#[test]
+fn loads_1_1_4() {
+    let settings: PasswordSettings = serde_json::from_str(SAVED_1_1_4).unwrap();
+    assert_eq!(settings.pass_amount, 3);
+    assert_eq!(settings.length, 24..=30);
+    assert_eq!(settings.retry_budget.assembly, 25);
+    assert_eq!(settings.get_special_chars(), "^!(-_=)$<[@.#]>%{~,+}&*");
+    assert_eq!(settings.words().len(), 9);
+    assert!(settings.separator.is_empty());
+    assert!(settings.warnings().is_empty());
+    assert_eq!(settings.generate().unwrap().len(), 3);
+}
+
+#[test]
+fn reset_amount_kept() {
+    let settings: PasswordSettings = serde_json::from_str(SAVED_1_1_4).unwrap();
+    let saved = serde_json::to_string(&settings).unwrap();
+    assert!(!saved.contains("reset_amount"));
+    let settings: PasswordSettings = serde_json::from_str(&saved).unwrap();
+    assert_eq!(settings.retry_budget.assembly, 25);
+
+    let untrusted = PasswordSettings::from_untrusted_json(SAVED_1_1_4).unwrap();
+    assert_eq!(untrusted.retry_budget.assembly, 25);
+    let over = SAVED_1_1_4.replace(r#""reset_amount": 25"#, r#""reset_amount": 1000000"#);
+    assert!(PasswordSettings::from_untrusted_json(&over).is_err());
+}
+
+#[test]
+fn case_flags_kept() {
+    let settings: PasswordSettings = serde_json::from_str(SAVED_1_1_4).unwrap();
+    assert_eq!(settings.upper_handling, CaseRule::Force);
+    assert_eq!(settings.lower_handling, CaseRule::Never);
+
+    let saved = serde_json::to_string(&settings).unwrap();
+    let settings: PasswordSettings = serde_json::from_str(&saved).unwrap();
+    assert_eq!(settings.upper_handling, CaseRule::Force);
+    assert_eq!(settings.lower_handling, CaseRule::Never);
+
+    // Forcing loses to never, like with the flags.
+    let both = SAVED_1_1_4.replace(r#""dont_upper": false"#, r#""dont_upper": true"#);
+    let settings: PasswordSettings = serde_json::from_str(&both).unwrap();
+    assert_eq!(settings.upper_handling, CaseRule::Never);
+
+    let forced = SAVED_1_1_4.replace(r#""force_upper": true"#, r#""force_upper": false"#);
+    let settings: PasswordSettings = serde_json::from_str(&forced).unwrap();
+    assert_eq!(settings.upper_handling, CaseRule::Auto);
+}
+
+#[test]
+fn words_given_a_source() {
+    let mut settings: PasswordSettings = serde_json::from_str(SAVED_1_1_4).unwrap();
+    assert_eq!(settings.sources().len(), 1);
+    assert!((0..9).all(|i| settings.word_source(i).is_some()));
+
+    settings.randomise = true;
+    settings.source_mixing = SourceMixing::RoundRobin;
+    assert_eq!(settings.generate().unwrap().len(), 3);
+
+    let json = serde_json::to_string(&settings).unwrap();
+    assert!(PasswordSettings::from_untrusted_json(&json).is_ok());
+    assert!(PasswordSettings::from_untrusted_json(SAVED_1_1_4).is_ok());
+}
@@ -0,0 +1,80 @@
+//! The deprecated `PassConfig` workflow from before `PasswordSettings`.
+
+#![allow(deprecated)]
+
+use genrepass::{ConfigError, GenerateError, PassConfig, PasswordSettings, WordCase};
+use std::error::Error;
+
+/// The workflow as it was written for the old configuration.
+fn old_workflow(text: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut config = PassConfig::new();
+    config.get_words_from_str(text);
+    config.pass_amount = 5;
+    config.word_case = WordCase::Capitalised;
+    config.length = 20..=30;
+
+    let validated = config.validate()?;
+    Ok(validated.generate()?)
+}
+
+#[test]
+fn old_workflow_generates() {
+    let passwords = old_workflow("the quick brown fox jumps over the lazy dog").unwrap();
+    assert_eq!(passwords.len(), 5);
+    for password in passwords {
+        assert!((20..=30).contains(&password.len()), "{password}");
+    }
+}
+
+#[test]
+fn no_words_only_fails_when_generating() {
+    let validated = PassConfig::new().validate().unwrap();
+    assert!(matches!(
+        validated.generate(),
+        Err(GenerateError::NotEnoughWords { .. })
+    ));
+
+    let error = old_workflow("").unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<GenerateError>(),
+        Some(GenerateError::NotEnoughWords { .. })
+    ));
+}
+
+#[test]
+fn validating_checks_like_the_builder() {
+    let mut config = PassConfig::new();
+    config.length = 8..=10;
+    config.number_amount = 6..=8;
+    config.special_chars_amount = 5..=5;
+    assert!(matches!(
+        config.validate(),
+        Err(ConfigError::InsertsExceedLength {
+            needed: 11,
+            length: 10
+        })
+    ));
+
+    let mut config = PassConfig::new();
+    #[allow(clippy::reversed_empty_ranges)]
+    {
+        config.upper_amount = 3..=2;
+    }
+    assert!(matches!(
+        config.validate(),
+        Err(ConfigError::Invalid { .. })
+    ));
+}
+
+#[test]
+fn validated_settings_are_frozen() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("sphinx of black quartz judge my vow");
+    let config = PassConfig::from(settings.clone());
+    assert_eq!(*config, settings);
+
+    let validated = config.validate().unwrap();
+    assert_eq!(validated.settings(), &settings);
+    let frozen = validated.into_frozen();
+    assert_eq!(frozen.generate().unwrap().len(), settings.pass_amount);
+}
@@ -0,0 +1,64 @@
+//! Words are extracted from a directory one file at a time,
+//! so the largest allocation should be about the size of a file, not of the whole directory.
+
+use genrepass::PasswordSettings;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fs,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+struct Counting;
+
+static LARGEST: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LARGEST.fetch_max(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        LARGEST.fetch_max(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[test]
+fn largest_allocation_is_a_single_file() {
+    const FILES: usize = 32;
+    const FILE_SIZE: usize = 64 * 1024;
+
+    let dir = std::env::temp_dir().join(format!("genrepass-path-memory-{}", std::process::id()));
+    fs::create_dir_all(dir.join("nested")).unwrap();
+    // Long words keep the vector of words small compared to the text.
+    let word = "a".repeat(1023) + " ";
+    for i in 0..FILES {
+        let sub = if i % 2 == 0 { "" } else { "nested" };
+        fs::write(
+            dir.join(sub).join(i.to_string()),
+            word.repeat(FILE_SIZE / 1024),
+        )
+        .unwrap();
+    }
+
+    let mut settings = PasswordSettings::new();
+    LARGEST.store(0, Ordering::Relaxed);
+    settings.get_words_from_path(&dir).unwrap();
+    let largest = LARGEST.load(Ordering::Relaxed);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(settings.words().len(), FILES * FILE_SIZE / 1024);
+    assert!(
+        largest < FILES * FILE_SIZE / 4,
+        "largest allocation was {largest} bytes for {} bytes of text",
+        FILES * FILE_SIZE
+    );
+}
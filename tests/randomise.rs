@@ -0,0 +1,98 @@
+//! The order of the words with `randomise`, shuffled for generation without being reordered.
+
+use genrepass::PasswordSettings;
+
+const FIRST: &str = "alpha bravo charlie delta echo foxtrot golf hotel india";
+const SECOND: &str = "juliett kilo lima mike november oscar papa quebec romeo";
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.randomise = true;
+    settings.get_words_from_str(FIRST);
+    settings.get_words_from_str(SECOND);
+    settings.pass_amount = 10;
+    settings
+}
+
+fn in_order() -> Vec<&'static str> {
+    FIRST.split(' ').chain(SECOND.split(' ')).collect()
+}
+
+#[test]
+fn loads_kept_in_order() {
+    let settings = settings();
+    assert_eq!(settings.words(), in_order());
+
+    settings.generate().unwrap();
+    settings.generate().unwrap();
+    assert_eq!(settings.words(), in_order());
+}
+
+#[test]
+fn shuffled_once_until_changed() {
+    let mut settings = settings();
+    let first: Vec<String> = settings
+        .mixed_words()
+        .into_iter()
+        .map(String::from)
+        .collect();
+    assert_ne!(first, in_order());
+    for _ in 0..5 {
+        settings.generate().unwrap();
+        assert_eq!(settings.mixed_words(), first);
+    }
+
+    let mut sorted = first.clone();
+    sorted.sort_unstable();
+    let mut expected = in_order();
+    expected.sort_unstable();
+    assert_eq!(sorted, expected);
+
+    // 21! orders, so keeping the same one means it wasn't shuffled again.
+    settings.get_words_from_str("sierra tango uniform");
+    let second = settings.mixed_words();
+    assert_eq!(second.len(), 21);
+    assert_ne!(second[..first.len()], first);
+}
+
+#[test]
+fn different_seed_for_a_call() {
+    let mut settings = settings();
+    settings.seed = Some(3);
+    let seeded: Vec<String> = settings
+        .mixed_words()
+        .into_iter()
+        .map(String::from)
+        .collect();
+    settings.seed = Some(4);
+    assert_ne!(settings.mixed_words(), seeded);
+    settings.seed = Some(3);
+    assert_eq!(settings.mixed_words(), seeded);
+}
+
+#[test]
+fn same_order_with_seed() {
+    let mut settings = settings();
+    settings.seed = Some(3);
+    let mixed = settings.mixed_words();
+    assert_ne!(mixed, in_order());
+    for _ in 0..5 {
+        assert_eq!(settings.mixed_words(), mixed);
+    }
+}
+
+#[test]
+fn shuffled_once_for_good() {
+    let mut settings = settings();
+    settings.shuffle_words();
+    let shuffled = settings.words().to_vec();
+    for _ in 0..5 {
+        assert_eq!(settings.mixed_words(), shuffled);
+    }
+    settings.generate().unwrap();
+    assert_eq!(settings.words(), shuffled);
+
+    settings.get_words_from_str("sierra tango uniform");
+    assert_eq!(settings.words()[..shuffled.len()], shuffled);
+    assert_ne!(settings.mixed_words()[..shuffled.len()], shuffled);
+}
@@ -0,0 +1,59 @@
+//! Configuration files checked against the JSON Schema of the settings.
+
+#![cfg(feature = "schema")]
+
+use genrepass::{BatchRetryMode, LengthMode, PasswordSettings, WordCase};
+use jsonschema::JSONSchema;
+use serde_json::{json, Value};
+
+fn schema() -> JSONSchema {
+    let schema = serde_json::to_value(PasswordSettings::json_schema()).unwrap();
+    JSONSchema::compile(&schema).unwrap()
+}
+
+fn config() -> Value {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings.word_case = WordCase::Capitalised;
+    settings.length_mode = LengthMode::Words(3..=5);
+    settings.batch_retry = BatchRetryMode::SharedPool(50);
+    settings
+        .add_special_group("brackets", "()[]", 1..=1)
+        .unwrap();
+    serde_json::to_value(settings).unwrap()
+}
+
+#[test]
+fn accepts_a_known_good_config() {
+    let schema = schema();
+    assert!(schema.is_valid(&config()));
+    assert!(schema.is_valid(&serde_json::to_value(PasswordSettings::default()).unwrap()));
+
+    // Missing fields are left as their defaults, like in settings saved by earlier versions.
+    let mut config = config();
+    config.as_object_mut().unwrap().remove("separator");
+    assert!(schema.is_valid(&config));
+    let settings: PasswordSettings = serde_json::from_value(config).unwrap();
+    assert!(settings.separator.is_empty());
+}
+
+#[test]
+fn rejects_known_bad_configs() {
+    let schema = schema();
+    let bad = |change: fn(&mut Value)| {
+        let mut config = config();
+        change(&mut config);
+        config
+    };
+
+    for config in [
+        bad(|c| c["length"] = json!("24-30")),
+        bad(|c| c["pass_amount"] = json!(-1)),
+        bad(|c| c["capitalise"] = json!("yes")),
+        bad(|c| c["word_selection"] = json!("Sometimes")),
+        bad(|c| c["retry_budget"] = json!({ "assembly": 10 })),
+    ] {
+        assert!(!schema.is_valid(&config), "{config}");
+        assert!(serde_json::from_value::<PasswordSettings>(config).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+//! The passwords generated with a seed.
+
+use genrepass::{PasswordSettings, SourceMixing};
+
+fn settings(seed: u64) -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings.get_words_from_str("sphinx of black quartz judge my vow");
+    settings.pass_amount = 50;
+    settings.randomise = true;
+    settings.source_mixing = SourceMixing::Shuffled;
+    settings.seed = Some(seed);
+    settings
+}
+
+#[test]
+fn same_seed() {
+    for seed in 0..20 {
+        let passwords = settings(seed).generate().unwrap();
+        assert_eq!(settings(seed).generate().unwrap(), passwords);
+        assert_ne!(settings(seed + 100).generate().unwrap(), passwords);
+    }
+}
+
+#[test]
+fn same_seed_consistent_batch() {
+    let mut first = settings(7);
+    first.consistent_batch = true;
+    first.number_amount = 1..=4;
+    let mut second = settings(7);
+    second.consistent_batch = true;
+    second.number_amount = 1..=4;
+
+    assert_eq!(
+        first.generate_detailed().unwrap().passwords,
+        second.generate_detailed().unwrap().passwords
+    );
+}
+
+#[test]
+fn no_seed() {
+    let mut settings = settings(0);
+    settings.seed = None;
+
+    assert_ne!(settings.generate().unwrap(), settings.generate().unwrap());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel() {
+    for seed in 0..20 {
+        let settings = settings(seed);
+        let passwords = settings.generate().unwrap();
+        assert_eq!(settings.generate_parallel().unwrap(), passwords);
+        assert_eq!(
+            settings
+                .generate_parallel_with_callback(|_, _| std::ops::ControlFlow::Continue(()))
+                .unwrap(),
+            passwords
+        );
+    }
+}
+
+#[test]
+fn given_rng() {
+    use rand::{rngs::mock::StepRng, rngs::StdRng, SeedableRng};
+
+    let settings = settings(0);
+    let passwords = settings
+        .generate_with_rng(&mut StdRng::seed_from_u64(5))
+        .unwrap();
+    assert_eq!(passwords.len(), 50);
+    assert_eq!(
+        settings
+            .generate_with_rng(&mut StdRng::seed_from_u64(5))
+            .unwrap(),
+        passwords
+    );
+    // The seed of the settings is ignored for the given generator.
+    assert_ne!(settings.generate().unwrap(), passwords);
+
+    let mut mock = StepRng::new(0, 1 << 40);
+    let mocked = settings.generate_with_rng(&mut mock).unwrap();
+    let mut mock = StepRng::new(0, 1 << 40);
+    assert_eq!(settings.generate_with_rng(&mut mock).unwrap(), mocked);
+}
+
+/// Settings sent to another "machine" generate the same passwords there.
+#[cfg(feature = "json")]
+#[test]
+fn serde_round_trip() {
+    let round_trip = |settings: &PasswordSettings| -> PasswordSettings {
+        serde_json::from_str(&serde_json::to_string(settings).unwrap()).unwrap()
+    };
+
+    for mixing in [
+        SourceMixing::Concatenate,
+        SourceMixing::Shuffled,
+        SourceMixing::RoundRobin,
+    ] {
+        for seed in 0..10 {
+            let mut settings = settings(seed);
+            settings.source_mixing = mixing;
+            let passwords = settings.generate().unwrap();
+            let received = round_trip(&settings);
+            assert_eq!(received.words(), settings.words());
+            assert_eq!(received.generate().unwrap(), passwords);
+            assert_eq!(round_trip(&received).generate().unwrap(), passwords);
+
+            // Shuffled for good before being sent, so the order is kept as it is.
+            settings.shuffle_words();
+            let passwords = settings.generate().unwrap();
+            assert_eq!(round_trip(&settings).generate().unwrap(), passwords);
+
+            // More words pending a shuffle after the stored one.
+            settings.get_words_from_str("pack my box with five dozen liquor jugs");
+            let passwords = settings.generate().unwrap();
+            assert_eq!(round_trip(&settings).generate().unwrap(), passwords);
+        }
+    }
+}
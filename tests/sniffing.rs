@@ -0,0 +1,72 @@
+//! Which files [`Lexicon::extract_words_from_path()`] accepts as text.
+
+#![cfg(feature = "from_path")]
+
+use genrepass::{Lexicon, Split};
+use std::{fs, path::PathBuf};
+
+fn words_of(name: &str, contents: &[u8]) -> Vec<String> {
+    // A directory per test, since they run in parallel.
+    let dir: PathBuf = std::env::temp_dir()
+        .join(format!("genrepass-sniffing-{}", std::process::id()))
+        .join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(name), contents).unwrap();
+
+    let mut lexicon = Lexicon::new(name, Split::UnicodeWords);
+    lexicon.extract_words_from_path(&[&dir], 1, None, |_| true);
+    fs::remove_dir_all(&dir).unwrap();
+
+    lexicon.words().to_vec()
+}
+
+#[test]
+fn utf8_with_bom() {
+    let mut contents = vec![0xEF, 0xBB, 0xBF];
+    contents.extend_from_slice("hello world".as_bytes());
+
+    assert_eq!(words_of("bom.txt", &contents), ["hello", "world"]);
+}
+
+#[test]
+fn utf16le() {
+    let mut contents = vec![0xFF, 0xFE];
+    contents.extend("hello world".encode_utf16().flat_map(u16::to_le_bytes));
+    let words = words_of("utf16le.txt", &contents);
+
+    if cfg!(feature = "utf16") {
+        assert_eq!(words, ["hello", "world"]);
+    } else {
+        assert!(words.is_empty(), "{words:?}");
+    }
+}
+
+#[test]
+fn binary_with_ascii_header() {
+    // Passes a check of only the first few bytes, and the rest is still valid UTF-8.
+    let mut contents =
+        b"PNG image exported by some tool, version 1.0.0 with a long header\n".to_vec();
+    contents.extend_from_slice(&[0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D]);
+    contents.extend((0..128).cycle().take(4096));
+
+    assert!(words_of("image", &contents).is_empty());
+}
+
+#[test]
+fn multibyte_char_past_the_first_bytes() {
+    let text = format!("{}ĳssel stroomt", "a".repeat(63));
+    assert!(!text.is_char_boundary(64));
+
+    assert_eq!(
+        words_of("straddling.txt", text.as_bytes()),
+        [format!("{}ĳssel", "a".repeat(63)), String::from("stroomt")]
+    );
+}
+
+#[test]
+fn multibyte_char_cut_off_by_the_sample() {
+    let text = format!("{} {}", "a".repeat(8 * 1024 - 2), "ĳ".repeat(100));
+    assert!(!text.is_char_boundary(8 * 1024));
+
+    assert_eq!(words_of("long.txt", text.as_bytes()).len(), 2);
+}
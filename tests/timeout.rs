@@ -0,0 +1,58 @@
+//! Giving up on generating after a deadline.
+
+use genrepass::{GenerateError, PasswordSettings, RetryBudget};
+use std::time::{Duration, Instant};
+
+/// Settings where every word is longer than the password, retrying practically forever.
+fn never_fits() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("incomprehensibilities counterrevolutionaries");
+    settings.length = 6..=6;
+    settings.retry_budget = RetryBudget {
+        assembly: usize::MAX,
+        ..Default::default()
+    };
+    settings
+}
+
+#[test]
+fn gives_up_in_time() {
+    let settings = never_fits();
+    let start = Instant::now();
+    let result = settings.try_generate(Duration::from_millis(50));
+
+    assert!(start.elapsed() < Duration::from_secs(5));
+    match result {
+        Err(GenerateError::TimedOut { source }) => {
+            assert_eq!(source.finished, 0);
+            assert!(source.resets > 0);
+        }
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+}
+
+#[test]
+fn counts_the_finished_passwords() {
+    let mut settings = never_fits();
+    settings.get_words_from_str("cat");
+    settings.word_selection = genrepass::WordSelection::Consecutive;
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+    settings.pass_amount = usize::MAX;
+
+    match settings.try_generate(Duration::from_millis(50)) {
+        Err(GenerateError::TimedOut { source }) => assert!(source.finished > 0),
+        other => panic!("expected a timeout, got {other:?}"),
+    }
+}
+
+#[test]
+fn finishes_in_time() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings.pass_amount = 20;
+    settings.seed = Some(5);
+
+    let passwords = settings.try_generate(Duration::from_secs(60)).unwrap();
+    assert_eq!(passwords, settings.generate().unwrap());
+}
@@ -0,0 +1,179 @@
+//! The events emitted with the `tracing` feature.
+
+#![cfg(feature = "tracing")]
+
+use genrepass::PasswordSettings;
+use std::{
+    fmt, fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Level, Metadata, Subscriber,
+};
+
+#[derive(Debug)]
+struct Recorded {
+    level: Level,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Recorded {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Keeps every event, without any spans.
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<Vec<Recorded>>>);
+
+impl Collector {
+    fn find(&self, message: &str) -> Vec<Recorded> {
+        let mut events = self.0.lock().unwrap();
+        let (found, rest) = events
+            .drain(..)
+            .partition(|e| e.field("message") == Some(message));
+        *events = rest;
+        found
+    }
+}
+
+impl Visit for Recorded {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.fields.push((field.name(), format!("{value:?}")));
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut recorded = Recorded {
+            level: *event.metadata().level(),
+            fields: Vec::new(),
+        };
+        event.record(&mut recorded);
+        self.0.lock().unwrap().push(recorded);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+fn collect(f: impl FnOnce()) -> Collector {
+    let collector = Collector::default();
+    tracing::subscriber::with_default(collector.clone(), f);
+    collector
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("genrepass-tracing-{}", std::process::id()))
+        .join(name);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn truncation() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("abcdefghij klmnopqrst");
+    settings.length = 15..=15;
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+    settings.retry_budget.assembly = 3;
+
+    let collector = collect(|| {
+        settings.generate().unwrap();
+    });
+
+    let truncations = collector.find("ran out of assembly retries, truncating the password");
+    assert_eq!(truncations.len(), 1);
+    assert_eq!(truncations[0].level, Level::WARN);
+    assert_eq!(truncations[0].field("resets"), Some("3"));
+
+    let generated = collector.find("generated a password");
+    assert_eq!(generated.len(), 1);
+    assert_eq!(generated[0].field("truncated"), Some("true"));
+    assert_eq!(generated[0].field("resets"), Some("3"));
+}
+
+#[test]
+fn clamped_inserts() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings.length = 6..=6;
+    settings.number_amount = 4..=4;
+    settings.special_chars_amount = 4..=4;
+
+    let collector = collect(|| {
+        settings.generate().unwrap();
+    });
+
+    let clamped = collector.find("too many numbers and special characters for the length");
+    assert_eq!(clamped.len(), 1);
+    assert_eq!(clamped[0].field("requested"), Some("8"));
+    assert_eq!(clamped[0].field("kept"), Some("6"));
+}
+
+#[test]
+fn skipped_binary_file() {
+    let dir = temp_dir("settings");
+    fs::write(dir.join("text.txt"), "hello world").unwrap();
+    fs::write(dir.join("binary"), [0xFF, 0xFE, 0x00, 0x89, 0x50]).unwrap();
+
+    let mut settings = PasswordSettings::new();
+    let collector = collect(|| {
+        settings.get_words_from_path(&dir).unwrap();
+    });
+    fs::remove_dir_all(&dir).unwrap();
+
+    let skipped = collector.find("skipping file");
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0].field("path").unwrap().ends_with("binary"));
+
+    let extracted = collector.find("extracted words from file");
+    assert_eq!(extracted.len(), 1);
+    assert!(extracted[0].field("path").unwrap().ends_with("text.txt"));
+    assert_eq!(extracted[0].field("words_added"), Some("2"));
+}
+
+#[cfg(feature = "from_path")]
+#[test]
+fn lexicon_skipped_binary_file() {
+    use genrepass::{Lexicon, Split};
+
+    let dir = temp_dir("lexicon");
+    fs::write(dir.join("text.txt"), "hello world").unwrap();
+    fs::write(dir.join("binary"), b"header\0\0\0\x01\x02").unwrap();
+
+    let mut lexicon = Lexicon::new("tracing", Split::UnicodeWords);
+    let collector = collect(|| {
+        lexicon.extract_words_from_path(&[&dir], 1, None, |_| true);
+    });
+    fs::remove_dir_all(&dir).unwrap();
+
+    let skipped = collector.find("skipping file that isn't text");
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0].field("path").unwrap().ends_with("binary"));
+
+    let extracted = collector.find("extracted words from paths");
+    assert_eq!(extracted.len(), 1);
+    assert_eq!(extracted[0].field("words_added"), Some("2"));
+}
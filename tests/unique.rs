@@ -0,0 +1,113 @@
+//! The passwords generated with `unique`.
+
+use genrepass::{GenerateError, PasswordSettings, RetryBudget};
+use std::collections::HashSet;
+
+/// Settings that can only make a handful of different passwords,
+/// like the six rotations and pairs of "a b c".
+fn settings(words: &str) -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str(words);
+    settings.length = 2..=3;
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+    settings.upper_amount = 0..=0;
+    settings.unique = true;
+    settings.retry_budget = RetryBudget {
+        uniqueness: 1000,
+        ..Default::default()
+    };
+    settings
+}
+
+fn assert_distinct(passwords: &[String]) {
+    let distinct: HashSet<_> = passwords.iter().collect();
+    assert_eq!(distinct.len(), passwords.len(), "{passwords:?}");
+}
+
+#[test]
+fn more_than_possible() {
+    let mut settings = settings("a b");
+    settings.length = 2..=2;
+    settings.pass_amount = 3;
+
+    match settings.generate() {
+        Err(GenerateError::CannotSatisfyUniqueness { source }) => {
+            assert_eq!(
+                source.to_string(),
+                "only 2 distinct passwords could be generated, even after 1000 retries"
+            );
+        }
+        other => panic!("expected CannotSatisfyUniqueness, got {other:?}"),
+    }
+
+    let mut passwords = settings.iter().unwrap();
+    assert_eq!(passwords.by_ref().count(), 2);
+    assert!(matches!(
+        passwords.error(),
+        Some(GenerateError::CannotSatisfyUniqueness { .. })
+    ));
+}
+
+#[test]
+fn distinct() {
+    let mut settings = settings("a b c");
+    settings.pass_amount = 6;
+    for _ in 0..20 {
+        assert_distinct(&settings.generate().unwrap());
+    }
+
+    for seed in 0..20 {
+        settings.seed = Some(seed);
+        let passwords = settings.generate().unwrap();
+        assert_distinct(&passwords);
+        assert_eq!(settings.generate().unwrap(), passwords);
+    }
+}
+
+#[test]
+fn retries_are_reported() {
+    let mut settings = settings("a b c");
+    settings.pass_amount = 6;
+    settings.seed = Some(1);
+
+    let details = settings.generate_detailed().unwrap();
+    assert!(details.passwords.iter().any(|p| p.retries.uniqueness > 0));
+}
+
+#[test]
+fn repeats_without_unique() {
+    let mut settings = settings("a b c");
+    settings.unique = false;
+    settings.pass_amount = 50;
+
+    let passwords = settings.generate().unwrap();
+    assert!(passwords.iter().collect::<HashSet<_>>().len() < passwords.len());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_same_as_sequential() {
+    let mut settings = settings("a b c");
+    settings.pass_amount = 6;
+    for seed in 0..20 {
+        settings.seed = Some(seed);
+        let passwords = settings.generate().unwrap();
+        assert_eq!(settings.generate_parallel().unwrap(), passwords);
+        assert_eq!(
+            settings
+                .generate_parallel_with_callback(|_, _| std::ops::ControlFlow::Continue(()))
+                .unwrap(),
+            passwords
+        );
+    }
+
+    settings.seed = None;
+    assert_distinct(&settings.generate_parallel().unwrap());
+    settings.length = 2..=2;
+    settings.pass_amount = 7;
+    assert!(matches!(
+        settings.generate_parallel(),
+        Err(GenerateError::CannotSatisfyUniqueness { .. })
+    ));
+}
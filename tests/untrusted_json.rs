@@ -0,0 +1,266 @@
+//! [`PasswordSettings::from_untrusted_json()`] with adversarial input.
+
+#![cfg(feature = "json")]
+
+use genrepass::{PasswordSettings, UntrustedSettingsError};
+use serde_json::Value;
+
+/// Valid settings as a JSON value, to be tampered with.
+fn valid() -> Value {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    serde_json::to_value(&settings).unwrap()
+}
+
+fn parse(value: &Value) -> Result<PasswordSettings, UntrustedSettingsError> {
+    PasswordSettings::from_untrusted_json(&value.to_string())
+}
+
+fn with(field: &str, value: Value) -> Value {
+    let mut settings = valid();
+    settings[field] = value;
+    settings
+}
+
+#[test]
+fn valid_settings_generate() {
+    let settings = parse(&valid()).unwrap();
+    assert_eq!(settings.generate().unwrap().len(), 1);
+}
+
+#[test]
+fn huge_numbers() {
+    for (field, value) in [
+        ("pass_amount", Value::from(u64::MAX)),
+        (
+            "length",
+            serde_json::json!({ "start": 1, "end": 1_000_000 }),
+        ),
+        (
+            "number_amount",
+            serde_json::json!({ "start": 0, "end": u64::MAX }),
+        ),
+        (
+            "special_chars_amount",
+            serde_json::json!({ "start": 0, "end": 5000 }),
+        ),
+        (
+            "retry_budget",
+            serde_json::json!({ "assembly": u64::MAX, "policy": 0, "uniqueness": 0 }),
+        ),
+        (
+            "upper_amount",
+            serde_json::json!({ "start": 0, "end": u64::MAX }),
+        ),
+        (
+            "lower_amount",
+            serde_json::json!({ "start": 0, "end": 5000 }),
+        ),
+        (
+            "insert_zone",
+            serde_json::json!({ "start": 0, "end": u64::MAX }),
+        ),
+    ] {
+        assert!(
+            matches!(
+                parse(&with(field, value)),
+                Err(UntrustedSettingsError::OverLimit { .. })
+            ),
+            "{field}"
+        );
+    }
+
+    // Doesn't fit in a usize at all.
+    let json = valid()
+        .to_string()
+        .replace(r#""pass_amount":1"#, r#""pass_amount":1e400"#);
+    assert!(matches!(
+        PasswordSettings::from_untrusted_json(&json),
+        Err(UntrustedSettingsError::Json { .. })
+    ));
+    let json = valid()
+        .to_string()
+        .replace(r#""pass_amount":1"#, r#""pass_amount":-1"#);
+    assert!(PasswordSettings::from_untrusted_json(&json).is_err());
+}
+
+#[test]
+fn contradictory_ranges() {
+    for field in [
+        "length",
+        "number_amount",
+        "upper_amount",
+        "lower_amount",
+        "insert_zone",
+    ] {
+        let settings = with(field, serde_json::json!({ "start": 10, "end": 2 }));
+        assert!(
+            matches!(
+                parse(&settings),
+                Err(UntrustedSettingsError::EmptyRange { .. })
+            ),
+            "{field}"
+        );
+    }
+}
+
+#[test]
+fn invalid_probability() {
+    for probability in [-0.1, 1.5, f64::MAX] {
+        assert!(matches!(
+            parse(&with("continue_probability", Value::from(probability))),
+            Err(UntrustedSettingsError::InvalidProbability { .. })
+        ));
+    }
+    // Not a number at all.
+    assert!(matches!(
+        parse(&with("continue_probability", Value::Null)),
+        Err(UntrustedSettingsError::Json { .. })
+    ));
+}
+
+#[test]
+fn words_that_would_break_generation() {
+    let mut settings = valid();
+    settings["words"][0] = Value::from("");
+    assert!(matches!(
+        parse(&settings),
+        Err(UntrustedSettingsError::Empty { field: "words" })
+    ));
+
+    let mut settings = valid();
+    settings["word_sources"][0] = Value::from(7);
+    assert!(matches!(
+        parse(&settings),
+        Err(UntrustedSettingsError::MismatchedSources)
+    ));
+
+    let mut settings = valid();
+    settings["word_sources"].as_array_mut().unwrap().pop();
+    assert!(matches!(
+        parse(&settings),
+        Err(UntrustedSettingsError::MismatchedSources)
+    ));
+
+    let mut settings = valid();
+    settings["sources"][0]["weight"] = Value::from(u32::MAX);
+    settings["sources"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({ "label": "other", "weight": 2 }));
+    assert!(matches!(
+        parse(&settings),
+        Err(UntrustedSettingsError::OverLimit { .. })
+    ));
+}
+
+#[test]
+fn exclusions_and_exceptions() {
+    for field in ["context_exclusions", "capitalise_exceptions"] {
+        let many: Vec<String> = (0..200_000).map(|i| format!("word{i}")).collect();
+        assert!(
+            matches!(
+                parse(&with(field, serde_json::json!(many))),
+                Err(UntrustedSettingsError::OverLimit { .. })
+            ),
+            "{field}"
+        );
+        assert!(matches!(
+            parse(&with(field, serde_json::json!(["a".repeat(1025)]))),
+            Err(UntrustedSettingsError::OverLimit { .. })
+        ));
+        assert!(matches!(
+            parse(&with(field, serde_json::json!(["fox", ""]))),
+            Err(UntrustedSettingsError::Empty { .. })
+        ));
+
+        let settings = parse(&with(field, serde_json::json!(["cat", "bird"]))).unwrap();
+        assert!(settings.generate().is_ok());
+    }
+}
+
+#[test]
+fn special_chars() {
+    assert!(matches!(
+        parse(&with("special_chars", Value::from("€"))),
+        Err(UntrustedSettingsError::NonAsciiSpecialChars)
+    ));
+    assert!(matches!(
+        parse(&with("special_chars", Value::from("!".repeat(257)))),
+        Err(UntrustedSettingsError::OverLimit { .. })
+    ));
+    assert!(matches!(
+        parse(&with("special_sequences", serde_json::json!(["!!", ""]))),
+        Err(UntrustedSettingsError::Empty { .. })
+    ));
+
+    let group = |chars: &str, start: usize, end: usize| serde_json::json!([{ "name": "g", "chars": chars, "amount": { "start": start, "end": end } }]);
+    assert!(matches!(
+        parse(&with("special_groups", group("€", 1, 1))),
+        Err(UntrustedSettingsError::NonAsciiSpecialChars)
+    ));
+    assert!(matches!(
+        parse(&with("special_groups", group("#", 2, 1))),
+        Err(UntrustedSettingsError::EmptyRange { .. })
+    ));
+    assert!(matches!(
+        parse(&with("special_groups", group("#", 1, 5000))),
+        Err(UntrustedSettingsError::OverLimit { .. })
+    ));
+    assert!(parse(&with("special_groups", group("#", 1, 1))).is_ok());
+}
+
+#[test]
+fn deep_nesting() {
+    let json = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+    assert!(matches!(
+        PasswordSettings::from_untrusted_json(&json),
+        Err(UntrustedSettingsError::Json { .. })
+    ));
+
+    let mut settings = valid();
+    let mut nested = Value::from(1);
+    for _ in 0..1000 {
+        nested = serde_json::json!({ "start": nested, "end": 1 });
+    }
+    settings["length"] = nested;
+    assert!(matches!(
+        parse(&settings),
+        Err(UntrustedSettingsError::Json { .. })
+    ));
+}
+
+#[test]
+fn invalid_escapes() {
+    for escape in [r"\ud800", r"\udfff\ud800", r"\u12", r"\x41", r"\"] {
+        let json = valid()
+            .to_string()
+            .replace(r#""the""#, &format!(r#""the{escape}""#));
+        assert!(matches!(
+            PasswordSettings::from_untrusted_json(&json),
+            Err(UntrustedSettingsError::Json { .. })
+        ));
+    }
+}
+
+#[test]
+fn not_settings() {
+    for json in [
+        "",
+        "null",
+        "[]",
+        "\"settings\"",
+        "{\"length\":",
+        "\u{feff}{}",
+    ] {
+        assert!(matches!(
+            PasswordSettings::from_untrusted_json(json),
+            Err(UntrustedSettingsError::Json { .. })
+        ));
+    }
+
+    // Every field missing is left as its default, which has no words to generate from.
+    let settings = PasswordSettings::from_untrusted_json("{}").unwrap();
+    assert_eq!(settings.length, PasswordSettings::default().length);
+    assert!(settings.generate().is_err());
+}
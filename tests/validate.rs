@@ -0,0 +1,109 @@
+//! Every way of generating failing with the same error as `can_generate()`.
+
+use genrepass::{GenerateError, GenerateOptions, Lexicon, PasswordSettings, Split};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{ops::ControlFlow, time::Duration};
+
+/// Check that each way of generating returns the error of `can_generate()`.
+fn assert_same_error(settings: &PasswordSettings) {
+    let expected = settings.can_generate().unwrap_err().to_string();
+    let mut lexicon = Lexicon::new("lexicon", Split::UnicodeWords);
+    lexicon.extract_words("the quick brown fox", |_| true);
+
+    let errors = [
+        settings.generate().err(),
+        settings.generate_n(5).err(),
+        settings.generate_with(&GenerateOptions::default()).err(),
+        settings
+            .generate_with_rng(&mut StdRng::seed_from_u64(0))
+            .err(),
+        settings.iter().err(),
+        settings.try_generate(Duration::from_secs(10)).err(),
+        settings.generate_for_keys(["alice", "bob"]).err(),
+        settings
+            .generate_with_callback(|_, _| ControlFlow::Continue(()))
+            .err(),
+        settings.generate_detailed().err(),
+        settings.draft(&mut StdRng::seed_from_u64(0)).err(),
+        settings.freeze().generate().err(),
+        settings.dry_run().err(),
+        #[cfg(feature = "rayon")]
+        settings.generate_parallel().err(),
+        #[cfg(feature = "rayon")]
+        settings
+            .generate_parallel_with_callback(|_, _| ControlFlow::Continue(()))
+            .err(),
+    ];
+    for error in errors {
+        assert_eq!(error.map(|e| e.to_string()).as_deref(), Some(&*expected));
+    }
+
+    if settings.words().is_empty() {
+        assert!(settings.generate_with_lexicon(&lexicon).is_ok());
+    } else {
+        let error = settings.generate_with_lexicon(&lexicon).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+    }
+}
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn empty_ranges() {
+    let mut settings = settings();
+    settings.length = 20..=10;
+    assert_same_error(&settings);
+
+    let mut settings = self::settings();
+    settings.upper_amount = 3..=2;
+    assert_same_error(&settings);
+
+    let mut settings = self::settings();
+    settings.insert_zone = Some(5..=4);
+    assert_same_error(&settings);
+}
+
+#[test]
+fn unsatisfiable_inserts() {
+    let mut settings = settings();
+    settings.distinct_inserts = true;
+    settings.number_amount = 11..=11;
+    assert_same_error(&settings);
+
+    let mut settings = self::settings();
+    settings
+        .add_special_group("brackets", "()[]", 40..=40)
+        .unwrap();
+    assert_same_error(&settings);
+
+    let mut settings = self::settings();
+    settings.replace = true;
+    settings.insert_zone = Some(30..=40);
+    assert_same_error(&settings);
+}
+
+#[test]
+fn no_words() {
+    let mut settings = settings();
+    settings.clear_words();
+    assert_same_error(&settings);
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn empty_range_in_options() {
+    let settings = settings();
+    let options = GenerateOptions {
+        length: Some(20..=10),
+        ..Default::default()
+    };
+    assert!(matches!(
+        settings.generate_with(&options),
+        Err(GenerateError::EmptyRange { .. })
+    ));
+}
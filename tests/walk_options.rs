@@ -0,0 +1,81 @@
+//! How [`Lexicon::extract_words_from_path_with()`] walks a tree with different [`WalkOptions`].
+
+#![cfg(feature = "from_path")]
+
+use genrepass::{Lexicon, Split, WalkOptions};
+use std::{fs, path::PathBuf};
+
+/// A file at every level of three nested directories, in two kinds of files.
+fn tree(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("genrepass-walk-options-{}", std::process::id()))
+        .join(name);
+    fs::create_dir_all(dir.join("one").join("two")).unwrap();
+    fs::write(dir.join("top.md"), "top").unwrap();
+    fs::write(dir.join("top.rs"), "rust").unwrap();
+    fs::write(dir.join("one").join("middle.md"), "middle").unwrap();
+    fs::write(dir.join("one").join("two").join("bottom.txt"), "bottom").unwrap();
+    dir
+}
+
+fn extract(dir: &PathBuf, options: &WalkOptions) -> Vec<String> {
+    let mut lexicon = Lexicon::new("tree", Split::UnicodeWords);
+    lexicon.extract_words_from_path_with(&[dir], options, |_| true);
+    lexicon.words().to_vec()
+}
+
+#[test]
+fn combinations() {
+    let dir = tree("combinations");
+    let cases = [
+        (WalkOptions::new(), &["middle", "bottom", "top", "rust"][..]),
+        (WalkOptions::new().depth(0), &[]),
+        (WalkOptions::new().depth(1), &["top", "rust"]),
+        (WalkOptions::new().depth(2), &["middle", "top", "rust"]),
+        (WalkOptions::new().extensions(["MD"]), &["middle", "top"]),
+        (
+            WalkOptions::new().depth(1).extensions(["md", "rs"]),
+            &["top", "rust"],
+        ),
+        (WalkOptions::new().extensions(["txt"]), &["bottom"]),
+    ];
+    let words: Vec<_> = cases
+        .iter()
+        .map(|(options, _)| extract(&dir, options))
+        .collect();
+    fs::remove_dir_all(&dir).unwrap();
+
+    for ((options, expected), words) in cases.iter().zip(words) {
+        assert_eq!(words, *expected, "{options:?}");
+    }
+}
+
+#[test]
+fn same_as_positional() {
+    let dir = tree("positional");
+    let mut lexicon = Lexicon::new("tree", Split::UnicodeWords);
+    lexicon.extract_words_from_path(&[&dir], 2, Some(&["md"]), |_| true);
+    let with = extract(&dir, &WalkOptions::new().depth(2).extensions(["md"]));
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(lexicon.words(), with);
+}
+
+#[cfg(unix)]
+#[test]
+fn follow_links() {
+    let dir = tree("links");
+    let linked = dir.parent().unwrap().join("links-target");
+    fs::create_dir_all(&linked).unwrap();
+    fs::write(linked.join("linked.md"), "linked").unwrap();
+    std::os::unix::fs::symlink(&linked, dir.join("link")).unwrap();
+
+    let options = WalkOptions::new().depth(2).extensions(["md"]);
+    let not_followed = extract(&dir, &options);
+    let followed = extract(&dir, &options.follow_links(true));
+    fs::remove_dir_all(&dir).unwrap();
+    fs::remove_dir_all(&linked).unwrap();
+
+    assert_eq!(not_followed, ["middle", "top"]);
+    assert_eq!(followed, ["linked", "middle", "top"]);
+}
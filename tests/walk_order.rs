@@ -0,0 +1,48 @@
+//! Sorting the entries of directories gives the words in the same order on every platform.
+
+use genrepass::PasswordSettings;
+use std::{fs, path::PathBuf};
+
+/// Files whose names are the words they contain, created out of order,
+/// along with a nested directory that sorts between them.
+fn unsorted_tree(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("genrepass-walk-order-{}", std::process::id()))
+        .join(name);
+    fs::create_dir_all(dir.join("m")).unwrap();
+
+    for word in ["q", "c", "x", "a", "k"] {
+        fs::write(dir.join(word), word).unwrap();
+    }
+    for word in ["mz", "mb"] {
+        fs::write(dir.join("m").join(word), word).unwrap();
+    }
+
+    dir
+}
+
+const SORTED: [&str; 7] = ["a", "c", "k", "mb", "mz", "q", "x"];
+
+#[test]
+fn settings_sort_entries() {
+    let dir = unsorted_tree("settings");
+    let mut settings = PasswordSettings::new();
+    settings.sort_entries = true;
+    settings.get_words_from_path(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(settings.words(), SORTED);
+}
+
+#[cfg(feature = "from_path")]
+#[test]
+fn lexicon_sorts_entries_by_default() {
+    use genrepass::{Lexicon, Split};
+
+    let dir = unsorted_tree("lexicon");
+    let mut lexicon = Lexicon::new("tree", Split::UnicodeWords);
+    lexicon.extract_words_from_path(&[&dir], 2, None, |_| true);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(lexicon.words(), SORTED);
+}
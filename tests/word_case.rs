@@ -0,0 +1,151 @@
+//! The case of the words with `word_case`, and what the case adjustments do with it.
+
+use genrepass::{CaseRule, GenerateOptions, PasswordSettings, WordCase};
+
+const TEXT: &str = "the Quick brown FOX jumps over the lazy dog";
+
+fn settings(case: WordCase) -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str(TEXT);
+    settings.word_case = case;
+    settings.separator = String::from("-");
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+    settings.length = 16..=30;
+    settings.pass_amount = 100;
+    settings
+}
+
+fn words(password: &str) -> Vec<&str> {
+    password
+        .split('-')
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[test]
+fn lower_is_never_uppercased() {
+    let mut settings = settings(WordCase::Lower);
+    settings.upper_handling = CaseRule::Force;
+    settings.upper_amount = 3..=3;
+
+    for password in settings.generate().unwrap() {
+        assert_eq!(password, password.to_lowercase());
+    }
+}
+
+#[test]
+fn upper_is_never_lowercased() {
+    let mut settings = settings(WordCase::Upper);
+    settings.lower_handling = CaseRule::Force;
+    settings.lower_amount = 3..=3;
+
+    for password in settings.generate().unwrap() {
+        assert_eq!(password, password.to_uppercase());
+    }
+}
+
+#[test]
+fn capitalised_keeps_the_initials() {
+    let mut settings = settings(WordCase::Capitalised);
+    settings.lower_handling = CaseRule::Force;
+    settings.lower_amount = 40..=40;
+
+    for password in settings.generate().unwrap() {
+        for word in words(&password) {
+            assert!(word.starts_with(char::is_uppercase), "{password}");
+        }
+    }
+}
+
+#[test]
+fn camel() {
+    let mut settings = settings(WordCase::Camel);
+    settings.separator = String::new();
+    settings.upper_amount = 0..=0;
+    settings.lower_handling = CaseRule::Force;
+    settings.lower_amount = 40..=40;
+
+    for details in settings.generate_detailed().unwrap().passwords {
+        let password = &details.password;
+        let mut spans = details.word_spans.iter();
+        let first = &password[spans.next().unwrap().clone()];
+        assert_eq!(first, first.to_lowercase(), "{password}");
+        for span in spans {
+            assert!(
+                password[span.clone()].starts_with(char::is_uppercase),
+                "{password}"
+            );
+        }
+    }
+
+    settings.add_capitalise_exception("fOX");
+    for password in settings.generate().unwrap() {
+        assert!(
+            !password.contains("Fox") && !password.contains("FOX"),
+            "{password}"
+        );
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn capitalise_is_an_alias() {
+    let mut old = settings(WordCase::AsIs);
+    old.capitalise = true;
+    old.seed = Some(5);
+    let mut new = settings(WordCase::Capitalised);
+    new.seed = Some(5);
+    assert_eq!(old.generate().unwrap(), new.generate().unwrap());
+
+    // The case that's set wins over the alias.
+    old.word_case = WordCase::Upper;
+    for password in old.generate().unwrap() {
+        assert_eq!(password, password.to_uppercase());
+    }
+
+    let built = PasswordSettings::builder()
+        .capitalise(true)
+        .build()
+        .unwrap();
+    assert_eq!(built.word_case, WordCase::Capitalised);
+
+    let options = GenerateOptions {
+        capitalise: Some(false),
+        ..Default::default()
+    };
+    let as_is = new.generate_with(&options).unwrap();
+    new.word_case = WordCase::AsIs;
+    assert_eq!(as_is, new.generate().unwrap());
+}
+
+#[test]
+fn overridden_per_call() {
+    let settings = settings(WordCase::Lower);
+    let options = GenerateOptions {
+        word_case: Some(WordCase::Upper),
+        ..Default::default()
+    };
+
+    for password in settings.generate_with(&options).unwrap() {
+        assert_eq!(password, password.to_uppercase());
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn capitalise_in_saved_settings() {
+    let settings = settings(WordCase::AsIs);
+    let mut value = serde_json::to_value(&settings).unwrap();
+    value["capitalise"] = serde_json::json!(true);
+    value.as_object_mut().unwrap().remove("word_case");
+
+    let mut saved: PasswordSettings = serde_json::from_value(value).unwrap();
+    saved.lower_handling = CaseRule::Force;
+    saved.lower_amount = 40..=40;
+    for password in saved.generate().unwrap() {
+        for word in words(&password) {
+            assert!(word.starts_with(char::is_uppercase), "{password}");
+        }
+    }
+}
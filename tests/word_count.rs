@@ -0,0 +1,80 @@
+//! Passwords with their length measured in words.
+
+use genrepass::{CaseRule, LengthMode, PasswordSettings, WordCase, WordSelection};
+
+const WORDS: [&str; 8] = [
+    "correct", "horse", "battery", "staple", "wizard", "quietly", "packs", "jugs",
+];
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str(&WORDS.join(" "));
+    settings.length_mode = LengthMode::Words(4..=4);
+    settings.pass_amount = 200;
+    settings
+}
+
+#[test]
+fn exactly_the_amount_of_source_words() {
+    let mut settings = settings();
+    settings.word_case = WordCase::Capitalised;
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+
+    for word_selection in [WordSelection::Consecutive, WordSelection::Uniform] {
+        settings.word_selection = word_selection;
+        for details in settings.generate_detailed().unwrap().passwords {
+            let password = &details.password;
+            assert_eq!(details.word_spans.len(), 4, "{password}");
+            assert!(!details.truncated);
+            let joined: String = details
+                .word_spans
+                .iter()
+                .map(|span| {
+                    let word = password[span.clone()].to_lowercase();
+                    assert!(WORDS.contains(&word.as_str()), "{password}");
+                    word
+                })
+                .collect();
+            assert_eq!(joined, password.to_lowercase());
+        }
+    }
+}
+
+#[test]
+fn inserts_on_top() {
+    let mut settings = settings();
+    settings.separator = "-".to_string();
+    settings.number_amount = 3..=3;
+    settings.special_chars_amount = 3..=3;
+    settings.set_special_chars("!@#").unwrap();
+
+    for replace in [false, true] {
+        settings.replace = replace;
+        for details in settings.generate_detailed().unwrap().passwords {
+            assert_eq!(details.composition.digits, 3, "{}", details.password);
+            assert_eq!(details.composition.special, 3, "{}", details.password);
+            assert_eq!(settings.conforms(&details.password), Ok(()));
+            if !replace {
+                let letters: String = details
+                    .password
+                    .chars()
+                    .filter(|c| c.is_ascii_alphabetic() || *c == '-')
+                    .collect();
+                assert_eq!(letters.split('-').count(), 4, "{}", details.password);
+            }
+        }
+    }
+}
+
+#[test]
+fn single_word() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("horse");
+    settings.length_mode = LengthMode::Words(3..=3);
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+    settings.upper_handling = CaseRule::Never;
+
+    assert_eq!(settings.generate().unwrap(), ["horsehorsehorse"]);
+}
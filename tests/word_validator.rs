@@ -0,0 +1,103 @@
+//! The words left out by a `word_validator`.
+
+use genrepass::{CharFilter, Deunicode, ExtractionBackend, PasswordSettings, Split};
+
+const TEXT: &str = "alpha bravo marker charlie Marker delta MARKER echo foxtrot";
+
+fn not_marker(word: &str) -> bool {
+    !word.eq_ignore_ascii_case("marker")
+}
+
+fn assert_no_marker(settings: &mut PasswordSettings) {
+    assert!(settings.words().iter().all(|word| not_marker(word)));
+
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+    settings.pass_amount = 200;
+    for password in settings.generate().unwrap() {
+        assert!(!password.to_lowercase().contains("marker"), "{password}");
+    }
+}
+
+#[test]
+fn rejected_words_are_never_stored_or_generated() {
+    for backend in [
+        ExtractionBackend::Legacy,
+        ExtractionBackend::Lexicon {
+            split: Split::UnicodeWords,
+            deunicode: Deunicode::BeforeFiltering,
+            filter: CharFilter::AsciiWithoutDigitsOrPunctuation,
+        },
+    ] {
+        let mut settings = PasswordSettings::new();
+        settings.extraction = backend;
+        settings.set_word_validator(not_marker);
+        settings.get_words_from_str(TEXT);
+        assert_eq!(settings.words().len(), 6);
+        assert_no_marker(&mut settings);
+    }
+}
+
+#[test]
+fn transliterated_words_are_validated() {
+    let mut settings = PasswordSettings::new();
+    settings.set_word_validator(not_marker);
+    settings.get_words_from_str("märker mårker MÄRKER plain");
+    assert_eq!(settings.words(), ["plain"]);
+
+    // Without transliterating, the validator sees the words as they are.
+    settings.clear_words();
+    settings.transliterate = false;
+    settings.get_words_from_str("märker plain");
+    assert_eq!(settings.words(), ["märker", "plain"]);
+}
+
+#[test]
+fn stored_words_are_validated_on_request() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str(TEXT);
+    let stored = settings.clone_words();
+
+    let mut restored = PasswordSettings::new();
+    restored.set_word_validator(not_marker);
+    restored.set_words(stored);
+    assert_eq!(restored.words().len(), 9);
+    assert_eq!(restored.retain_valid_words(), 3);
+    assert_no_marker(&mut restored);
+
+    restored.clear_word_validator();
+    assert_eq!(restored.retain_valid_words(), 0);
+}
+
+#[test]
+fn all_rejected_adds_no_source() {
+    let mut settings = PasswordSettings::new();
+    settings.set_word_validator(|_| false);
+    assert!(settings.get_words_from_str(TEXT).is_none());
+    assert!(settings.words().is_empty());
+    assert!(settings.generate().is_err());
+}
+
+#[cfg(feature = "profanity")]
+#[test]
+fn profanity_is_left_out() {
+    use genrepass::WordValidator;
+
+    let mut settings = PasswordSettings::new();
+    settings.word_validator = Some(WordValidator::Profanity);
+    settings.get_words_from_str("What the Fuck is this shit, said the kind assistant");
+    assert_eq!(
+        settings.words(),
+        [
+            "What",
+            "the",
+            "is",
+            "this",
+            "said",
+            "the",
+            "kind",
+            "assistant"
+        ]
+    );
+    assert_no_marker(&mut settings);
+}
@@ -0,0 +1,35 @@
+//! Wrapping around from the last word to the first doesn't favour any of the words.
+
+use genrepass::PasswordSettings;
+use std::collections::HashMap;
+
+#[test]
+fn even_word_usage_across_sources() {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("aaa bbb ccc ddd");
+    settings.get_words_from_str("eee fff ggg hhh");
+    settings.length = 9..=9;
+    settings.number_amount = 0..=0;
+    settings.special_chars_amount = 0..=0;
+    settings.upper_amount = 0..=0;
+    settings.lower_amount = 0..=0;
+    settings.pass_amount = 8000;
+
+    let report = settings.generate_detailed().unwrap();
+    let mut usage: HashMap<&str, usize> = HashMap::new();
+    let mut wrapped = 0;
+    for details in &report.passwords {
+        wrapped += usize::from(details.wrapped);
+        for span in &details.word_spans {
+            *usage.entry(&details.password[span.clone()]).or_default() += 1;
+        }
+    }
+
+    // Starting from either of the last two words wraps around.
+    assert!(wrapped > 1000, "{wrapped}");
+    assert_eq!(usage.len(), 8);
+    // 24000 words in total, so 3000 each.
+    for (word, count) in usage {
+        assert!((2550..=3450).contains(&count), "{word}: {count}");
+    }
+}
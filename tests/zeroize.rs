@@ -0,0 +1,45 @@
+//! The passwords that are overwritten when they're dropped.
+
+#![cfg(feature = "zeroize")]
+
+use genrepass::{PasswordSettings, WordCase, Zeroizing};
+
+fn settings() -> PasswordSettings {
+    let mut settings = PasswordSettings::new();
+    settings.get_words_from_str("the quick brown fox jumps over the lazy dog");
+    settings.word_case = WordCase::Capitalised;
+    settings.pass_amount = 20;
+    settings
+}
+
+#[test]
+fn same_passwords_as_generate() {
+    let mut settings = settings();
+    settings.seed = Some(11);
+    let secure: Vec<Zeroizing<String>> = settings.generate_secure().unwrap();
+    let plain = settings.generate().unwrap();
+
+    assert_eq!(secure.len(), 20);
+    for (secure, plain) in secure.iter().zip(&plain) {
+        assert_eq!(secure.as_str(), plain);
+    }
+}
+
+#[test]
+fn every_stage_still_applies() {
+    let mut settings = settings();
+    settings.add_context_exclusion("fox");
+    settings.retry_budget.policy = 1000;
+    settings.set_special_sequences(&["!!"]).unwrap();
+    settings.special_chars_amount = 2..=3;
+    settings.upper_amount = 3..=4;
+    settings.set_word_transform(|word, _| word.to_uppercase());
+
+    for replace in [false, true] {
+        settings.replace = replace;
+        for password in settings.generate_secure().unwrap() {
+            assert!(!password.to_lowercase().contains("fox"), "{}", *password);
+            assert!(settings.conforms(&password).is_ok(), "{}", *password);
+        }
+    }
+}